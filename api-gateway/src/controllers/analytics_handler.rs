@@ -0,0 +1,114 @@
+use axum::{Extension, Json};
+use axum::body::Body;
+use axum::extract::{Path, Query};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use hyper::StatusCode;
+use proto_definations_snip_sight::generated::url_shortner::{AnalyticsComparisonRequest, AnalyticsRange, InsightExportRequest};
+use tokio_stream::StreamExt;
+use tokio_util::io::ReaderStream;
+use crate::controllers::common::{grpc_connection_error, grpc_json_response, grpc_status_error};
+use crate::controllers::url_shortner_handler::create_grpc_connection;
+use crate::models::authentication_models::Claims;
+use crate::models::responses::ErrorResponse;
+use crate::models::url_shorten_models::{AnalyticsParams, ComparisonParams, ExportParams};
+use crate::services::insight_export::{csv_rows, ParquetExport, CSV_HEADER};
+
+type HandlerResult = Result<(StatusCode, String), (StatusCode, Json<ErrorResponse>)>;
+
+pub async fn get_analytics_summary(Path(id): Path<i32>, Query(params): Query<AnalyticsParams>, Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("get analytics summary request recieved to the gate_way ") ;
+    // the dates are validated by the url shortner service
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = AnalyticsRange {
+        id,
+        user_id: claims.user_id,
+        workspace_id: claims.workspace_id,
+        from: params.from.unwrap_or_default(),
+        to: params.to.unwrap_or_default(),
+    };
+    grpc_json_response(client.get_analytics_summary(request).await, StatusCode::OK).await
+}
+
+async fn compare(id: i32, campaign: bool, params: ComparisonParams, claims: Claims) -> HandlerResult {
+    // the dates are validated by the url shortner service
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = AnalyticsComparisonRequest {
+        id,
+        user_id: claims.user_id,
+        workspace_id: claims.workspace_id,
+        campaign,
+        from: params.from.unwrap_or_default(),
+        to: params.to.unwrap_or_default(),
+        previous_from: params.previous_from.unwrap_or_default(),
+        previous_to: params.previous_to.unwrap_or_default(),
+    };
+    grpc_json_response(client.compare_analytics(request).await, StatusCode::OK).await
+}
+
+pub async fn compare_analytics(Path(id): Path<i32>, Query(params): Query<ComparisonParams>, Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("compare analytics request recieved to the gate_way ") ;
+    compare(id, false, params, claims).await
+}
+
+pub async fn compare_campaign_analytics(Path(id): Path<i32>, Query(params): Query<ComparisonParams>, Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("compare campaign analytics request recieved to the gate_way ") ;
+    compare(id, true, params, claims).await
+}
+
+fn export_error(err: String) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::error!("unable to write the parquet export : {}", err);
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { message: "unable to export the insights".to_string() }))
+}
+
+// the parquet writer works on a plain file, so its writes are kept off the runtime threads
+async fn blocking<T: Send + 'static>(work: impl FnOnce() -> Result<T, String> + Send + 'static) -> Result<T, String> {
+    tokio::task::spawn_blocking(work).await.map_err(|err| err.to_string())?
+}
+
+// the csv is streamed as the pages come in, the parquet file needs its footer so it is sent once all the rows were written
+pub async fn export_insights(Query(params): Query<ExportParams>, Extension(claims): Extension<Claims>) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("export insights request recieved to the gate_way ") ;
+    let format = params.format.unwrap_or("csv".to_string()).to_lowercase();
+    if format != "csv" && format != "parquet" {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { message: "format should be csv or parquet".to_string() })))
+    }
+
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = InsightExportRequest {
+        user_id: claims.user_id,
+        workspace_id: claims.workspace_id,
+        url_id: params.url_id.unwrap_or_default(),
+        from: params.from.unwrap_or_default(),
+        to: params.to.unwrap_or_default(),
+    };
+    let mut stream = match client.export_insights(request).await {
+        Ok(response) => response.into_inner(),
+        Err(status) => return Err(grpc_status_error(status).await),
+    };
+
+    if format == "csv" {
+        // an error half way through can't change the status anymore, the body is cut short instead
+        let rows = stream.map(|batch| batch.map(|batch| csv_rows(&batch.rows)).map_err(Box::new));
+        let body = Body::from_stream(tokio_stream::once(Ok(CSV_HEADER.to_string())).chain(rows));
+        return Ok(([(CONTENT_TYPE, "text/csv"), (CONTENT_DISPOSITION, "attachment; filename=\"insights.csv\"")], body).into_response())
+    }
+
+    let mut export = blocking(ParquetExport::new).await.map_err(export_error)?;
+    loop {
+        match stream.message().await {
+            Ok(Some(batch)) => {
+                export = blocking(move || {
+                    let mut export = export;
+                    export.push(batch.rows)?;
+                    Ok(export)
+                }).await.map_err(export_error)?
+            },
+            Ok(None) => break,
+            Err(status) => return Err(grpc_status_error(status).await),
+        }
+    }
+    let file = tokio::fs::File::from_std(blocking(move || export.finish()).await.map_err(export_error)?);
+    let body = Body::from_stream(ReaderStream::new(file));
+    Ok(([(CONTENT_TYPE, "application/vnd.apache.parquet"), (CONTENT_DISPOSITION, "attachment; filename=\"insights.parquet\"")], body).into_response())
+}
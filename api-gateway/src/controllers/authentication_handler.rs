@@ -1,9 +1,11 @@
-use axum::Form;
+use axum::{Extension, Form, Json};
 use axum::http::HeaderValue;
 use axum::response::IntoResponse;
 use hyper::StatusCode;
 use reqwest::Client;
-use crate::models::authentication_models::{Login, Register};
+use axum::extract::Path;
+use crate::models::authentication_models::{ApiKeyModel, Claims, DigestSettingsModel, Login, Register, WorkspaceMemberModel, WorkspaceModel};
+use crate::models::responses::ErrorResponse;
 use crate::middlewares::response_creator::response_creator;
 pub async fn sign_in(Form(login) :Form<Login>) -> Result<impl IntoResponse, impl IntoResponse> {
     let client = Client::new() ; // authentication-container
@@ -48,4 +50,104 @@ pub async fn sign_up(Form(register) :Form<Register>) -> impl IntoResponse{
             ))
         }
     }
-}
\ No newline at end of file
+}
+
+fn authentication_error(error: reqwest::Error) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::error!(" ---> Error was : {}", error);
+    tracing::error!("Where the error was occurred because of not able to get the response from the authentication server ");
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { message: "Internal Server Error".to_string() }))
+}
+
+// the digest settings live with the users in the authentication service
+pub async fn get_digest_settings(Extension(claims): Extension<Claims>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("get digest settings request recieved to the gate_way ") ;
+    let response = Client::new().get(format!("http://authentication-container:9090/digest-settings/{}", claims.user_id))
+        .send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
+
+pub async fn update_digest_settings(Extension(claims): Extension<Claims>, Form(settings): Form<DigestSettingsModel>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("update digest settings request recieved to the gate_way ") ;
+    let frequency = settings.frequency.unwrap_or("weekly".to_string());
+    if frequency != "daily" && frequency != "weekly" {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { message: "frequency must be daily or weekly".to_string() })))
+    }
+    let response = Client::new().post(format!("http://authentication-container:9090/digest-settings/{}/{}/{}", claims.user_id, settings.enabled, frequency))
+        .send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
+
+// the names and the members come from the users, so they are escaped as path segments
+fn authentication_url(segments: &[&str]) -> String {
+    let mut url = reqwest::Url::parse("http://authentication-container:9090").unwrap();
+    url.path_segments_mut().unwrap().extend(segments);
+    url.to_string()
+}
+
+// the workspaces and their members live with the users too, the url shortener asks it for the roles
+pub async fn get_workspaces(Extension(claims): Extension<Claims>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("get workspaces request recieved to the gate_way ") ;
+    let response = Client::new().get(format!("http://authentication-container:9090/workspaces/{}", claims.user_id))
+        .send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
+
+pub async fn create_workspace(Extension(claims): Extension<Claims>, Form(data): Form<WorkspaceModel>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("create workspace request recieved to the gate_way ") ;
+    let response = Client::new().post(authentication_url(&["workspaces", &claims.user_id.to_string(), data.name.trim()]))
+        .send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
+
+pub async fn get_workspace_members(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("get workspace members request recieved to the gate_way ") ;
+    let response = Client::new().get(format!("http://authentication-container:9090/workspace-members/{}/{}", id, claims.user_id))
+        .send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
+
+pub async fn set_workspace_member(Path(id): Path<i32>, Extension(claims): Extension<Claims>, Form(data): Form<WorkspaceMemberModel>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("set workspace member request recieved to the gate_way ") ;
+    let url = authentication_url(&["workspace-members", &id.to_string(), &claims.user_id.to_string(), data.member.trim(), data.role.trim()]);
+    let response = Client::new().post(url).send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
+
+pub async fn remove_workspace_member(Path((id, member_id)): Path<(i32, i32)>, Extension(claims): Extension<Claims>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("remove workspace member request recieved to the gate_way ") ;
+    let response = Client::new().delete(format!("http://authentication-container:9090/workspace-members/{}/{}/{}", id, claims.user_id, member_id))
+        .send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
+
+// a new token for the workspace comes back in the Authorization header, the client uses it from then on
+pub async fn switch_workspace(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("switch workspace request recieved to the gate_way ") ;
+    let response = Client::new().get(format!("http://authentication-container:9090/switch-workspace/{}/{}", id, claims.user_id))
+        .send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
+
+// every key of the user whichever workspace it is for, the whole key is only in the response of the creation
+pub async fn get_api_keys(Extension(claims): Extension<Claims>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("get api keys request recieved to the gate_way ") ;
+    let response = Client::new().get(format!("http://authentication-container:9090/api-keys/{}", claims.user_id))
+        .send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
+
+// the key works on the active workspace of the token
+pub async fn create_api_key(Extension(claims): Extension<Claims>, Form(data): Form<ApiKeyModel>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("create api key request recieved to the gate_way ") ;
+    let url = authentication_url(&["api-keys", &claims.user_id.to_string(), &claims.workspace_id.to_string(), data.name.trim(),
+        data.scopes.trim(), &data.expires_in_days.unwrap_or_default().to_string()]);
+    let response = Client::new().post(url).send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
+
+pub async fn delete_api_key(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("delete api key request recieved to the gate_way ") ;
+    let response = Client::new().delete(format!("http://authentication-container:9090/api-keys/{}/{}", claims.user_id, id))
+        .send().await.map_err(authentication_error)?;
+    Ok(response_creator(response).await)
+}
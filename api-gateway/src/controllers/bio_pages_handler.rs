@@ -0,0 +1,87 @@
+use axum::{Extension, Form, Json};
+use axum::extract::Path;
+use axum::response::{Html, IntoResponse, Response};
+use hyper::StatusCode;
+use proto_definations_snip_sight::generated::url_shortner::{BioPage, User};
+use validator::Validate;
+use crate::controllers::common::{get_status, grpc_connection_error, grpc_json_response};
+use crate::controllers::url_shortner_handler::create_grpc_connection;
+use crate::models::authentication_models::Claims;
+use crate::models::responses::ErrorResponse;
+use crate::models::url_shorten_models::BioPageModel;
+use crate::services::html_pages::bio_page;
+
+type HandlerResult = Result<(StatusCode, String), (StatusCode, Json<ErrorResponse>)>;
+
+// the slug, title and theme are normalised by the url shortner service, it also checks that the links are the user's
+fn bio_page_request(id: i32, claims: &Claims, data: BioPageModel) -> Result<BioPage, (StatusCode, Json<ErrorResponse>)> {
+    if let Err(error) = data.validate() {
+        tracing::warn!("Failed to validate bio page: {:?}", error);
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { message: error.to_string() })))
+    }
+    let url_ids = data.links.unwrap_or_default().split(',')
+        .map(str::trim).filter(|id| !id.is_empty())
+        .map(|id| id.parse::<i32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(ErrorResponse { message: "links must be comma separated link ids".to_string() })))?;
+    Ok(BioPage {
+        id,
+        user_id: claims.user_id,
+        workspace_id: claims.workspace_id,
+        slug: data.slug,
+        title: data.title,
+        avatar_url: data.avatar_url.unwrap_or_default(),
+        theme: data.theme.unwrap_or_default(),
+        url_ids,
+        ..Default::default()
+    })
+}
+
+pub async fn get_bio_pages(Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("get bio pages request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = User { user_id: claims.user_id, workspace_id: claims.workspace_id, ..Default::default() };
+    grpc_json_response(client.get_bio_pages(request).await, StatusCode::OK).await
+}
+
+pub async fn create_bio_page(Extension(claims): Extension<Claims>, Form(data): Form<BioPageModel>) -> HandlerResult {
+    tracing::info!("create bio page request recieved to the gate_way ") ;
+    let request = bio_page_request(0, &claims, data)?;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    grpc_json_response(client.create_bio_page(request).await, StatusCode::CREATED).await
+}
+
+pub async fn update_bio_page(Path(id): Path<i32>, Extension(claims): Extension<Claims>, Form(data): Form<BioPageModel>) -> HandlerResult {
+    tracing::info!("update bio page request recieved to the gate_way ") ;
+    let request = bio_page_request(id, &claims, data)?;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    grpc_json_response(client.update_bio_page(request).await, StatusCode::OK).await
+}
+
+pub async fn delete_bio_page(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("delete bio page request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = BioPage { id, user_id: claims.user_id, workspace_id: claims.workspace_id, ..Default::default() };
+    grpc_json_response(client.delete_bio_page(request).await, StatusCode::OK).await
+}
+
+// the public page at /p/{slug}, visiting it isn't counted, only the clicks on its links are
+pub async fn public_bio_page(Path(slug): Path<String>) -> Response {
+    tracing::info!("bio page request recieved to the gate_way ") ;
+    match create_grpc_connection().await {
+        Ok(mut client) => {
+            let request = BioPage { slug, ..Default::default() };
+            match client.get_public_bio_page(request).await {
+                Ok(response) => Html(bio_page(&response.into_inner())).into_response(),
+                Err(error) => {
+                    tracing::error!("Error in gRPC server response: {}", error);
+                    get_status(error.code()).await.into_response()
+                }
+            }
+        },
+        Err(err) => {
+            tracing::error!("unable to connect to gRPC : {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
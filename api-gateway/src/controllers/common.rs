@@ -1,5 +1,8 @@
 use axum::http::StatusCode;
+use axum::Json;
+use serde::Serialize;
 use tonic::Code;
+use crate::models::responses::ErrorResponse;
 
 pub async fn get_status(code:Code) -> StatusCode {
     match code {
@@ -11,4 +14,58 @@ pub async fn get_status(code:Code) -> StatusCode {
         tonic::Code::Internal => StatusCode::INTERNAL_SERVER_ERROR,
         _ => StatusCode::INTERNAL_SERVER_ERROR,
     }
-}
\ No newline at end of file
+}
+
+// sends the gRPC response back as the json body, the gRPC errors are mapped to their http status
+pub async fn grpc_json_response<T: Serialize>(response: Result<tonic::Response<T>, tonic::Status>, success: StatusCode) -> Result<(StatusCode, String), (StatusCode, Json<ErrorResponse>)> {
+    match response {
+        Ok(response) => {
+            tracing::info!("Response from gRPC server was successful");
+            Ok((success, serde_json::to_string(&response.into_inner()).unwrap()))
+        },
+        Err(status) => Err(grpc_status_error(status).await)
+    }
+}
+
+// the gRPC error as the http status and the json body
+pub async fn grpc_status_error(status: tonic::Status) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::error!("Error in gRPC server response: {}", status);
+    (
+        get_status(status.code()).await,
+        Json(ErrorResponse {
+            message: status.message().to_string(),
+        })
+    )
+}
+
+// the error response when the gRPC server couldn't be reached
+pub fn grpc_connection_error(err: tonic::transport::Error) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::error!("unable to connect to gRPC : {}", err);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            message: "Error in getting response from gRPC".to_string(),
+        })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use proto_definations_snip_sight::generated::url_shortner::{Urls, UrlsList};
+    use super::*;
+
+    #[tokio::test]
+    async fn times_are_rfc3339_in_the_json() {
+        let urls = UrlsList { list: vec![Urls {
+            id: 7,
+            created_at: "2026-03-01T10:00:00.123456Z".parse().ok(),
+            ..Default::default()
+        }] };
+        let Ok((status, body)) = grpc_json_response(Ok(tonic::Response::new(urls)), StatusCode::OK).await else { panic!("the response was an error") };
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["list"][0]["created_at"], "2026-03-01T10:00:00.123456Z");
+        assert!(json["list"][0]["health_checked_at"].is_null());
+        assert!(json["list"][0].get("legacy_created_at").is_none());
+    }
+}
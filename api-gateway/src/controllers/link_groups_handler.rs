@@ -0,0 +1,80 @@
+use axum::{Extension, Form, Json};
+use axum::extract::Path;
+use hyper::StatusCode;
+use proto_definations_snip_sight::generated::url_shortner::{Folder, Tag, UrlFolder, UrlTags, User};
+use validator::Validate;
+use crate::controllers::common::{grpc_connection_error, grpc_json_response};
+use crate::controllers::url_shortner_handler::create_grpc_connection;
+use crate::models::authentication_models::Claims;
+use crate::models::responses::ErrorResponse;
+use crate::models::url_shorten_models::{FolderModel, UrlFolderModel, UrlTagsModel};
+
+type HandlerResult = Result<(StatusCode, String), (StatusCode, Json<ErrorResponse>)>;
+
+fn user(claims: &Claims) -> User {
+    User {
+        user_id: claims.user_id,
+        workspace_id: claims.workspace_id,
+        ..Default::default()
+    }
+}
+
+pub async fn get_tags(Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("get tags request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    grpc_json_response(client.get_tags(user(&claims)).await, StatusCode::OK).await
+}
+
+pub async fn delete_tag(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("delete tag request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = Tag { id, user_id: claims.user_id, workspace_id: claims.workspace_id, ..Default::default() };
+    grpc_json_response(client.delete_tag(request).await, StatusCode::OK).await
+}
+
+pub async fn set_url_tags(Path(id): Path<i32>, Extension(claims): Extension<Claims>, Form(data): Form<UrlTagsModel>) -> HandlerResult {
+    tracing::info!("set url tags request recieved to the gate_way ") ;
+    // the names are validated and normalised by the url shortner service
+    let tags = data.tags.split(',').map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()).collect();
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = UrlTags { id, user_id: claims.user_id, workspace_id: claims.workspace_id, tags };
+    grpc_json_response(client.set_url_tags(request).await, StatusCode::OK).await
+}
+
+pub async fn get_folders(Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("get folders request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    grpc_json_response(client.get_folders(user(&claims)).await, StatusCode::OK).await
+}
+
+pub async fn create_folder(Extension(claims): Extension<Claims>, Form(data): Form<FolderModel>) -> HandlerResult {
+    tracing::info!("create folder request recieved to the gate_way ") ;
+    if let Err(error) = data.validate() {
+        tracing::warn!("Failed to validate folder: {:?}", error);
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { message: error.to_string() })))
+    }
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = Folder { user_id: claims.user_id, workspace_id: claims.workspace_id, name: data.name, ..Default::default() };
+    grpc_json_response(client.create_folder(request).await, StatusCode::CREATED).await
+}
+
+pub async fn delete_folder(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("delete folder request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = Folder { id, user_id: claims.user_id, workspace_id: claims.workspace_id, ..Default::default() };
+    grpc_json_response(client.delete_folder(request).await, StatusCode::OK).await
+}
+
+pub async fn set_url_folder(Path(id): Path<i32>, Extension(claims): Extension<Claims>, Form(data): Form<UrlFolderModel>) -> HandlerResult {
+    tracing::info!("set url folder request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = UrlFolder { id, user_id: claims.user_id, workspace_id: claims.workspace_id, folder_id: data.folder_id.unwrap_or_default() };
+    grpc_json_response(client.set_url_folder(request).await, StatusCode::OK).await
+}
+
+pub async fn get_campaign_insights(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("campaign insights request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = Folder { id, user_id: claims.user_id, workspace_id: claims.workspace_id, ..Default::default() };
+    grpc_json_response(client.get_campaign_insights(request).await, StatusCode::OK).await
+}
@@ -1,3 +1,7 @@
 pub mod authentication_handler;
 pub mod url_shortner_handler;
+pub mod link_groups_handler;
+pub mod analytics_handler;
+pub mod webhooks_handler;
+pub mod bio_pages_handler;
 pub mod common;
\ No newline at end of file
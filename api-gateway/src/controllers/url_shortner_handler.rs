@@ -1,20 +1,36 @@
 use axum::{Extension, Form, Json};
 use axum::extract::{Path, Query};
-use axum::response::IntoResponse;
+use axum::response::{Html, IntoResponse, Response};
 use hyper::StatusCode;
-use proto_definations_snip_sight::generated::url_shortner::{CreateShortenUrlPayload, CustomName, UrlId, User, Url, GetInsights};
+use proto_definations_snip_sight::generated::url_shortner::{ActivationWindow, CreateShortenUrlPayload, CustomDomain, CustomName, UrlId, User, Url, GetInsights, SocialPreview};
 use proto_definations_snip_sight::generated::url_shortner::url_shortner_service_client::UrlShortnerServiceClient;
 use tonic::transport::{Channel, Error};
 use crate::middlewares::url_shortner_middlewares::validate_url_shortner_name;
 use crate::models::authentication_models::Claims;
 use crate::models::responses::ErrorResponse;
-use crate::models::url_shorten_models::{Insight, InsightEvent, KeyInsights, PaginationParams, UrlShortenModel};
-use aws_sdk_sqs::{Client};
-use serde_json::to_string;
-use aws_config::BehaviorVersion;
-use crate::controllers::common::get_status;
+use crate::models::url_shorten_models::{ActivationWindowModel, CustomDomainModel, DomainParams, Insight, InsightFilterParams, KeyInsights, KeyInsightsPath, PaginationParams, SocialPreviewModel, SocialUnfurler, UrlShortenModel};
+use crate::services::custom_domains::request_domain;
+use snipsight_events::insights::insight_key;
+use axum::http::HeaderMap;
+use snipsight_events::events::{CreateInsight, Envelope, SnipSightEvent};
+use snipsight_events::publisher::BufferedPublisher;
+use crate::controllers::common::{get_status, grpc_connection_error, grpc_json_response};
+use crate::services::html_pages::{coming_soon_page, link_ended_page, link_preview_page, social_preview_page, unsafe_link_page};
+use axum::http::header::CACHE_CONTROL;
+use validator::Validate;
 
-async fn create_grpc_connection() -> Result<UrlShortnerServiceClient<Channel>, Error> {
+// what the visitors get before and after the activation window of the link, None when the link is live
+fn outside_window_response(shorten_url: &str, url: &Url) -> Option<Response> {
+    match url.activation_state.as_str() {
+        "upcoming" if !url.fallback_url.is_empty() => Some(axum::response::Redirect::temporary(&url.fallback_url).into_response()),
+        // the page changes at the launch, so it shouldn't be cached anywhere
+        "upcoming" => Some(([(CACHE_CONTROL, "no-store")], Html(coming_soon_page(shorten_url, &url.coming_soon_message, &url.active_from))).into_response()),
+        "ended" => Some((StatusCode::GONE, Html(link_ended_page(shorten_url))).into_response()),
+        _ => None
+    }
+}
+
+pub async fn create_grpc_connection() -> Result<UrlShortnerServiceClient<Channel>, Error> {
     UrlShortnerServiceClient::connect("http://url-shortner-container:9091").await
 }
 
@@ -30,8 +46,15 @@ pub async fn create_shorten_url(Extension(claims): Extension<Claims>, Form(data)
             // Creating a Request
             let request = tonic::Request::new( CreateShortenUrlPayload{
                 user_id: claims.user_id,
+                workspace_id: claims.workspace_id,
                 custom_url: name,
                 original_url: data.original_url,
+                fetch_social_preview: data.fetch_social_preview.unwrap_or(false),
+                domain: data.domain.unwrap_or_default(),
+                active_from: data.active_from.unwrap_or_default(),
+                active_until: data.active_until.unwrap_or_default(),
+                fallback_url: data.fallback_url.unwrap_or_default(),
+                coming_soon_message: data.coming_soon_message.unwrap_or_default(),
             }) ;
             // sending the request
             let response = client_channel.create_shorten_url(request).await ;
@@ -85,6 +108,9 @@ pub async fn get_urls(Query(params): Query<PaginationParams>,Extension(claims):
                     page_size: params.page_size.unwrap_or(5),
                     page_number: params.page_number.unwrap_or(1),
                     user_id: claims.user_id,
+                    workspace_id: claims.workspace_id,
+                    tag: params.tag.unwrap_or_default(),
+                    folder_id: params.folder_id.unwrap_or_default(),
                 }
             );
 
@@ -135,6 +161,7 @@ pub async fn delete_url(Path(id): Path<i32>,Extension(claims): Extension<Claims>
            let request = tonic::Request::new(
                UrlId {
                    user_id: claims.user_id,
+                   workspace_id: claims.workspace_id,
                    id
                }
            ) ;
@@ -178,8 +205,15 @@ pub async fn delete_url(Path(id): Path<i32>,Extension(claims): Extension<Claims>
 
 
 
-pub async fn redirect_url(Path(shorten_url): Path<String>,Extension(insights): Extension<Insight>) -> impl IntoResponse {
+pub async fn redirect_url(Path(shorten_url): Path<String>,Extension(insights): Extension<Insight>, Extension(events): Extension<BufferedPublisher>, unfurler: Option<Extension<SocialUnfurler>>, headers: HeaderMap) -> impl IntoResponse {
     tracing::info!("redirect url request recieved to the gate_way ") ;
+    // the host decides which domain's short url it was, custom domains have their own codes
+    let domain = request_domain(&headers) ;
+
+    if unfurler.is_some() {
+        // unfurlers are not visitors, so no count and no insight for them
+        return social_preview(shorten_url, domain).await
+    }
 
     match validate_url_shortner_name(&shorten_url)  {
         Ok(_) => {
@@ -190,6 +224,8 @@ pub async fn redirect_url(Path(shorten_url): Path<String>,Extension(insights): E
                     let request = tonic::Request::new(
                         Url {
                             url: shorten_url.clone(),
+                            domain: domain.clone(),
+                            ..Default::default()
                         }
                     ) ;
 
@@ -198,10 +234,22 @@ pub async fn redirect_url(Path(shorten_url): Path<String>,Extension(insights): E
                         Ok(response) => {
                             tracing::info!("Response from gRPC server: {:?}", response);
 
+                            // flagged destinations get the warning, not counted and no insight for them
+                            if !response.get_ref().disabled_reason.is_empty() {
+                                tracing::warn!("the destination of {} was disabled : {}", shorten_url, response.get_ref().disabled_reason) ;
+                                return (StatusCode::FORBIDDEN, Html(unsafe_link_page(&shorten_url, &response.get_ref().url, &response.get_ref().disabled_reason))).into_response()
+                            }
+                            // outside the activation window, not counted either
+                            if let Some(page) = outside_window_response(&shorten_url, response.get_ref()) {
+                                return page
+                            }
+
                             // Now we are going to store the count
                             let request = tonic::Request::new(
                                 Url {
                                     url: shorten_url.clone(),
+                                    domain: domain.clone(),
+                                    ..Default::default()
                                 }
                             ) ;
 
@@ -211,29 +259,20 @@ pub async fn redirect_url(Path(shorten_url): Path<String>,Extension(insights): E
                                 Ok(response1) => {
                                     if response1.into_inner().operation {
                                         tracing::info!("count incremented successfully") ;
-                                        // here we are going to call the SQS by passing the following insights
-
-                                        // Load AWS credentials from environment or EC2 IAM role
-                                        let config = aws_config::load_defaults(BehaviorVersion::v2025_01_17()).await;
-                                        let client = Client::new(&config);
-
-                                        // Your FIFO queue URL (get this from AWS Console)
-                                        let queue_url = "https://sqs.ap-south-1.amazonaws.com/637423550786/snipsightmessages.fifo";
-                                        let insights_event = InsightEvent::new(shorten_url,insights.ip_address,insights.refferal,insights.device_type,insights.browser,insights.os) ;
-                                        let message_body = to_string(&insights_event).unwrap();
-                                        let resp = client.send_message()
-                                            .queue_url(queue_url).message_body(message_body).message_group_id("insight-event")
-                                            .message_deduplication_id(uuid::Uuid::new_v4().to_string()).send().await; // all the messages of type insight-event go down the same lane, so when ever we are sending a create insight , we need send with the same name
-                                        match resp {
-                                            Ok(message) => {
-                                                tracing::info!("Message sent successfully the id was {}", message.message_id.unwrap()) ;
-                                            },
-                                            Err(error) => {
-                                                tracing::error!("Error in sending message to SQS: {:?}", error);
-                                                tracing::error!("Error Type was {:?}", error.raw_response()) ;
-                                                tracing::info!("Message not sent successfully, But Redirection will takes place") ;
-                                            }
-                                        };
+                                        // the event only goes in the buffer, the redirection doesn't wait for the queue
+                                        let insights_event = Envelope::new(SnipSightEvent::CreateInsight(CreateInsight {
+                                            shorten_url: insight_key(&shorten_url, &response.get_ref().domain),
+                                            ip_address: insights.ip_address,
+                                            referral_source: insights.refferal,
+                                            source: insights.source,
+                                            channel: insights.channel,
+                                            device_type: insights.device_type,
+                                            browser: insights.browser,
+                                            os: insights.os,
+                                        })) ;
+                                        if !events.publish(insights_event.to_message()) {
+                                            tracing::info!("Message not sent successfully, But Redirection will takes place") ;
+                                        }
                                         axum::response::Redirect::temporary(&response.into_inner().url).into_response()
                                     }else{
                                         StatusCode::NOT_FOUND.into_response()
@@ -268,96 +307,415 @@ pub async fn redirect_url(Path(shorten_url): Path<String>,Extension(insights): E
 }
 
 
-pub async fn get_key_insights(Path((shorten_url, page_size, last_evaluated_key)):Path<(String, u32, String)>, Query(params):Query<PaginationParams>, Extension(claims):Extension<Claims>) -> Result<impl IntoResponse,impl IntoResponse> {
+async fn social_preview(shorten_url: String, domain: String) -> Response {
+    if let Err(error) = validate_url_shortner_name(&shorten_url) {
+        tracing::error!("error occured in social preview for invalid shorten url name : {}", error) ;
+        return StatusCode::NOT_FOUND.into_response()
+    }
+
+    match create_grpc_connection().await {
+        Ok(mut client) => {
+            let request = tonic::Request::new(
+                Url {
+                    url: shorten_url.clone(),
+                    domain,
+                    ..Default::default()
+                }
+            ) ;
+
+            match client.get_social_preview(request).await {
+                Ok(response) => {
+                    tracing::info!("Response from gRPC server: {:?}", response);
+                    let preview = response.into_inner() ;
+                    if let Some(response) = outside_window_response(&shorten_url, &preview.window.unwrap_or_default()) {
+                        return response
+                    }
+                    Html(social_preview_page(&preview.original_url, &preview.title, &preview.description, &preview.image_url)).into_response()
+                },
+                Err(error) => {
+                    tracing::error!("Error in gRPC server response: {}", error);
+                    get_status(error.code()).await.into_response()
+                }
+            }
+        },
+        Err(err) => {
+            tracing::error!("unable to connect to gRPC : {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+pub async fn get_link_health(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("link health request recieved to the gate_way ") ;
+    match create_grpc_connection().await {
+        Ok(mut client) => {
+            let request = tonic::Request::new(
+                UrlId {
+                    user_id: claims.user_id,
+                    workspace_id: claims.workspace_id,
+                    id
+                }
+            ) ;
 
-    if last_evaluated_key.is_empty() {
-        return Err(
-            (
-                StatusCode::BAD_REQUEST,
+            match client.get_link_health(request).await {
+                Ok(response) => {
+                    tracing::info!("Response from gRPC server: {:?}", response);
+                    Ok(
+                        (
+                            StatusCode::OK,
+                            serde_json::to_string(&response.into_inner()).unwrap()
+                        )
+                    )
+                },
+                Err(status) => {
+                    tracing::error!("Error in gRPC server response: {}", status);
+                    Err((
+                        get_status(status.code()).await,
+                        Json(ErrorResponse{
+                            message: status.message().to_string(),
+                        })
+                    ))
+                }
+            }
+        },
+        Err(err) => {
+            tracing::error!("unable to connect to gRPC : {}", err);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
                 Json(
                     ErrorResponse {
-                        message: "Mention Last evaluated Key".to_string(),
+                        message: "Error in getting response from gRPC".to_string(),
                     }
                 )
-                )
-        )
+            ))
+        }
     }
+}
 
-    match validate_url_shortner_name(&shorten_url) {
-        Ok(_) => {
-            if page_size < 1 || page_size > 100 {
-                return Err(
-                    (
-                        StatusCode::BAD_REQUEST,
-                        Json(
-                            ErrorResponse {
-                                message: "Page size should be between 1 and 100".to_string(),
-                            }
+pub async fn update_activation_window(Path(id): Path<i32>, Extension(claims): Extension<Claims>, Form(data): Form<ActivationWindowModel>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("update activation window request recieved to the gate_way ") ;
+
+    if let Err(error) = data.validate() {
+        tracing::warn!("Failed to validate activation window data: {:?}", error);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                message: error.to_string(),
+            })
+        ))
+    }
+
+    match create_grpc_connection().await {
+        Ok(mut client) => {
+            let request = tonic::Request::new(
+                ActivationWindow {
+                    id,
+                    user_id: claims.user_id,
+                    workspace_id: claims.workspace_id,
+                    active_from: data.active_from.unwrap_or_default(),
+                    active_until: data.active_until.unwrap_or_default(),
+                    fallback_url: data.fallback_url.unwrap_or_default(),
+                    coming_soon_message: data.coming_soon_message.unwrap_or_default(),
+                }
+            ) ;
+
+            match client.update_activation_window(request).await {
+                Ok(response) => {
+                    tracing::info!("Response from gRPC server: {:?}", response);
+                    Ok(
+                        (
+                            StatusCode::OK,
+                            serde_json::to_string(&response.into_inner()).unwrap()
                         )
                     )
+                },
+                Err(status) => {
+                    tracing::error!("Error in gRPC server response: {}", status);
+                    Err((
+                        get_status(status.code()).await,
+                        Json(ErrorResponse{
+                            message: status.message().to_string(),
+                        })
+                    ))
+                }
+            }
+        },
+        Err(err) => {
+            tracing::error!("unable to connect to gRPC : {}", err);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(
+                    ErrorResponse {
+                        message: "Error in getting response from gRPC".to_string(),
+                    }
                 )
+            ))
+        }
+    }
+}
+
+pub async fn update_social_preview(Path(id): Path<i32>, Extension(claims): Extension<Claims>, Form(data): Form<SocialPreviewModel>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("update social preview request recieved to the gate_way ") ;
+
+    if let Err(error) = data.validate() {
+        tracing::warn!("Failed to validate social preview data: {:?}", error);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                message: error.to_string(),
+            })
+        ))
+    }
+
+    match create_grpc_connection().await {
+        Ok(mut client) => {
+            let request = tonic::Request::new(
+                SocialPreview {
+                    id,
+                    user_id: claims.user_id,
+                    workspace_id: claims.workspace_id,
+                    title: data.title.unwrap_or_default(),
+                    description: data.description.unwrap_or_default(),
+                    image_url: data.image_url.unwrap_or_default(),
+                    original_url: String::new(),
+                    window: None,
+                }
+            ) ;
+
+            match client.update_social_preview(request).await {
+                Ok(response) => {
+                    tracing::info!("Response from gRPC server: {:?}", response);
+                    Ok(
+                        (
+                            StatusCode::OK,
+                            serde_json::to_string(&response.into_inner()).unwrap()
+                        )
+                    )
+                },
+                Err(status) => {
+                    tracing::error!("Error in gRPC server response: {}", status);
+                    Err((
+                        get_status(status.code()).await,
+                        Json(ErrorResponse{
+                            message: status.message().to_string(),
+                        })
+                    ))
+                }
             }
+        },
+        Err(err) => {
+            tracing::error!("unable to connect to gRPC : {}", err);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(
+                    ErrorResponse {
+                        message: "Error in getting response from gRPC".to_string(),
+                    }
+                )
+            ))
+        }
+    }
+}
 
-            // we are going to send the request
+// renders the preview page for `/{shorten_url}+`, nothing is counted or sent as an insight over here
+pub async fn preview_url(shorten_url: String, domain: String) -> Response {
+    tracing::info!("preview url request recieved to the gate_way ") ;
 
-            let client = create_grpc_connection().await ;
-            match client {
-                Ok(mut client) => {
-                    let request = tonic::Request::new(GetInsights{
-                        last_evaluated_key,
-                        shorten_url,
-                        page_size
-                    }) ;
+    if let Err(error) = validate_url_shortner_name(&shorten_url) {
+        tracing::error!("error occured in preview for invalid shorten url name : {}", error) ;
+        return StatusCode::NOT_FOUND.into_response()
+    }
 
-                    let response = client.get_key_insights(request).await ;
-                    match response {
-                        Ok(response) => {
-                            tracing::info!("Response from gRPC server: {:?}", response);
-                            Ok(
-                                (
-                                    StatusCode::OK,
-                                    to_string(&response.into_inner()).unwrap()
-                                )
-                            )
-                        },
-                        Err(error) => {
-                            tracing::error!("Error in gRPC server response: {}", error);
-                            Err((
-                                get_status(error.code()).await,
-                                Json(ErrorResponse{
-                                    message: error.message().to_string(),
-                                })
-                            ))
-                        }
+    match create_grpc_connection().await {
+        Ok(mut client) => {
+            let request = tonic::Request::new(
+                Url {
+                    url: shorten_url.clone(),
+                    domain,
+                    ..Default::default()
+                }
+            ) ;
+
+            match client.get_url_preview(request).await {
+                Ok(response) => {
+                    tracing::info!("Response from gRPC server: {:?}", response);
+                    let details = response.into_inner() ;
+                    if !details.disabled_reason.is_empty() {
+                        return (StatusCode::FORBIDDEN, Html(unsafe_link_page(&shorten_url, &details.original_url, &details.disabled_reason))).into_response()
+                    }
+                    if let Some(response) = outside_window_response(&shorten_url, &details.window.unwrap_or_default()) {
+                        return response
                     }
+                    let created_at = details.created_at.map(|at| at.to_string()).unwrap_or_default() ;
+                    Html(link_preview_page(&shorten_url, &details.original_url, &created_at)).into_response()
                 },
                 Err(error) => {
-                    tracing::error!("unable to connect to gRPC : {}", error);
-                    return Err(
+                    tracing::error!("Error in gRPC server response: {}", error);
+                    get_status(error.code()).await.into_response()
+                }
+            }
+        },
+        Err(err) => {
+            tracing::error!("unable to connect to gRPC : {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+pub async fn get_key_insights(Path(path):Path<KeyInsightsPath>, Query(filters):Query<InsightFilterParams>, Query(domain_params):Query<DomainParams>, Extension(claims):Extension<Claims>) -> Result<(StatusCode, String), (StatusCode, Json<ErrorResponse>)> {
+    if let Err(error) = validate_url_shortner_name(&path.shorten_url) {
+        tracing::error!("error occured in key insights for invalid shorten url name : {}", error) ;
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { message: "Invalid Shorten Url".to_string() })))
+    }
+    if path.page_size < 1 || path.page_size > 100 {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { message: "Page size should be between 1 and 100".to_string() })))
+    }
+
+    // the range, filters and token are validated by the url shortner service
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = tonic::Request::new(GetInsights{
+        page_token: path.page_token.unwrap_or_default(),
+        shorten_url: path.shorten_url,
+        page_size: path.page_size,
+        domain: domain_params.domain.unwrap_or_default(),
+        from: filters.from.unwrap_or_default(),
+        to: filters.to.unwrap_or_default(),
+        browser: filters.browser.unwrap_or_default(),
+        os: filters.os.unwrap_or_default(),
+        device_type: filters.device_type.unwrap_or_default(),
+        referrer: filters.referrer.unwrap_or_default(),
+        location: filters.location.unwrap_or_default(),
+        user_id: claims.user_id,
+        workspace_id: claims.workspace_id,
+    }) ;
+    grpc_json_response(client.get_key_insights(request).await, StatusCode::OK).await
+}
+
+
+pub async fn add_custom_domain(Extension(claims): Extension<Claims>, Form(data): Form<CustomDomainModel>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("add custom domain request recieved to the gate_way ") ;
+
+    if let Err(error) = data.validate() {
+        tracing::warn!("Failed to validate custom domain: {:?}", error);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                message: error.to_string(),
+            })
+        ))
+    }
+
+    let request = tonic::Request::new(
+        CustomDomain {
+            user_id: claims.user_id,
+            workspace_id: claims.workspace_id,
+            domain: data.domain,
+            ..Default::default()
+        }
+    ) ;
+    custom_domain_request(request, true).await
+}
+
+pub async fn verify_custom_domain(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("verify custom domain request recieved to the gate_way ") ;
+    let request = tonic::Request::new(
+        CustomDomain {
+            id,
+            user_id: claims.user_id,
+            workspace_id: claims.workspace_id,
+            ..Default::default()
+        }
+    ) ;
+    custom_domain_request(request, false).await
+}
+
+async fn custom_domain_request(request: tonic::Request<CustomDomain>, add: bool) -> Result<(StatusCode, String), (StatusCode, Json<ErrorResponse>)> {
+    match create_grpc_connection().await {
+        Ok(mut client) => {
+            let response = if add {
+                client.add_custom_domain(request).await
+            } else {
+                client.verify_custom_domain(request).await
+            };
+            match response {
+                Ok(response) => {
+                    tracing::info!("Response from gRPC server: {:?}", response);
+                    Ok(
                         (
-                            StatusCode::INTERNAL_SERVER_ERROR,
-                            Json(
-                                ErrorResponse {
-                                    message: "Error in getting response from gRPC".to_string(),
-                                }
-                            )
+                            if add { StatusCode::CREATED } else { StatusCode::OK },
+                            serde_json::to_string(&response.into_inner()).unwrap()
                         )
                     )
+                },
+                Err(status) => {
+                    tracing::error!("Error in gRPC server response: {}", status);
+                    Err((
+                        get_status(status.code()).await,
+                        Json(ErrorResponse{
+                            message: status.message().to_string(),
+                        })
+                    ))
                 }
             }
         },
-        Err(error) => {
-            tracing::error!("error occured in redirection for invalid shorten url name : {}", error) ;
-            return Err(
-                (
-                    StatusCode::BAD_REQUEST,
-                    Json(
-                        ErrorResponse {
-                            message: "Invalid Shorten Url".to_string(),
-                        }
+        Err(err) => {
+            tracing::error!("unable to connect to gRPC : {}", err);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(
+                    ErrorResponse {
+                        message: "Error in getting response from gRPC".to_string(),
+                    }
+                )
+            ))
+        }
+    }
+}
+
+pub async fn get_custom_domains(Extension(claims): Extension<Claims>) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    tracing::info!("get custom domains request recieved to the gate_way ") ;
+    match create_grpc_connection().await {
+        Ok(mut client) => {
+            let request = tonic::Request::new(
+                User {
+                    user_id: claims.user_id,
+                    workspace_id: claims.workspace_id,
+                    page_number: 1,
+                    page_size: 0,
+                    ..Default::default()
+                }
+            ) ;
+            match client.get_custom_domains(request).await {
+                Ok(response) => {
+                    tracing::info!("Response from gRPC server: {:?}", response);
+                    Ok(
+                        (
+                            StatusCode::OK,
+                            serde_json::to_string(&response.into_inner()).unwrap()
+                        )
                     )
+                },
+                Err(status) => {
+                    tracing::error!("Error in gRPC server response: {}", status);
+                    Err((
+                        get_status(status.code()).await,
+                        Json(ErrorResponse{
+                            message: status.message().to_string(),
+                        })
+                    ))
+                }
+            }
+        },
+        Err(err) => {
+            tracing::error!("unable to connect to gRPC : {}", err);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(
+                    ErrorResponse {
+                        message: "Error in getting response from gRPC".to_string(),
+                    }
                 )
-            )
+            ))
         }
     }
 }
@@ -0,0 +1,80 @@
+use axum::{Extension, Form, Json};
+use axum::extract::Path;
+use hyper::StatusCode;
+use proto_definations_snip_sight::generated::url_shortner::{User, WebhookEndpoint, WebhookRule};
+use validator::Validate;
+use crate::controllers::common::{grpc_connection_error, grpc_json_response};
+use crate::controllers::url_shortner_handler::create_grpc_connection;
+use crate::models::authentication_models::Claims;
+use crate::models::responses::ErrorResponse;
+use crate::models::url_shorten_models::{WebhookEndpointModel, WebhookRuleModel};
+
+type HandlerResult = Result<(StatusCode, String), (StatusCode, Json<ErrorResponse>)>;
+
+fn user(claims: &Claims) -> User {
+    User {
+        user_id: claims.user_id,
+        workspace_id: claims.workspace_id,
+        ..Default::default()
+    }
+}
+
+pub async fn get_webhook_endpoints(Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("get webhook endpoints request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    grpc_json_response(client.get_webhook_endpoints(user(&claims)).await, StatusCode::OK).await
+}
+
+pub async fn create_webhook_endpoint(Extension(claims): Extension<Claims>, Form(data): Form<WebhookEndpointModel>) -> HandlerResult {
+    tracing::info!("create webhook endpoint request recieved to the gate_way ") ;
+    if let Err(error) = data.validate() {
+        tracing::warn!("Failed to validate webhook endpoint: {:?}", error);
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { message: error.to_string() })))
+    }
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = WebhookEndpoint { user_id: claims.user_id, workspace_id: claims.workspace_id, url: data.url, ..Default::default() };
+    grpc_json_response(client.create_webhook_endpoint(request).await, StatusCode::CREATED).await
+}
+
+pub async fn delete_webhook_endpoint(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("delete webhook endpoint request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = WebhookEndpoint { id, user_id: claims.user_id, workspace_id: claims.workspace_id, ..Default::default() };
+    grpc_json_response(client.delete_webhook_endpoint(request).await, StatusCode::OK).await
+}
+
+pub async fn get_webhook_rules(Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("get webhook rules request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    grpc_json_response(client.get_webhook_rules(user(&claims)).await, StatusCode::OK).await
+}
+
+pub async fn create_webhook_rule(Extension(claims): Extension<Claims>, Form(data): Form<WebhookRuleModel>) -> HandlerResult {
+    tracing::info!("create webhook rule request recieved to the gate_way ") ;
+    // the rule is validated by the url shortner service, it knows the limits of each type
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = WebhookRule {
+        user_id: claims.user_id,
+        workspace_id: claims.workspace_id,
+        url_id: data.url_id.unwrap_or_default(),
+        rule_type: data.rule_type,
+        threshold: data.threshold.unwrap_or_default(),
+        spike_factor: data.spike_factor.unwrap_or_default(),
+        days: data.days.unwrap_or_default(),
+        ..Default::default()
+    };
+    grpc_json_response(client.create_webhook_rule(request).await, StatusCode::CREATED).await
+}
+
+pub async fn delete_webhook_rule(Path(id): Path<i32>, Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("delete webhook rule request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    let request = WebhookRule { id, user_id: claims.user_id, workspace_id: claims.workspace_id, ..Default::default() };
+    grpc_json_response(client.delete_webhook_rule(request).await, StatusCode::OK).await
+}
+
+pub async fn get_webhook_deliveries(Extension(claims): Extension<Claims>) -> HandlerResult {
+    tracing::info!("get webhook deliveries request recieved to the gate_way ") ;
+    let mut client = create_grpc_connection().await.map_err(grpc_connection_error)?;
+    grpc_json_response(client.get_webhook_deliveries(user(&claims)).await, StatusCode::OK).await
+}
@@ -4,12 +4,15 @@ mod controllers;
 mod services;
 mod models;
 
-use std::sync::Arc;
-use axum::{middleware, Router};
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use axum::{middleware, Extension, Router};
 use axum::http::{HeaderValue};
 use axum::routing::get;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use crate::controllers::url_shortner_handler::redirect_url;
+use crate::controllers::bio_pages_handler::public_bio_page;
 use crate::middlewares::authentication_middlewares::authorization_check;
 use crate::routes::authentication_routes::authentication_routes;
 use crate::routes::file_sharing_routes::file_sharing_routes;
@@ -18,12 +21,18 @@ use crate::routes::url_shortner_routes::{url_shortner_routes};
 use aws_config::meta::region::RegionProviderChain;
 use aws_sdk_ssm::Client;
 use user_agent_parser::UserAgentParser;
-use crate::middlewares::url_shortner_middlewares::redirection_data_gathering;
+use crate::middlewares::url_shortner_middlewares::{link_preview_interception, redirection_data_gathering};
+use crate::services::custom_domains::{is_allowed_origin, refresh_custom_domains, CustomDomains};
+use crate::services::name_blocklist::refresh_blocked_names;
+use snipsight_events::publisher::BufferedPublisher;
+use snipsight_events::publisher_from_env;
 
 #[derive(Clone)]
 pub struct AppState {
     pub secret_key: String,
     pub user_agent: Arc<UserAgentParser>,
+    pub custom_domains: CustomDomains,
+    pub events: BufferedPublisher,
 }
 
 #[tokio::main]
@@ -32,13 +41,20 @@ async fn main() {
 
     tracing_subscriber::fmt::init();
 
-    // Build the CORS layer
+    let custom_domains: CustomDomains = Arc::new(RwLock::new(HashSet::new()));
+    tokio::spawn(refresh_custom_domains(custom_domains.clone()));
+    tokio::spawn(refresh_blocked_names());
+
+    // Build the CORS layer, our web app along with the verified custom domains of the users
+    let allowed_domains = custom_domains.clone();
     let cors = CorsLayer::new()
-        .allow_origin("https://web.snipsight.phani.services".parse::<HeaderValue>().unwrap()) // You can use `Exact` or `AllowOrigin::predicate(...)` for specific domains
+        .allow_origin(AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+            origin.to_str().map(|origin| is_allowed_origin(origin, &allowed_domains.read().unwrap())).unwrap_or(false)
+        }))
         .allow_methods(Any)
         .allow_headers(Any);
 
-    let app = routes(cors).await ;
+    let app = routes(cors, custom_domains).await ;
     tracing::info!("built the Router") ;
 
     tracing::info!("Going to start the server") ;
@@ -52,14 +68,17 @@ async fn main() {
 
 
 
-async fn routes(cors_layer: CorsLayer) -> Router {
+async fn routes(cors_layer: CorsLayer, custom_domains: CustomDomains) -> Router {
     let user_agent_parser = Arc::new(UserAgentParser::from_str(include_str!("regexes.yaml")).unwrap_or_else(|_| {
         tracing::error!("Failed to initialize UserAgentParser. Ensure YAML definitions are correct or crate provides defaults.");
         // Fallback to a default or panic if initialization is critical
         UserAgentParser::from_str("").expect("Fallback UserAgentParser initialization failed")
     }));
     let secret = get_jwt_secret().await;
-    let app_state = AppState { secret_key: secret, user_agent: user_agent_parser} ;
+    // the redirects only put the insight events in the buffer, they are sent in batches of 10 from the background
+    let config = aws_config::load_defaults(aws_config::BehaviorVersion::v2025_01_17()).await;
+    let events = BufferedPublisher::spawn(publisher_from_env(&config), 10_000, Duration::from_millis(200));
+    let app_state = AppState { secret_key: secret, user_agent: user_agent_parser, custom_domains, events} ;
     let protected_routes = Router::new()
         .nest("/url-shortner", url_shortner_routes())
         .nest("/file-sharing", file_sharing_routes())
@@ -68,7 +87,11 @@ async fn routes(cors_layer: CorsLayer) -> Router {
 
     let public_routes = Router::new()
         .nest("/authentication", authentication_routes())
-        .route("/{shorten_url}", get(redirect_url).layer(middleware::from_fn_with_state(app_state.clone(),redirection_data_gathering)));
+        .route("/p/{slug}", get(public_bio_page)) // the link-in-bio pages
+        .route("/{shorten_url}", get(redirect_url)
+            .layer(middleware::from_fn_with_state(app_state.clone(),redirection_data_gathering))
+            .layer(middleware::from_fn(link_preview_interception))) // `/{shorten_url}+` is the link preview
+        .layer(Extension(app_state.events.clone())); // the redirects publish the insight events
 
     Router::new()
         .merge(public_routes)
@@ -1,9 +1,9 @@
-use std::sync::Arc;
+use std::sync::LazyLock;
 use axum::middleware::Next;
 use axum::{extract::{Request}, response::Response} ;
 use axum::body::{to_bytes, Body, Bytes};
 use axum::extract::State;
-use axum::http::{Method, StatusCode};
+use axum::http::{HeaderValue, Method, StatusCode};
 use axum::response::IntoResponse;
 use crate::models::responses::ErrorResponse;
 use jsonwebtoken::{decode, DecodingKey, Validation};
@@ -25,6 +25,19 @@ pub async fn authorization_check(State(state): State<AppState>, mut req: Request
     let jwt_secret = state.secret_key ;
     let secret = String::from(jwt_secret.as_str());
     match req.headers().get("Authorization") {
+        Some(header) if header.to_str().is_ok_and(|header| header.starts_with("ApiKey ")) => {
+            let claims = check_api_key(header.clone()).await?;
+            let Some(scope) = required_scope(req.method(), req.uri().path()) else {
+                tracing::info!("{} can't be called with an API key", req.uri().path());
+                return Err((StatusCode::FORBIDDEN, Json(ErrorResponse{message: String::from("API keys can't be used for this, please sign in")})))
+            };
+            if !claims.scopes.as_ref().is_some_and(|scopes| scopes.iter().any(|s| s == scope)) {
+                tracing::info!("API key of the user {} doesn't have the scope {}", claims.user_id, scope);
+                return Err((StatusCode::FORBIDDEN, Json(ErrorResponse{message: format!("API key doesn't have the {} scope", scope)})))
+            }
+            req.extensions_mut().insert(claims);
+            Ok(next.run(req).await)
+        },
         Some(header) => {
             tracing::info!("Authorization header was provided {}", header.to_str().unwrap());
             let header_string ;
@@ -39,7 +52,7 @@ pub async fn authorization_check(State(state): State<AppState>, mut req: Request
             match result {
                 Ok(claims) => {
                     tracing::info!("claims: {:?}", claims);
-                    req.extensions_mut().insert(Claims {user_id: claims.0, username: claims.1});
+                    req.extensions_mut().insert(claims);
                     Ok(next.run(req).await)
                 },
                 Err(error) => {
@@ -57,7 +70,7 @@ pub async fn authorization_check(State(state): State<AppState>, mut req: Request
     }
 }
 
-pub async fn check_authorization_header(jwt_secret: String, header: String) -> Result<(i32, String), bool> {
+pub async fn check_authorization_header(jwt_secret: String, header: String) -> Result<Claims, bool> {
     let result = decode::<Claims>(
         header.as_ref(),
         &DecodingKey::from_secret(jwt_secret.as_ref()),
@@ -67,7 +80,10 @@ pub async fn check_authorization_header(jwt_secret: String, header: String) -> R
     match result {
         Ok(token_data) => {
             tracing::info!("Authorization header decoded successfully");
-            Ok((token_data.claims.user_id,token_data.claims.username))
+            let claims = token_data.claims;
+            // the older tokens are for the personal workspace, which has the user id as its id
+            let workspace_id = if claims.workspace_id == 0 { claims.user_id } else { claims.workspace_id };
+            Ok(Claims { workspace_id, ..claims })
         },
         Err(error) => {
             tracing::error!("error was {:?}", error);
@@ -76,6 +92,50 @@ pub async fn check_authorization_header(jwt_secret: String, header: String) -> R
     }
 }
 
+// the keys are hashed in the authentication service, so every request with one is checked there
+// one client for all of them, so the connections to it are reused
+static AUTHENTICATION_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+
+async fn check_api_key(header: HeaderValue) -> Result<Claims, (StatusCode, Json<ErrorResponse>)> {
+    let response = AUTHENTICATION_CLIENT.get("http://authentication-container:9090/verify-api-key")
+        .header("Authorization", header).send().await.map_err(|error| {
+            tracing::error!("unable to verify the API key : {}", error);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse{message: String::from("Internal Server Error")}))
+        })?;
+    if !response.status().is_success() {
+        tracing::info!("API key was rejected with {}", response.status());
+        return Err((StatusCode::UNAUTHORIZED, Json(ErrorResponse{message: String::from("Invalid API key")})))
+    }
+    response.json::<Claims>().await.map_err(|error| {
+        tracing::error!("unable to read the claims of the API key : {}", error);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse{message: String::from("Internal Server Error")}))
+    })
+}
+
+// the sections an API key can call, everything else (the webhooks, custom domains, bio pages, the API keys,
+// workspaces and digest settings) needs a sign in, so a new route is closed to the keys until it is listed here
+const LINK_SECTIONS: [&str; 8] = ["create-url", "get-urls", "delete-url", "activation-window", "social-preview", "tags", "urls", "folders"];
+const INSIGHT_SECTIONS: [&str; 4] = ["analytics", "insights", "key-insights", "link-health"];
+// the routes which change the links even though they are called with GET
+const CHANGED_WITH_GET: [&str; 1] = ["delete-url"];
+
+// the scope an API key needs for the route, None when only the signed in users can call it
+pub fn required_scope(method: &Method, path: &str) -> Option<&'static str> {
+    let path = path.strip_prefix("/url-shortner/")?;
+    let section = path.split('/').next().unwrap_or_default();
+    let insights = INSIGHT_SECTIONS.contains(&section)
+        || (section == "folders" && (path.ends_with("/insights") || path.ends_with("/compare")));
+    if insights {
+        Some("insights:read")
+    } else if !LINK_SECTIONS.contains(&section) {
+        None
+    } else if method == Method::GET && !CHANGED_WITH_GET.contains(&section) {
+        Some("links:read")
+    } else {
+        Some("links:write")
+    }
+}
+
 pub async fn sign_in_check(mut req: Request, next: Next) -> Result<Response, (StatusCode,ErrorResponse)>{
     let (mut parts, body) = req.into_parts() ;
     tracing::info!("body was {:?}", body);
@@ -172,4 +232,31 @@ pub fn validate_digits_only(value: &str) -> Result<(), ValidationError> {
     } else {
         Err(ValidationError::new("must contain exactly 10 digits"))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_key_scopes_follow_the_route() {
+        assert_eq!(required_scope(&Method::POST, "/url-shortner/create-url"), Some("links:write"));
+        assert_eq!(required_scope(&Method::GET, "/url-shortner/get-urls"), Some("links:read"));
+        assert_eq!(required_scope(&Method::GET, "/url-shortner/delete-url/7"), Some("links:write"));
+        assert_eq!(required_scope(&Method::DELETE, "/url-shortner/folders/3"), Some("links:write"));
+        assert_eq!(required_scope(&Method::GET, "/url-shortner/folders/3/compare"), Some("insights:read"));
+        assert_eq!(required_scope(&Method::GET, "/url-shortner/key-insights/abcde/10"), Some("insights:read"));
+        assert_eq!(required_scope(&Method::POST, "/url-shortner/api-keys"), None);
+        assert_eq!(required_scope(&Method::POST, "/url-shortner/workspaces/2/switch"), None);
+        assert_eq!(required_scope(&Method::GET, "/payment-routes/plans"), None);
+        // the keys can't reach what sends the workspace's data out or changes what is served on its behalf
+        assert_eq!(required_scope(&Method::POST, "/url-shortner/webhooks"), None);
+        assert_eq!(required_scope(&Method::GET, "/url-shortner/webhooks"), None);
+        assert_eq!(required_scope(&Method::POST, "/url-shortner/webhook-rules"), None);
+        assert_eq!(required_scope(&Method::GET, "/url-shortner/webhook-deliveries"), None);
+        assert_eq!(required_scope(&Method::POST, "/url-shortner/custom-domains"), None);
+        assert_eq!(required_scope(&Method::POST, "/url-shortner/custom-domains/4/verify"), None);
+        assert_eq!(required_scope(&Method::POST, "/url-shortner/bio-pages"), None);
+        assert_eq!(required_scope(&Method::GET, "/url-shortner/some-new-section"), None);
+    }
+}
@@ -3,10 +3,16 @@ use axum::body::{to_bytes, Body};
 use axum::response::IntoResponse;
 use regex::Regex;
 use validator::{Validate, ValidationError};
-use crate::models::url_shorten_models::{Insight, UrlShortenModel};
+use crate::models::url_shorten_models::{Insight, SocialUnfurler, UrlShortenModel};
+use user_agent_parser::UserAgentParser;
 use std::net::SocketAddr;
-use axum::extract::State;
+use axum::extract::{Path, State};
+use axum::http::HeaderMap;
 use crate::AppState;
+use crate::controllers::url_shortner_handler::preview_url;
+use crate::services::custom_domains::request_domain;
+use crate::services::name_blocklist::is_blocked_name;
+use crate::services::traffic_sources::{bio_page_referrer, classify};
 
 pub fn validate_url_shortner_name(input: &str) -> Result<(), ValidationError> {
     let allowed_chars = Regex::new(r"^[a-zA-Z0-9_-]{5,}$").unwrap();
@@ -19,6 +25,37 @@ pub fn validate_url_shortner_name(input: &str) -> Result<(), ValidationError> {
     }
 }
 
+// product names given by the user agent parser for the link unfurlers of social apps (lowercase)
+const SOCIAL_UNFURLERS: [&str; 9] = [
+    "facebookbot", "twitterbot", "linkedinbot", "slackbot-linkexpanding", "whatsapp",
+    "telegrambot", "discordbot", "pinterestbot", "redditbot",
+];
+
+pub fn is_social_unfurler(user_agent: &UserAgentParser, ua_str: &str) -> bool {
+    user_agent.parse_product(ua_str).name
+        .map(|name| SOCIAL_UNFURLERS.contains(&name.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+// custom names chosen by the users, on top of the allowed characters the reserved and abusive names are blocked
+pub fn validate_custom_name(input: &str) -> Result<(), ValidationError> {
+    validate_url_shortner_name(input)?;
+    if is_blocked_name(input) {
+        Err(ValidationError::new("Custom name is not allowed"))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn validate_domain_name(input: &str) -> Result<(), ValidationError> {
+    let domain = Regex::new(r"^(?i)([a-z0-9]([a-z0-9-]{0,61}[a-z0-9])?\.)+[a-z]{2,63}$").unwrap();
+    if input.len() <= 253 && domain.is_match(input) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("Invalid Domain"))
+    }
+}
+
 
 pub async fn shorten_url_validation(req: Request, next: Next) -> Result<Response, impl IntoResponse>
 {
@@ -51,6 +88,15 @@ pub async fn shorten_url_validation(req: Request, next: Next) -> Result<Response
     }
 }
 
+// `/{shorten_url}+` shares the route with the redirection, so the preview is served from here
+// before any of the redirection data gathering happens
+pub async fn link_preview_interception(Path(shorten_url): Path<String>, headers: HeaderMap, req: Request, next: Next) -> Response {
+    match shorten_url.strip_suffix('+') {
+        Some(name) => preview_url(name.to_string(), request_domain(&headers)).await,
+        None => next.run(req).await
+    }
+}
+
 pub async fn redirection_data_gathering(State(state): State<AppState>,req: Request, next: Next) -> Result<Response, impl IntoResponse> {
     // here itself we are going to get all the required fields like ip address reffereal source and all
     let (parts, body) = req.into_parts();
@@ -66,8 +112,13 @@ pub async fn redirection_data_gathering(State(state): State<AppState>,req: Reque
         "unknown".to_string()
     };
     tracing::info!("IP Address: {}", ip_address);
-    let referrer = headers.get("referer").and_then(|h| h.to_str().ok()).unwrap_or("Direct").to_string();
+    // the links of a bio page carry its slug, the page is the referrer of those clicks
+    let bio_page = bio_page_referrer(parts.uri.query());
+    let referer_header = bio_page.as_deref().or_else(|| headers.get("referer").and_then(|h| h.to_str().ok()));
+    let referrer = referer_header.unwrap_or("Direct").to_string();
     tracing::info!("Referrer: {}", referrer);
+    // the raw referrer is kept as it is, the source and channel are what the analytics group by
+    let traffic_source = classify(referer_header, parts.uri.query());
     let user_agent_header = headers .get(axum::http::header::USER_AGENT)
         .and_then(|value| value.to_str().ok());
 
@@ -100,9 +151,15 @@ pub async fn redirection_data_gathering(State(state): State<AppState>,req: Reque
         tracing::info!("IP: {}", ip_address);
         tracing::info!("Referrer: {}", referrer);
 
+        let social_unfurler = is_social_unfurler(&user_agent, ua_str);
+
         // Rebuild the request and forward it
         let mut req = Request::from_parts(parts, body);
-        req.extensions_mut().insert(Insight::new(ip_address,referrer,device_name,browser,os));
+        if social_unfurler {
+            tracing::info!("request was from a social unfurler, social preview will be served");
+            req.extensions_mut().insert(SocialUnfurler);
+        }
+        req.extensions_mut().insert(Insight::new(ip_address,referrer,traffic_source,device_name,browser,os));
         Ok(next.run(req).await)
 
     } else {
@@ -110,4 +167,28 @@ pub async fn redirection_data_gathering(State(state): State<AppState>,req: Reque
         Err(Response::builder().status(400).body(Body::from("Invalid User Agent")).unwrap())
     }
 
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_social_unfurlers_only() {
+        let parser = UserAgentParser::from_str(include_str!("../regexes.yaml")).unwrap();
+        assert!(is_social_unfurler(&parser, "facebookexternalhit/1.1 (+http://www.facebook.com/externalhit_uatext.php)"));
+        assert!(is_social_unfurler(&parser, "Mozilla/5.0 (compatible; Discordbot/2.0; +https://discordapp.com)"));
+        assert!(is_social_unfurler(&parser, "Slackbot-LinkExpanding 1.0 (+https://api.slack.com/robots)"));
+        assert!(!is_social_unfurler(&parser, "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36"));
+    }
+
+    #[test]
+    fn domain_names_are_validated() {
+        assert!(validate_domain_name("go.example.com").is_ok());
+        assert!(validate_domain_name("Links.Example.co.uk").is_ok());
+        assert!(validate_domain_name("localhost").is_err());
+        assert!(validate_domain_name("-bad.example.com").is_err());
+        assert!(validate_domain_name("https://go.example.com").is_err());
+    }
+}
@@ -5,7 +5,11 @@ use crate::middlewares::authentication_middlewares::{validate_digits_only, valid
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Claims{
     pub user_id: i32,
-    pub username: String
+    pub username: String,
+    #[serde(default)]
+    pub workspace_id: i32, // the active workspace, the tokens from before the workspaces don't have it
+    #[serde(default)]
+    pub scopes: Option<Vec<String>> // what an API key can do, None for the signed in users who can do everything
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -30,3 +34,28 @@ pub struct Register{
     pub country_id : i32
 }
 
+
+
+#[derive(Debug, Deserialize)]
+pub struct DigestSettingsModel {
+    pub enabled: bool, // opting in or out of the analytics digest emails
+    pub frequency: Option<String>, // daily or weekly, weekly when left out
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceModel {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceMemberModel {
+    pub member: String, // the username or the mail id of the user
+    pub role: String, // owner, editor or viewer
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiKeyModel {
+    pub name: String,
+    pub scopes: String, // comma separated, links:read, links:write or insights:read
+    pub expires_in_days: Option<i32>, // never expires when left out
+}
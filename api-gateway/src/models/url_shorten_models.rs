@@ -1,18 +1,145 @@
 use serde::{Deserialize, Serialize};
 use validator::Validate;
-use crate::middlewares::url_shortner_middlewares::validate_url_shortner_name;
+use crate::services::traffic_sources::TrafficSource;
+use crate::middlewares::url_shortner_middlewares::{validate_custom_name, validate_domain_name};
 #[derive(Deserialize, Debug, Validate )]
 pub struct UrlShortenModel {
     #[validate(url)]
     pub original_url: String,
-    #[validate(custom(function = "validate_url_shortner_name", message="Invalid url custom name"))]
+    #[validate(custom(function = "validate_custom_name", message="Invalid url custom name"))]
     pub custom_url: Option<String>, // it can be None, if the user was not a premium member
+    pub fetch_social_preview: Option<bool>, // uses the destination's own og tags as the default social preview
+    #[validate(custom(function = "validate_domain_name", message="Invalid domain"))]
+    pub domain: Option<String>, // verified custom domain, None means our domain
+    pub active_from: Option<String>, // RFC 3339, the link only redirects from here
+    pub active_until: Option<String>, // RFC 3339, after this the ended page is shown
+    #[validate(url)]
+    pub fallback_url: Option<String>, // visitors are sent here before active_from instead of the coming soon page
+    #[validate(length(max = 500))]
+    pub coming_soon_message: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Validate)]
+pub struct ActivationWindowModel {
+    pub active_from: Option<String>, // RFC 3339, None clears it
+    pub active_until: Option<String>,
+    #[validate(url)]
+    pub fallback_url: Option<String>,
+    #[validate(length(max = 500))]
+    pub coming_soon_message: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Validate)]
+pub struct CustomDomainModel {
+    #[validate(custom(function = "validate_domain_name", message="Invalid domain"))]
+    pub domain: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DomainParams {
+    pub domain: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Validate)]
+pub struct SocialPreviewModel {
+    #[validate(length(max = 200))]
+    pub title: Option<String>,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    #[validate(url)]
+    pub image_url: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct PaginationParams {
     pub page_size: Option<u32>,
     pub page_number: Option<u32>,
+    pub tag: Option<String>, // only the links with this tag
+    pub folder_id: Option<i32>, // only the links in this folder
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AnalyticsParams {
+    pub from: Option<String>, // YYYY-MM-DD, 29 days before `to` when not given
+    pub to: Option<String>, // YYYY-MM-DD and inclusive, today when not given
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ComparisonParams {
+    pub from: Option<String>, // YYYY-MM-DD, 6 days before `to` when not given
+    pub to: Option<String>, // YYYY-MM-DD and inclusive, today when not given
+    pub previous_from: Option<String>, // the same number of days right before `from` when not given
+    pub previous_to: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ExportParams {
+    pub format: Option<String>, // csv (the default) or parquet
+    pub url_id: Option<i32>, // all the links of the user when not given
+    pub from: Option<String>, // YYYY-MM-DD, 29 days before `to` when not given
+    pub to: Option<String>, // YYYY-MM-DD and inclusive, today when not given
+}
+
+// the first page has no token, the next ones pass the next_page_token of the previous page
+#[derive(Deserialize, Debug)]
+pub struct KeyInsightsPath {
+    pub shorten_url: String,
+    pub page_size: u32,
+    pub page_token: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct InsightFilterParams {
+    pub from: Option<String>, // RFC 3339, inclusive
+    pub to: Option<String>, // RFC 3339, inclusive
+    pub browser: Option<String>,
+    pub os: Option<String>,
+    pub device_type: Option<String>,
+    pub referrer: Option<String>,
+    pub location: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UrlTagsModel {
+    pub tags: String, // comma separated, replaces all the tags of the link
+}
+
+#[derive(Deserialize, Debug, Validate)]
+pub struct FolderModel {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug, Validate)]
+pub struct BioPageModel {
+    #[validate(length(min = 3, max = 50))]
+    pub slug: String, // the page is served at /p/{slug}
+    #[validate(length(min = 1, max = 100))]
+    pub title: String,
+    #[validate(url, length(max = 2048))]
+    pub avatar_url: Option<String>,
+    pub theme: Option<String>, // light (default) or dark
+    pub links: Option<String>, // comma separated ids of the user's links in the order they are shown
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UrlFolderModel {
+    pub folder_id: Option<i32>, // None takes the link out of its folder
+}
+
+#[derive(Deserialize, Debug, Validate)]
+pub struct WebhookEndpointModel {
+    #[validate(url, length(max = 2048))]
+    pub url: String, // the notifications are posted here as JSON, signed with the secret returned on creation
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WebhookRuleModel {
+    pub rule_type: String, // click_threshold, spike or no_clicks
+    pub url_id: Option<i32>, // None watches all the links of the user
+    pub threshold: Option<i64>, // click_threshold, the total clicks
+    pub spike_factor: Option<f64>, // spike, today's clicks against the trailing daily average
+    pub days: Option<i32>, // spike, the days of the trailing average. no_clicks, the days without a click
 }
 
 #[derive(Debug, Serialize)]
@@ -20,49 +147,32 @@ pub struct KeyInsights {
     pub insights: Vec<Insight>
 }
 
+// inserted by the redirection middleware when the requester was a link unfurler of a social app
+#[derive(Debug, Clone)]
+pub struct SocialUnfurler;
+
 #[derive(Serialize, Debug)]
 #[derive(Clone)]
 pub struct Insight{
     pub ip_address: String,
     pub refferal: String,
+    pub source: String,
+    pub channel: String,
     pub device_type: String,
     pub browser: String,
     pub os: String
 }
 
 impl Insight {
-    pub fn new(ip_address: String, refferal: String, device_type: String, browser: String, os: String) -> Self {
+    pub fn new(ip_address: String, refferal: String, traffic_source: TrafficSource, device_type: String, browser: String, os: String) -> Self {
         Self {
             ip_address,
             refferal,
+            source: traffic_source.source,
+            channel: traffic_source.channel,
             device_type,
             browser,
             os
         }
     }
 }
-
-#[derive(Serialize)]
-pub struct InsightEvent {
-    pub message_type: String,
-    pub shorten_url: String,
-    pub ip_address: String,
-    pub refferal_source: String,
-    pub device_type: String,
-    pub browser: String,
-    pub os: String,
-}
-
-impl InsightEvent {
-    pub fn new(shorten_url: String, ip_address: String, refferal_source: String, device_type: String, browser: String, os: String) -> Self {
-        Self {
-            message_type: "CREATE_INSIGHT".to_string(),
-            shorten_url,
-            ip_address,
-            refferal_source,
-            device_type,
-            browser,
-            os
-        }
-    }
-}
\ No newline at end of file
@@ -1,6 +1,11 @@
 use axum::{middleware, Router};
-use axum::routing::{post, get};
-use crate::controllers::url_shortner_handler::{create_shorten_url, delete_url, get_key_insights, get_urls};
+use axum::routing::{post, get, delete};
+use crate::controllers::bio_pages_handler::{create_bio_page, delete_bio_page, get_bio_pages, update_bio_page};
+use crate::controllers::analytics_handler::{compare_analytics, compare_campaign_analytics, export_insights, get_analytics_summary};
+use crate::controllers::link_groups_handler::{create_folder, delete_folder, delete_tag, get_campaign_insights, get_folders, get_tags, set_url_folder, set_url_tags};
+use crate::controllers::url_shortner_handler::{create_shorten_url, delete_url, get_key_insights, get_link_health, update_activation_window, get_urls, update_social_preview, add_custom_domain, get_custom_domains, verify_custom_domain};
+use crate::controllers::authentication_handler::{create_api_key, create_workspace, delete_api_key, get_api_keys, get_digest_settings, get_workspace_members, get_workspaces, remove_workspace_member, set_workspace_member, switch_workspace, update_digest_settings};
+use crate::controllers::webhooks_handler::{create_webhook_endpoint, create_webhook_rule, delete_webhook_endpoint, delete_webhook_rule, get_webhook_deliveries, get_webhook_endpoints, get_webhook_rules};
 use crate::middlewares::url_shortner_middlewares::{shorten_url_validation};
 
 pub fn url_shortner_routes() -> Router {
@@ -9,7 +14,38 @@ pub fn url_shortner_routes() -> Router {
         .route("/create-url", post(create_shorten_url).layer(middleware::from_fn(shorten_url_validation)))
         .route("/get-urls", get(get_urls))
         .route("/delete-url/{id}", get(delete_url))
-        .route("/key-insights/{shorten_url}/{page_size}/{last_evaluated_key}", get(get_key_insights))
+        .route("/link-health/{id}", get(get_link_health))
+        .route("/activation-window/{id}", post(update_activation_window))
+        .route("/social-preview/{id}", post(update_social_preview))
+        .route("/custom-domains", get(get_custom_domains).post(add_custom_domain))
+        .route("/custom-domains/{id}/verify", post(verify_custom_domain))
+        .route("/tags", get(get_tags))
+        .route("/tags/{id}", delete(delete_tag))
+        .route("/urls/{id}/tags", post(set_url_tags))
+        .route("/folders", get(get_folders).post(create_folder))
+        .route("/folders/{id}", delete(delete_folder))
+        .route("/folders/{id}/insights", get(get_campaign_insights))
+        .route("/urls/{id}/folder", post(set_url_folder))
+        .route("/analytics/{id}", get(get_analytics_summary))
+        .route("/analytics/{id}/compare", get(compare_analytics))
+        .route("/folders/{id}/compare", get(compare_campaign_analytics))
+        .route("/insights/export", get(export_insights))
+        .route("/webhooks", get(get_webhook_endpoints).post(create_webhook_endpoint))
+        .route("/webhooks/{id}", delete(delete_webhook_endpoint))
+        .route("/webhook-rules", get(get_webhook_rules).post(create_webhook_rule))
+        .route("/webhook-rules/{id}", delete(delete_webhook_rule))
+        .route("/webhook-deliveries", get(get_webhook_deliveries))
+        .route("/digest-settings", get(get_digest_settings).post(update_digest_settings))
+        .route("/workspaces", get(get_workspaces).post(create_workspace))
+        .route("/workspaces/{id}/members", get(get_workspace_members).post(set_workspace_member))
+        .route("/workspaces/{id}/members/{member_id}", delete(remove_workspace_member))
+        .route("/workspaces/{id}/switch", post(switch_workspace))
+        .route("/api-keys", get(get_api_keys).post(create_api_key))
+        .route("/api-keys/{id}", delete(delete_api_key))
+        .route("/bio-pages", get(get_bio_pages).post(create_bio_page))
+        .route("/bio-pages/{id}", post(update_bio_page).delete(delete_bio_page))
+        .route("/key-insights/{shorten_url}/{page_size}", get(get_key_insights))
+        .route("/key-insights/{shorten_url}/{page_size}/{page_token}", get(get_key_insights))
 }
 /*
 from_fn_with_state, the middleware first parameter to be State(value) : State<T>
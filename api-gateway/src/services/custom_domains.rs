@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use axum::http::HeaderMap;
+use proto_definations_snip_sight::generated::url_shortner::VerifiedDomainsRequest;
+use crate::controllers::url_shortner_handler::create_grpc_connection;
+
+// verified custom domains of the users, kept in memory for the CORS layer
+pub type CustomDomains = Arc<RwLock<HashSet<String>>>;
+
+pub const WEB_ORIGIN: &str = "https://web.snipsight.phani.services";
+
+// host the request came on, in lowercase and without the port
+pub fn request_domain(headers: &HeaderMap) -> String {
+    headers.get(axum::http::header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .map(normalise_host)
+        .unwrap_or_default()
+}
+
+fn normalise_host(host: &str) -> String {
+    let host = host.trim().to_lowercase();
+    host.split(':').next().unwrap_or_default().trim_end_matches('.').to_string()
+}
+
+pub fn is_allowed_origin(origin: &str, custom_domains: &HashSet<String>) -> bool {
+    if origin == WEB_ORIGIN {
+        return true
+    }
+    match origin.strip_prefix("https://").or_else(|| origin.strip_prefix("http://")) {
+        Some(host) => custom_domains.contains(&normalise_host(host)),
+        None => false
+    }
+}
+
+// keeps the verified domains up to date, a newly verified domain is allowed with in a minute
+pub async fn refresh_custom_domains(custom_domains: CustomDomains) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    loop {
+        interval.tick().await;
+        let client = create_grpc_connection().await;
+        match client {
+            Ok(mut client) => {
+                match client.get_verified_domains(tonic::Request::new(VerifiedDomainsRequest {})).await {
+                    Ok(response) => {
+                        let domains = response.into_inner().list.into_iter().map(|domain| domain.domain).collect::<HashSet<String>>();
+                        tracing::info!("refreshed {} verified custom domains", domains.len());
+                        *custom_domains.write().unwrap() = domains;
+                    },
+                    Err(error) => {
+                        tracing::error!("Error in gRPC server response while refreshing custom domains: {}", error);
+                    }
+                }
+            },
+            Err(error) => {
+                tracing::error!("unable to connect to gRPC while refreshing custom domains : {}", error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snipsight_events::insights::insight_key;
+
+    #[test]
+    fn verified_domains_pass_cors() {
+        let domains = HashSet::from(["go.example.com".to_string()]);
+        assert!(is_allowed_origin(WEB_ORIGIN, &domains));
+        assert!(is_allowed_origin("https://go.example.com", &domains));
+        assert!(is_allowed_origin("https://GO.example.com:443", &domains));
+        assert!(!is_allowed_origin("https://evil.example.com", &domains));
+        assert!(!is_allowed_origin("go.example.com", &domains));
+    }
+
+    #[test]
+    fn host_header_is_normalised() {
+        let mut headers = HeaderMap::new();
+        headers.insert(axum::http::header::HOST, "Go.Example.com:8080".parse().unwrap());
+        assert_eq!(request_domain(&headers), "go.example.com");
+        assert_eq!(insight_key("launch", &request_domain(&headers)), "go.example.com/launch");
+    }
+}
@@ -0,0 +1,298 @@
+// small server rendered pages served on the public short url routes, no templating engine needed for these
+use proto_definations_snip_sight::generated::url_shortner::PublicBioPage;
+use crate::services::traffic_sources::BIO_PAGE_PARAM;
+
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// returns the host of the destination, if the url can't be parsed we show the whole url itself
+pub fn destination_domain(original_url: &str) -> String {
+    reqwest::Url::parse(original_url).ok()
+        .and_then(|url| url.host_str().map(|host| host.to_string()))
+        .unwrap_or_else(|| original_url.to_string())
+}
+
+pub fn link_preview_page(shorten_url: &str, original_url: &str, created_at: &str) -> String {
+    let domain = escape_html(&destination_domain(original_url));
+    let original_url = escape_html(original_url);
+    let shorten_url = escape_html(shorten_url);
+    // created_at comes as RFC 3339, only the date part is shown
+    let created_on = escape_html(created_at.split('T').next().unwrap_or(created_at));
+
+    format!(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="robots" content="noindex">
+<title>Link preview - {shorten_url}</title>
+<style>
+body {{ font-family: sans-serif; background: #f5f5f5; display: flex; justify-content: center; padding: 40px 16px; }}
+.card {{ background: #fff; border-radius: 8px; padding: 24px; max-width: 560px; width: 100%; box-shadow: 0 1px 4px rgba(0,0,0,.1); }}
+.url {{ word-break: break-all; color: #444; }}
+.continue {{ display: inline-block; margin-top: 16px; padding: 10px 20px; background: #2563eb; color: #fff; border-radius: 6px; text-decoration: none; }}
+</style>
+</head>
+<body>
+<div class="card">
+<h1>This link goes to {domain}</h1>
+<p class="url">{original_url}</p>
+<p>Created on {created_on}</p>
+<a class="continue" href="{original_url}" rel="noopener noreferrer">Continue to {domain}</a>
+</div>
+</body>
+</html>"#)
+}
+
+// served instead of the 307 for the social unfurlers, so the shared link shows a card
+pub fn social_preview_page(original_url: &str, title: &str, description: &str, image_url: &str) -> String {
+    let domain = destination_domain(original_url);
+    let title = escape_html(if title.is_empty() { &domain } else { title });
+    let description = escape_html(description);
+    let original_url = escape_html(original_url);
+
+    let mut meta_tags = vec![
+        format!(r#"<meta property="og:title" content="{title}">"#),
+        format!(r#"<meta property="og:url" content="{original_url}">"#),
+        r#"<meta property="og:type" content="website">"#.to_string(),
+        format!(r#"<meta name="twitter:title" content="{title}">"#),
+    ];
+    if !description.is_empty() {
+        meta_tags.push(format!(r#"<meta property="og:description" content="{description}">"#));
+        meta_tags.push(format!(r#"<meta name="twitter:description" content="{description}">"#));
+    }
+    if image_url.is_empty() {
+        meta_tags.push(r#"<meta name="twitter:card" content="summary">"#.to_string());
+    } else {
+        let image_url = escape_html(image_url);
+        meta_tags.push(format!(r#"<meta property="og:image" content="{image_url}">"#));
+        meta_tags.push(format!(r#"<meta name="twitter:image" content="{image_url}">"#));
+        meta_tags.push(r#"<meta name="twitter:card" content="summary_large_image">"#.to_string());
+    }
+    let meta_tags = meta_tags.join("\n");
+
+    format!(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+{meta_tags}
+<meta http-equiv="refresh" content="0; url={original_url}">
+</head>
+<body>
+<a href="{original_url}">{title}</a>
+</body>
+</html>"#)
+}
+
+// served instead of the redirection when the destination was flagged by the threat lists, the
+// destination is only shown as text so nobody lands on it with a single click
+pub fn unsafe_link_page(shorten_url: &str, original_url: &str, reason: &str) -> String {
+    let domain = escape_html(&destination_domain(original_url));
+    let original_url = escape_html(original_url);
+    let shorten_url = escape_html(shorten_url);
+    let reason = escape_html(reason);
+
+    format!(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="robots" content="noindex">
+<title>Warning - {shorten_url}</title>
+<style>
+body {{ font-family: sans-serif; background: #f5f5f5; display: flex; justify-content: center; padding: 40px 16px; }}
+.card {{ background: #fff; border-radius: 8px; border-top: 6px solid #dc2626; padding: 24px; max-width: 560px; width: 100%; box-shadow: 0 1px 4px rgba(0,0,0,.1); }}
+.url {{ word-break: break-all; color: #444; }}
+</style>
+</head>
+<body>
+<div class="card">
+<h1>This link has been disabled</h1>
+<p>The destination on {domain} was flagged as unsafe: {reason}.</p>
+<p class="url">{original_url}</p>
+<p>We recommend not visiting it.</p>
+</div>
+</body>
+</html>"#)
+}
+
+// shown before the activation window of the link when there was no fallback url
+pub fn coming_soon_page(shorten_url: &str, message: &str, active_from: &str) -> String {
+    let shorten_url = escape_html(shorten_url);
+    let message = escape_html(if message.is_empty() { "This link is not live yet, check back soon." } else { message });
+    let starts = if active_from.is_empty() {
+        String::new()
+    } else {
+        // the visitor's browser shows it in their own timezone
+        let active_from = escape_html(active_from);
+        format!(r#"<p>Goes live on <time datetime="{active_from}">{active_from}</time></p>
+<script>var t = document.querySelector("time"); t.textContent = new Date(t.dateTime).toLocaleString();</script>"#)
+    };
+
+    format!(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="robots" content="noindex">
+<title>Coming soon - {shorten_url}</title>
+<style>
+body {{ font-family: sans-serif; background: #f5f5f5; display: flex; justify-content: center; padding: 40px 16px; }}
+.card {{ background: #fff; border-radius: 8px; padding: 24px; max-width: 560px; width: 100%; box-shadow: 0 1px 4px rgba(0,0,0,.1); }}
+</style>
+</head>
+<body>
+<div class="card">
+<h1>Coming soon</h1>
+<p>{message}</p>
+{starts}
+</div>
+</body>
+</html>"#)
+}
+
+// shown after the activation window of the link
+pub fn link_ended_page(shorten_url: &str) -> String {
+    let shorten_url = escape_html(shorten_url);
+
+    format!(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="robots" content="noindex">
+<title>Link ended - {shorten_url}</title>
+<style>
+body {{ font-family: sans-serif; background: #f5f5f5; display: flex; justify-content: center; padding: 40px 16px; }}
+.card {{ background: #fff; border-radius: 8px; padding: 24px; max-width: 560px; width: 100%; box-shadow: 0 1px 4px rgba(0,0,0,.1); }}
+</style>
+</head>
+<body>
+<div class="card">
+<h1>This link has ended</h1>
+<p>The link was only available for a limited time.</p>
+</div>
+</body>
+</html>"#)
+}
+
+// where a link of the bio page goes, the links carry the slug so their clicks are attributed to the page
+pub fn bio_page_link(slug: &str, shorten_url: &str, domain: &str) -> String {
+    let path = format!("/{}?{}={}", shorten_url, BIO_PAGE_PARAM, slug);
+    if domain.is_empty() { path } else { format!("https://{}{}", domain, path) }
+}
+
+// the public link-in-bio page, the dark theme only swaps the colours
+pub fn bio_page(page: &PublicBioPage) -> String {
+    let title = escape_html(&page.title);
+    let (background, card, text) = if page.theme == "dark" { ("#111827", "#1f2937", "#f9fafb") } else { ("#f5f5f5", "#fff", "#111") };
+    let avatar = if page.avatar_url.is_empty() {
+        String::new()
+    } else {
+        format!(r#"<img class="avatar" src="{}" alt="">"#, escape_html(&page.avatar_url))
+    };
+    let links = page.links.iter().map(|link| {
+        let label = if link.title.is_empty() { destination_domain(&link.original_url) } else { link.title.clone() };
+        format!(r#"<a class="link" href="{}">{}</a>"#, escape_html(&bio_page_link(&page.slug, &link.shorten_url, &link.domain)), escape_html(&label))
+    }).collect::<Vec<_>>().join("\n");
+
+    format!(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<meta property="og:title" content="{title}">
+<style>
+body {{ font-family: sans-serif; background: {background}; color: {text}; display: flex; justify-content: center; padding: 40px 16px; margin: 0; }}
+.page {{ max-width: 560px; width: 100%; text-align: center; }}
+.avatar {{ width: 96px; height: 96px; border-radius: 50%; object-fit: cover; }}
+.link {{ display: block; background: {card}; color: {text}; text-decoration: none; border-radius: 8px; padding: 16px; margin: 12px 0; box-shadow: 0 1px 4px rgba(0,0,0,.1); word-break: break-word; }}
+</style>
+</head>
+<body>
+<div class="page">
+{avatar}
+<h1>{title}</h1>
+{links}
+</div>
+</body>
+</html>"#)
+}
+
+#[cfg(test)]
+mod tests {
+    use proto_definations_snip_sight::generated::url_shortner::BioPageLink;
+    use super::*;
+
+    #[test]
+    fn escapes_markup_in_destination() {
+        assert_eq!(escape_html(r#"<a href="x">'&'</a>"#), "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;");
+    }
+
+    #[test]
+    fn preview_shows_domain_and_date() {
+        let page = link_preview_page("snip-abc", "https://www.example.com/path?q=<b>", "2025-07-20T10:11:12.123Z");
+        assert!(page.contains("This link goes to www.example.com"));
+        assert!(page.contains("https://www.example.com/path?q=&lt;b&gt;"));
+        assert!(page.contains("Created on 2025-07-20<"));
+    }
+
+    #[test]
+    fn bio_page_links_carry_the_slug() {
+        let page = PublicBioPage {
+            slug: "ada".to_string(),
+            title: "Ada <3".to_string(),
+            theme: "dark".to_string(),
+            links: vec![
+                BioPageLink { shorten_url: "launch".to_string(), original_url: "https://example.com/a".to_string(), ..Default::default() },
+                BioPageLink { shorten_url: "talk".to_string(), domain: "go.ada.dev".to_string(), title: "My talk".to_string(), ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let html = bio_page(&page);
+        assert!(html.contains(r#"<a class="link" href="/launch?bio=ada">example.com</a>"#));
+        assert!(html.contains(r#"<a class="link" href="https://go.ada.dev/talk?bio=ada">My talk</a>"#));
+        assert!(html.contains("<h1>Ada &lt;3</h1>"));
+        assert!(html.contains("#111827"));
+        assert!(!html.contains("<img"));
+    }
+
+    #[test]
+    fn social_preview_falls_back_to_domain_title() {
+        let page = social_preview_page("https://example.com/launch", "", "", "");
+        assert!(page.contains(r#"<meta property="og:title" content="example.com">"#));
+        assert!(page.contains(r#"<meta name="twitter:card" content="summary">"#));
+        assert!(page.contains(r#"<meta http-equiv="refresh" content="0; url=https://example.com/launch">"#));
+        assert!(!page.contains("og:image"));
+    }
+
+    #[test]
+    fn unsafe_link_page_does_not_link_the_destination() {
+        let page = unsafe_link_page("snip-abc", "https://evil.example/login", "destination domain was listed as unsafe");
+        assert!(page.contains("flagged as unsafe: destination domain was listed as unsafe"));
+        assert!(page.contains("https://evil.example/login"));
+        assert!(!page.contains("href="));
+    }
+
+    #[test]
+    fn coming_soon_page_shows_the_message_and_start() {
+        let page = coming_soon_page("launch", "Doors open <soon>", "2026-03-01T10:00:00+00:00");
+        assert!(page.contains("<p>Doors open &lt;soon&gt;</p>"));
+        assert!(page.contains(r#"datetime="2026-03-01T10:00:00+00:00""#));
+        assert!(coming_soon_page("launch", "", "").contains("This link is not live yet"));
+    }
+}
@@ -0,0 +1,159 @@
+use std::fs::File;
+use std::io::{Seek, SeekFrom};
+use std::sync::Arc;
+use parquet::basic::Compression;
+use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use proto_definations_snip_sight::generated::url_shortner::ExportedInsight;
+
+pub const CSV_HEADER: &str = "shorten_url,timestamp,ip,browser,os,device,referrer,location\n";
+
+// the rows kept before they are written out as a row group, this is all the export holds in memory
+const ROW_GROUP_SIZE: usize = 50_000;
+
+// the timestamp is left null for an insight without a valid insight_time
+const PARQUET_SCHEMA: &str = "message insight {
+    REQUIRED BYTE_ARRAY shorten_url (UTF8);
+    OPTIONAL INT64 timestamp (TIMESTAMP(MICROS,true));
+    REQUIRED BYTE_ARRAY ip (UTF8);
+    REQUIRED BYTE_ARRAY browser (UTF8);
+    REQUIRED BYTE_ARRAY os (UTF8);
+    REQUIRED BYTE_ARRAY device (UTF8);
+    REQUIRED BYTE_ARRAY referrer (UTF8);
+    REQUIRED BYTE_ARRAY location (UTF8);
+}";
+
+// quoted only when it has to be, a field starting with a formula character is prefixed so spreadsheets show it as text
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) { format!("'{}", value) } else { value.to_string() };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+pub fn csv_rows(rows: &[ExportedInsight]) -> String {
+    let mut csv = String::new();
+    for row in rows {
+        let insight_time = row.insight_time.map(|at| at.to_string()).unwrap_or_default();
+        let fields = [&row.shorten_url, &insight_time, &row.ip_address, &row.browser, &row.os, &row.device_type, &row.referrer, &row.location];
+        csv.push_str(&fields.map(|field| csv_field(field)).join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+// writes the rows into an unnamed temporary file a row group at a time, the file is sent once the export is complete
+pub struct ParquetExport {
+    writer: SerializedFileWriter<File>,
+    rows: Vec<ExportedInsight>,
+}
+
+impl ParquetExport {
+    pub fn new() -> Result<Self, String> {
+        let schema = Arc::new(parse_message_type(PARQUET_SCHEMA).map_err(|err| err.to_string())?);
+        let properties = Arc::new(WriterProperties::builder().set_compression(Compression::SNAPPY).build());
+        let file = tempfile::tempfile().map_err(|err| format!("unable to create the export file : {}", err))?;
+        let writer = SerializedFileWriter::new(file, schema, properties).map_err(|err| err.to_string())?;
+        Ok(Self { writer, rows: Vec::new() })
+    }
+
+    pub fn push(&mut self, rows: Vec<ExportedInsight>) -> Result<(), String> {
+        self.rows.extend(rows);
+        if self.rows.len() >= ROW_GROUP_SIZE {
+            self.write_row_group()?;
+        }
+        Ok(())
+    }
+
+    fn write_row_group(&mut self) -> Result<(), String> {
+        if self.rows.is_empty() {
+            return Ok(())
+        }
+        let rows = std::mem::take(&mut self.rows);
+        let timestamps = rows.iter().map(|row| row.insight_time.map(|at| at.seconds * 1_000_000 + i64::from(at.nanos / 1_000))).collect::<Vec<_>>();
+        let text_columns: [fn(&ExportedInsight) -> &str; 7] = [
+            |row| &row.shorten_url,
+            |row| &row.ip_address,
+            |row| &row.browser,
+            |row| &row.os,
+            |row| &row.device_type,
+            |row| &row.referrer,
+            |row| &row.location,
+        ];
+        let mut text_columns = text_columns.iter();
+
+        let mut row_group = self.writer.next_row_group().map_err(|err| err.to_string())?;
+        let mut index = 0;
+        while let Some(mut column) = row_group.next_column().map_err(|err| err.to_string())? {
+            // the second column of the schema is the timestamp, the rest are text
+            if index == 1 {
+                let values = timestamps.iter().flatten().copied().collect::<Vec<_>>();
+                let definition_levels = timestamps.iter().map(|timestamp| timestamp.is_some() as i16).collect::<Vec<_>>();
+                column.typed::<Int64Type>().write_batch(&values, Some(&definition_levels), None).map_err(|err| err.to_string())?;
+            } else {
+                let text = text_columns.next().ok_or("the schema has more columns than the rows")?;
+                let values = rows.iter().map(|row| ByteArray::from(text(row))).collect::<Vec<_>>();
+                column.typed::<ByteArrayType>().write_batch(&values, None, None).map_err(|err| err.to_string())?;
+            }
+            column.close().map_err(|err| err.to_string())?;
+            index += 1;
+        }
+        row_group.close().map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    // writes the rows left and the footer, the file comes back rewound
+    pub fn finish(mut self) -> Result<File, String> {
+        self.write_row_group()?;
+        let mut file = self.writer.into_inner().map_err(|err| err.to_string())?;
+        file.seek(SeekFrom::Start(0)).map_err(|err| err.to_string())?;
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+
+    fn row(insight_time: &str, referrer: &str) -> ExportedInsight {
+        ExportedInsight {
+            shorten_url: "launch".to_string(),
+            insight_time: insight_time.parse().ok(),
+            ip_address: "203.0.113.7".to_string(),
+            browser: "Firefox".to_string(),
+            os: "Linux".to_string(),
+            device_type: "Other".to_string(),
+            referrer: referrer.to_string(),
+            location: String::new(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn csv_fields_are_escaped() {
+        let csv = csv_rows(&[row("2026-03-01T10:00:00.123456Z", "https://example.com/?a=1,b=\"2\""), row("2026-03-01T11:00:00.000000Z", "=cmd()")]);
+        assert_eq!(csv, "launch,2026-03-01T10:00:00.123456Z,203.0.113.7,Firefox,Linux,Other,\"https://example.com/?a=1,b=\"\"2\"\"\",\n\
+            launch,2026-03-01T11:00:00Z,203.0.113.7,Firefox,Linux,Other,'=cmd(),\n");
+        assert_eq!(CSV_HEADER.split(',').count(), 8);
+    }
+
+    #[test]
+    fn parquet_keeps_every_row_with_typed_timestamps() {
+        let mut export = ParquetExport::new().unwrap();
+        export.push(vec![row("2026-03-01T10:00:00.123456Z", "Direct"), row("not a time", "Direct")]).unwrap();
+        export.push(vec![row("2026-03-01T11:00:00Z", "https://news.ycombinator.com/")]).unwrap();
+        let reader = SerializedFileReader::new(export.finish().unwrap()).unwrap();
+
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 3);
+        let rows = reader.get_row_iter(None).unwrap().map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(rows[0].get_timestamp_micros(1).unwrap(), 1_772_359_200_123_456);
+        assert!(rows[1].get_timestamp_micros(1).is_err());
+        assert_eq!(rows[2].get_string(6).unwrap(), "https://news.ycombinator.com/");
+    }
+}
@@ -0,0 +1,5 @@
+pub mod html_pages;
+pub mod custom_domains;
+pub mod name_blocklist;
+pub mod insight_export;
+pub mod traffic_sources;
@@ -0,0 +1,38 @@
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+use proto_definations_snip_sight::generated::url_shortner::BlockedNamesRequest;
+use proto_definations_snip_sight::name_blocklist::NameBlocklist;
+use crate::controllers::url_shortner_handler::create_grpc_connection;
+
+// copy of the blocked_names table of the url shortener, the form validators can't reach the state
+static BLOCKED_NAMES: LazyLock<RwLock<NameBlocklist>> = LazyLock::new(|| RwLock::new(NameBlocklist::default()));
+
+pub fn is_blocked_name(name: &str) -> bool {
+    BLOCKED_NAMES.read().unwrap().is_blocked(name)
+}
+
+// the url shortener checks the table again while storing, this copy only rejects early
+pub async fn refresh_blocked_names() {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    loop {
+        interval.tick().await;
+        let client = create_grpc_connection().await;
+        match client {
+            Ok(mut client) => {
+                match client.get_blocked_names(tonic::Request::new(BlockedNamesRequest {})).await {
+                    Ok(response) => {
+                        let names = response.into_inner().list;
+                        tracing::info!("refreshed {} blocked names", names.len());
+                        *BLOCKED_NAMES.write().unwrap() = NameBlocklist::new(names);
+                    },
+                    Err(error) => {
+                        tracing::error!("Error in gRPC server response while refreshing blocked names: {}", error);
+                    }
+                }
+            },
+            Err(error) => {
+                tracing::error!("unable to connect to gRPC while refreshing blocked names : {}", error);
+            }
+        }
+    }
+}
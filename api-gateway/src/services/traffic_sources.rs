@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+pub const DIRECT: &str = "direct";
+pub const OTHER: &str = "other";
+// the query parameter the links of the link-in-bio pages carry, its value is the slug of the page
+pub const BIO_PAGE_PARAM: &str = "bio";
+// the clicks from a bio page have "bio:<slug>" as their referrer, the pages are linked from the social profiles
+const BIO_PAGE_PREFIX: &str = "bio:";
+const BIO_PAGE_CHANNEL: &str = "social";
+const MAX_BIO_SLUG_LENGTH: usize = 50;
+// the utm values are free text from the link, they are cut so a long one doesn't blow up the dimensions
+const MAX_SOURCE_LENGTH: usize = 100;
+
+static TRAFFIC_SOURCES: LazyLock<TrafficSources> = LazyLock::new(|| TrafficSources::parse(include_str!("../traffic_sources.txt")));
+
+// where a visit came from, the registrable domain of the referrer (or the utm_source) and its channel
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrafficSource {
+    pub source: String,
+    pub channel: String,
+}
+
+#[derive(Debug, Default)]
+pub struct TrafficSources {
+    names: HashMap<String, String>, // host, domain or utm name -> channel
+    wildcards: HashMap<String, String>, // "google" of "google.*" -> channel
+    labels: HashMap<String, String>, // "facebook" of "facebook.com" -> channel, only for the utm values
+}
+
+impl TrafficSources {
+    pub fn parse(table: &str) -> Self {
+        let mut sources = TrafficSources::default();
+        for line in table.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let Some((channel, name)) = line.split_once(char::is_whitespace) else {
+                tracing::error!("skipping the traffic source without a name {}", line);
+                continue
+            };
+            let (channel, name) = (channel.to_string(), name.trim().to_lowercase());
+            if let Some(wildcard) = name.strip_suffix(".*") {
+                sources.wildcards.insert(wildcard.to_string(), channel);
+                continue
+            }
+            if let Some(label) = psl::domain_str(&name).and_then(|domain| domain.split('.').next()) {
+                sources.labels.entry(label.to_string()).or_insert(channel.clone());
+            }
+            sources.names.insert(name, channel);
+        }
+        sources
+    }
+
+    // the host itself, then its parent domains, then the wildcard of its registrable domain
+    fn channel_of_host(&self, host: &str) -> Option<&str> {
+        let mut candidate = host;
+        loop {
+            if let Some(channel) = self.names.get(candidate) {
+                return Some(channel)
+            }
+            match candidate.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => candidate = parent,
+                _ => break
+            }
+        }
+        let label = psl::domain_str(host)?.split('.').next()?;
+        self.wildcards.get(label).map(String::as_str)
+    }
+
+    fn channel_of_name(&self, name: &str) -> Option<&str> {
+        self.channel_of_host(name)
+            .or_else(|| self.wildcards.get(name).map(String::as_str))
+            .or_else(|| self.labels.get(name).map(String::as_str))
+    }
+
+    // utm_source wins over the referrer, utm_medium decides the channel when it is a known one.
+    // the referrer is normalised to its registrable domain, so every page of a site counts as one source
+    pub fn classify(&self, referrer: Option<&str>, query: Option<&str>) -> TrafficSource {
+        let utm = query.map(utm_values).unwrap_or_default();
+        let medium_channel = utm.get("utm_medium").and_then(|medium| self.channel_of_name(medium));
+
+        if let Some(source) = utm.get("utm_source") {
+            let channel = medium_channel.or_else(|| self.channel_of_name(source)).unwrap_or(OTHER);
+            return TrafficSource { source: source.clone(), channel: channel.to_string() }
+        }
+        let Some(referrer) = referrer.map(str::trim).filter(|referrer| !referrer.is_empty()) else {
+            return TrafficSource { source: "Direct".to_string(), channel: medium_channel.unwrap_or(DIRECT).to_string() }
+        };
+        if referrer.starts_with(BIO_PAGE_PREFIX) {
+            return TrafficSource { source: referrer.to_string(), channel: medium_channel.unwrap_or(BIO_PAGE_CHANNEL).to_string() }
+        }
+        match referrer_host(referrer) {
+            Some(host) => {
+                let channel = medium_channel.or_else(|| self.channel_of_host(&host)).unwrap_or(OTHER);
+                // an app's package name is kept whole, it isn't a domain
+                let source = if referrer.starts_with("android-app:") { host.clone() } else { psl::domain_str(&host).unwrap_or(&host).to_string() };
+                TrafficSource { source, channel: channel.to_string() }
+            },
+            None => TrafficSource { source: "Unknown".to_string(), channel: medium_channel.unwrap_or(OTHER).to_string() }
+        }
+    }
+}
+
+pub fn classify(referrer: Option<&str>, query: Option<&str>) -> TrafficSource {
+    TRAFFIC_SOURCES.classify(referrer, query)
+}
+
+// the referrer of a click from a link-in-bio page, the page is recorded instead of the referer header which
+// the browsers cut down to the origin (or drop) for the links on the custom domains
+pub fn bio_page_referrer(query: Option<&str>) -> Option<String> {
+    serde_urlencoded::from_str::<Vec<(String, String)>>(query?).ok()?.into_iter()
+        .find(|(key, _)| key == BIO_PAGE_PARAM)
+        .map(|(_, slug)| slug.trim().to_lowercase())
+        .filter(|slug| !slug.is_empty() && slug.len() <= MAX_BIO_SLUG_LENGTH && slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+        .map(|slug| format!("{}{}", BIO_PAGE_PREFIX, slug))
+}
+
+// the lowercased, non empty utm values of the short url's query string
+fn utm_values(query: &str) -> HashMap<String, String> {
+    serde_urlencoded::from_str::<Vec<(String, String)>>(query).unwrap_or_default().into_iter()
+        .filter(|(key, _)| key == "utm_source" || key == "utm_medium")
+        .map(|(key, value)| (key, value.trim().to_lowercase().chars().take(MAX_SOURCE_LENGTH).collect::<String>()))
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+// the web pages have a host, the android apps send "android-app://<package>/" as the referrer
+fn referrer_host(referrer: &str) -> Option<String> {
+    let url = reqwest::Url::parse(referrer).ok()?;
+    let host = url.host_str()?.trim_end_matches('.').to_lowercase();
+    Some(host.strip_prefix("www.").map(str::to_string).unwrap_or(host))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classified(referrer: Option<&str>, query: Option<&str>) -> (String, String) {
+        let source = classify(referrer, query);
+        (source.source, source.channel)
+    }
+
+    fn pair(source: &str, channel: &str) -> (String, String) {
+        (source.to_string(), channel.to_string())
+    }
+
+    #[test]
+    fn referrers_are_normalised_to_registrable_domains() {
+        assert_eq!(classified(Some("https://www.facebook.com/groups/rust/posts/123?fbclid=abc"), None), pair("facebook.com", "social"));
+        assert_eq!(classified(Some("https://l.facebook.com/l.php?u=x"), None), pair("facebook.com", "social"));
+        assert_eq!(classified(Some("https://t.co/AbC123"), None), pair("t.co", "social"));
+        assert_eq!(classified(Some("https://www.google.co.in/"), None), pair("google.co.in", "search"));
+        assert_eq!(classified(Some("https://blog.example.co.uk/post/1"), None), pair("example.co.uk", "other"));
+        assert_eq!(classified(Some("android-app://com.google.android.gm/"), None), pair("com.google.android.gm", "email"));
+        assert_eq!(classified(Some("android-app://com.example.reader/"), None), pair("com.example.reader", "other"));
+        assert_eq!(classified(Some("not a url"), None), pair("Unknown", "other"));
+        assert_eq!(classified(None, None), pair("Direct", "direct"));
+    }
+
+    #[test]
+    fn the_more_specific_host_wins() {
+        assert_eq!(classified(Some("https://mail.google.com/mail/u/0/"), None), pair("google.com", "email"));
+        assert_eq!(classified(Some("https://news.ycombinator.com/item?id=1"), None), pair("ycombinator.com", "social"));
+        assert_eq!(classified(Some("https://www.ycombinator.com/companies"), None), pair("ycombinator.com", "other"));
+    }
+
+    #[test]
+    fn utm_values_win_over_the_referrer() {
+        assert_eq!(classified(Some("https://www.google.com/"), Some("utm_source=Newsletter&utm_medium=email")), pair("newsletter", "email"));
+        assert_eq!(classified(None, Some("utm_source=facebook")), pair("facebook", "social"));
+        assert_eq!(classified(None, Some("utm_source=poster&utm_medium=qr")), pair("poster", "qr"));
+        assert_eq!(classified(None, Some("utm_medium=qr")), pair("Direct", "qr"));
+        assert_eq!(classified(None, Some("utm_source=partner-site")), pair("partner-site", "other"));
+        assert_eq!(classified(None, Some("utm_source=&ref=1")), pair("Direct", "direct"));
+    }
+
+    #[test]
+    fn bio_pages_are_the_referrer_of_their_clicks() {
+        assert_eq!(bio_page_referrer(Some("bio=Ada-Lovelace")), Some("bio:ada-lovelace".to_string()));
+        assert_eq!(bio_page_referrer(Some("utm_source=x&bio=%3Cscript%3E")), None);
+        assert_eq!(bio_page_referrer(Some("bio=")), None);
+        assert_eq!(bio_page_referrer(None), None);
+        assert_eq!(classified(Some("bio:ada"), None), pair("bio:ada", "social"));
+        assert_eq!(classified(Some("bio:ada"), Some("utm_medium=email")), pair("bio:ada", "email"));
+    }
+
+    #[test]
+    fn the_table_skips_comments_and_broken_lines() {
+        let sources = TrafficSources::parse("# comment\n\nsocial example.com\nbroken\nsearch find.*\n");
+        assert_eq!(sources.classify(Some("https://a.example.com/"), None).channel, "social");
+        assert_eq!(sources.classify(Some("https://find.de/"), None).channel, "search");
+        assert_eq!(sources.names.len(), 1);
+    }
+}
@@ -0,0 +1,12 @@
+-- the analytics digest emails, users without a row haven't opted in
+CREATE TABLE digest_settings (
+                       user_id INTEGER PRIMARY KEY,
+                       enabled BOOLEAN NOT NULL DEFAULT FALSE,
+                       frequency VARCHAR(10) NOT NULL DEFAULT 'weekly',  -- daily or weekly
+                       last_sent_at TIMESTAMP,                           -- the last digest, the next one is due a day or a week later
+                       updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                       FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+                       CHECK (frequency IN ('daily', 'weekly'))
+);
+
+CREATE INDEX digest_settings_enabled ON digest_settings (frequency, last_sent_at) WHERE enabled;
@@ -0,0 +1,27 @@
+-- the links belong to a workspace, every user gets a personal one on sign up and can be a member of others
+CREATE TABLE workspaces (
+                       id SERIAL PRIMARY KEY,
+                       name VARCHAR(100) NOT NULL,
+                       created_by INTEGER NOT NULL,
+                       personal BOOLEAN NOT NULL DEFAULT FALSE, -- the one made on sign up, the user lands in it after signing in
+                       created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                       FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE TABLE workspace_members (
+                       workspace_id INTEGER NOT NULL,
+                       user_id INTEGER NOT NULL,
+                       role VARCHAR(10) NOT NULL,   -- owner, editor or viewer
+                       added_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                       PRIMARY KEY (workspace_id, user_id),
+                       FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+                       FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+                       CHECK (role IN ('owner', 'editor', 'viewer'))
+);
+
+CREATE INDEX workspace_members_user_id ON workspace_members (user_id);
+
+-- the personal workspaces of the existing users take their user id, the url shortener moves their links to the same id
+INSERT INTO workspaces (id, name, created_by, personal) SELECT id, username, id, TRUE FROM users;
+INSERT INTO workspace_members (workspace_id, user_id, role) SELECT id, id, 'owner' FROM users;
+SELECT setval(pg_get_serial_sequence('workspaces', 'id'), (SELECT COALESCE(MAX(id), 0) + 1 FROM workspaces), false);
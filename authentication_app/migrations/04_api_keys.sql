@@ -0,0 +1,17 @@
+-- the keys for the scripts, only the sha-256 of the secret is kept and the prefix is what the user sees
+CREATE TABLE api_keys (
+                       id SERIAL PRIMARY KEY,
+                       user_id INTEGER NOT NULL,
+                       workspace_id INTEGER NOT NULL,
+                       name VARCHAR(50) NOT NULL,
+                       prefix VARCHAR(20) NOT NULL UNIQUE,   -- snip_ and 12 hex characters, the lookup of the key
+                       key_hash TEXT NOT NULL,
+                       scopes TEXT[] NOT NULL,   -- links:read, links:write or insights:read
+                       expires_at TIMESTAMP,   -- never expires when null
+                       last_used_at TIMESTAMP,
+                       created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                       FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+                       FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+CREATE INDEX api_keys_user_id ON api_keys (user_id);
@@ -0,0 +1,194 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::extract::{Path, State};
+use axum::Json;
+use axum::http::{HeaderMap, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::Error;
+use crate::handlers::ErrorResponse;
+use crate::state::AppState;
+use crate::workspaces::role_of;
+
+// what the keys can be used for, the gateway checks them against the route
+pub const SCOPES: [&str; 3] = ["links:read", "links:write", "insights:read"];
+const KEY_PREFIX: &str = "snip_";
+const PREFIX_BYTES: usize = 6;
+const SECRET_BYTES: usize = 24;
+const MAX_NAME_LENGTH: usize = 50;
+const MAX_EXPIRY_DAYS: i32 = 365;
+const MAX_KEYS_PER_USER: i64 = 20;
+
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub workspace_id: i32,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ApiKeys {
+    pub keys: Vec<ApiKey>,
+}
+
+// the only time the whole key is handed out
+#[derive(Serialize, Deserialize)]
+pub struct CreatedApiKey {
+    pub key: String,
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+}
+
+// the same fields as the claims of the tokens, along with what the key can do
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct ApiKeyClaims {
+    pub user_id: i32,
+    pub username: String,
+    pub workspace_id: i32,
+    pub scopes: Vec<String>,
+}
+
+type HandlerResult<T> = Result<(StatusCode, Json<T>), (StatusCode, Json<ErrorResponse>)>;
+
+const KEY_COLUMNS: &str = "id, name, prefix, workspace_id, scopes, expires_at::TEXT AS expires_at, last_used_at::TEXT AS last_used_at, created_at::TEXT AS created_at";
+
+fn database_error(error: Error) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::error!("error was {}", error) ;
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new("Database Error".to_string())))
+}
+
+fn invalid_key() -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::UNAUTHORIZED, Json(ErrorResponse::new("Invalid API key".to_string())))
+}
+
+fn random_hex(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    OsRng.fill_bytes(&mut buffer);
+    buffer.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// the secrets are 192 random bits, so a plain sha-256 is enough and keeps the checks cheap unlike the passwords
+pub fn hash_secret(secret: &str) -> String {
+    Sha256::digest(secret.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// the scopes are comma separated, the repeated ones are dropped
+pub fn parse_scopes(scopes: &str) -> Option<Vec<String>> {
+    let mut parsed: Vec<String> = Vec::new();
+    for scope in scopes.split(',').map(str::trim) {
+        if !SCOPES.contains(&scope) {
+            return None
+        }
+        if !parsed.iter().any(|s| s == scope) {
+            parsed.push(scope.to_string());
+        }
+    }
+    Some(parsed)
+}
+
+// a key looks like snip_<prefix>_<secret>, the prefix finds the row and the secret is checked against its hash
+pub fn split_key(key: &str) -> Option<(String, &str)> {
+    let (prefix, secret) = key.strip_prefix(KEY_PREFIX)?.split_once('_')?;
+    let hex = |part: &str, bytes: usize| part.len() == bytes * 2 && part.chars().all(|c| c.is_ascii_hexdigit());
+    (hex(prefix, PREFIX_BYTES) && hex(secret, SECRET_BYTES)).then(|| (format!("{}{}", KEY_PREFIX, prefix), secret))
+}
+
+pub async fn get_api_keys_handler(State(state): State<AppState>, Path(user_id): Path<i32>) -> HandlerResult<ApiKeys> {
+    let keys = sqlx::query_as::<_, ApiKey>(&format!("select {} from api_keys where user_id=$1 ORDER BY id", KEY_COLUMNS))
+        .bind(user_id).fetch_all(&state.db_pool).await.map_err(database_error)?;
+    Ok((StatusCode::OK, Json(ApiKeys { keys })))
+}
+
+// the key works on the workspace it was created in, expires_in_days of 0 never expires
+pub async fn create_api_key_handler(State(state): State<AppState>, Path((user_id, workspace_id, name, scopes, expires_in_days)): Path<(i32, i32, String, String, i32)>) -> HandlerResult<CreatedApiKey> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse::new(format!("name must be 1 to {} characters long", MAX_NAME_LENGTH)))))
+    }
+    let Some(scopes) = parse_scopes(&scopes) else {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse::new(format!("scopes must be some of {}", SCOPES.join(", "))))))
+    };
+    if !(0..=MAX_EXPIRY_DAYS).contains(&expires_in_days) {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse::new(format!("expires_in_days must be 0 to {}", MAX_EXPIRY_DAYS)))))
+    }
+    if role_of(workspace_id, user_id, &state.db_pool).await.map_err(database_error)?.is_none() {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse::new("Workspace doesn't exists".to_string()))))
+    }
+    let (keys,): (i64,) = sqlx::query_as("select COUNT(*) from api_keys where user_id=$1")
+        .bind(user_id).fetch_one(&state.db_pool).await.map_err(database_error)?;
+    if keys >= MAX_KEYS_PER_USER {
+        return Err((StatusCode::CONFLICT, Json(ErrorResponse::new(format!("a user can have at most {} API keys", MAX_KEYS_PER_USER)))))
+    }
+
+    let prefix = format!("{}{}", KEY_PREFIX, random_hex(PREFIX_BYTES));
+    let secret = random_hex(SECRET_BYTES);
+    let api_key = sqlx::query_as::<_, ApiKey>(&format!("INSERT INTO api_keys (user_id, workspace_id, name, prefix, key_hash, scopes, expires_at)
+         VALUES ($1, $2, $3, $4, $5, $6, CASE WHEN $7 = 0 THEN NULL ELSE NOW() + make_interval(days => $7) END)
+         RETURNING {}", KEY_COLUMNS))
+        .bind(user_id).bind(workspace_id).bind(&name).bind(&prefix).bind(hash_secret(&secret)).bind(&scopes).bind(expires_in_days)
+        .fetch_one(&state.db_pool).await.map_err(database_error)?;
+    tracing::info!("API key {} was created by the user {}", api_key.prefix, user_id);
+    Ok((StatusCode::CREATED, Json(CreatedApiKey { key: format!("{}_{}", prefix, secret), api_key })))
+}
+
+pub async fn delete_api_key_handler(State(state): State<AppState>, Path((user_id, id)): Path<(i32, i32)>) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let result = sqlx::query("DELETE FROM api_keys where id=$1 AND user_id=$2")
+        .bind(id).bind(user_id).execute(&state.db_pool).await.map_err(database_error)?;
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse::new("API key doesn't exists".to_string()))))
+    }
+    tracing::info!("API key {} of the user {} was deleted", id, user_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// used by the gateway for the `Authorization: ApiKey ...` requests, the key stops working once its user leaves the workspace
+pub async fn verify_api_key_handler(State(state): State<AppState>, headers: HeaderMap) -> HandlerResult<ApiKeyClaims> {
+    let key = headers.get("Authorization").and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("ApiKey ")).map(str::trim);
+    let Some((prefix, secret)) = key.and_then(split_key) else {
+        return Err(invalid_key())
+    };
+    let row = sqlx::query_as::<_, (i32, i32, String, i32, Vec<String>)>("select k.id, k.user_id, u.username, k.workspace_id, k.scopes
+         from api_keys k JOIN users u ON u.id = k.user_id
+         JOIN workspace_members m ON m.workspace_id = k.workspace_id AND m.user_id = k.user_id
+         where k.prefix=$1 AND k.key_hash=$2 AND (k.expires_at IS NULL OR k.expires_at > NOW())")
+        .bind(&prefix).bind(hash_secret(secret)).fetch_optional(&state.db_pool).await.map_err(database_error)?;
+    let Some((id, user_id, username, workspace_id, scopes)) = row else {
+        tracing::warn!("API key {} doesn't exists, has a wrong secret, expired or lost its workspace", prefix);
+        return Err(invalid_key())
+    };
+    sqlx::query("update api_keys SET last_used_at=NOW() where id=$1")
+        .bind(id).execute(&state.db_pool).await.map_err(database_error)?;
+    Ok((StatusCode::OK, Json(ApiKeyClaims { user_id, username, workspace_id, scopes })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_are_parsed() {
+        assert_eq!(parse_scopes("links:write, insights:read,links:write"), Some(vec!["links:write".to_string(), "insights:read".to_string()]));
+        assert_eq!(parse_scopes("links:delete"), None);
+        assert_eq!(parse_scopes(""), None);
+    }
+
+    #[test]
+    fn keys_are_split_in_to_the_prefix_and_the_secret() {
+        let key = format!("snip_{}_{}", random_hex(PREFIX_BYTES), random_hex(SECRET_BYTES));
+        let (prefix, secret) = split_key(&key).unwrap();
+        assert_eq!(format!("{}_{}", prefix, secret), key);
+        assert!(split_key("snip_abc_def").is_none());
+        assert!(split_key(&key.replacen("snip_", "ssh_", 1)).is_none());
+        assert!(split_key(&format!("{}x", key)).is_none());
+    }
+
+    #[test]
+    fn secrets_are_hashed_with_sha_256() {
+        assert_eq!(hash_secret("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+}
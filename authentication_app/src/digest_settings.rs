@@ -0,0 +1,106 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use axum::http::StatusCode;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::Error;
+use crate::handlers::ErrorResponse;
+use crate::state::AppState;
+
+pub const FREQUENCIES: [&str; 2] = ["daily", "weekly"];
+// the recipients handed to the digest job in one go, the rest are picked up by its next run
+const RECIPIENTS_BATCH_SIZE: i64 = 500;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct DigestSettings {
+    pub enabled: bool,
+    pub frequency: String,
+    pub last_sent_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct DigestRecipient {
+    pub user_id: i32,
+    pub username: String,
+    pub mail_id: String,
+    pub frequency: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DigestRecipients {
+    pub recipients: Vec<DigestRecipient>,
+}
+
+type HandlerResult<T> = Result<(StatusCode, Json<T>), (StatusCode, Json<ErrorResponse>)>;
+
+fn database_error(error: Error) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::error!("error was {}", error) ;
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new("Database Error".to_string())))
+}
+
+pub fn valid_frequency(frequency: &str) -> bool {
+    FREQUENCIES.contains(&frequency)
+}
+
+// users who never opted in get the defaults, the digest is off and weekly once it is turned on
+pub async fn get_digest_settings_handler(State(state): State<AppState>, Path(user_id): Path<i32>) -> HandlerResult<DigestSettings> {
+    let settings: Option<(bool, String, Option<NaiveDateTime>)> = sqlx::query_as("select enabled, frequency, last_sent_at from digest_settings where user_id=$1")
+        .bind(user_id).fetch_optional(&state.db_pool).await.map_err(database_error)?;
+    let (enabled, frequency, last_sent_at) = settings.unwrap_or((false, "weekly".to_string(), None));
+    Ok((StatusCode::OK, Json(DigestSettings { enabled, frequency, last_sent_at: last_sent_at.map(|at| at.to_string()) })))
+}
+
+pub async fn update_digest_settings_handler(State(state): State<AppState>, Path((user_id, enabled, frequency)): Path<(i32, bool, String)>) -> HandlerResult<DigestSettings> {
+    if !valid_frequency(&frequency) {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse::new("frequency must be daily or weekly".to_string()))))
+    }
+    let settings: Result<(Option<NaiveDateTime>,), _> = sqlx::query_as("INSERT INTO digest_settings (user_id, enabled, frequency) VALUES ($1, $2, $3)
+         ON CONFLICT (user_id) DO UPDATE SET enabled=$2, frequency=$3, updated_at=NOW()
+         RETURNING last_sent_at")
+        .bind(user_id).bind(enabled).bind(&frequency).fetch_one(&state.db_pool).await;
+    match settings {
+        Ok((last_sent_at,)) => {
+            tracing::info!("digest settings of the user {} were updated", user_id);
+            Ok((StatusCode::OK, Json(DigestSettings { enabled, frequency, last_sent_at: last_sent_at.map(|at| at.to_string()) })))
+        },
+        Err(Error::Database(err)) if err.constraint() == Some("digest_settings_user_id_fkey") => {
+            Err((StatusCode::NOT_FOUND, Json(ErrorResponse::new("User doesn't exists".to_string()))))
+        },
+        Err(error) => Err(database_error(error))
+    }
+}
+
+// the users whose digest is due, a daily one once a calendar day and a weekly one once in 7 days
+pub async fn get_digest_recipients_handler(State(state): State<AppState>) -> HandlerResult<DigestRecipients> {
+    let recipients = sqlx::query_as::<_, DigestRecipient>("select d.user_id, u.username, u.mail_id, d.frequency from digest_settings d
+         JOIN users u ON u.id = d.user_id
+         where d.enabled AND (d.last_sent_at IS NULL
+            OR (d.frequency = 'daily' AND d.last_sent_at < date_trunc('day', NOW()))
+            OR (d.frequency = 'weekly' AND d.last_sent_at < date_trunc('day', NOW()) - INTERVAL '6 days'))
+         ORDER BY d.user_id LIMIT $1")
+        .bind(RECIPIENTS_BATCH_SIZE).fetch_all(&state.db_pool).await.map_err(database_error)?;
+    Ok((StatusCode::OK, Json(DigestRecipients { recipients })))
+}
+
+// called by the digest job once the user's digest went out (or there was nothing to send)
+pub async fn digest_sent_handler(State(state): State<AppState>, Path(user_id): Path<i32>) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let result = sqlx::query("UPDATE digest_settings SET last_sent_at=NOW() where user_id=$1")
+        .bind(user_id).execute(&state.db_pool).await.map_err(database_error)?;
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse::new("User hasn't opted in for the digest".to_string()))))
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_daily_and_weekly_digests() {
+        assert!(valid_frequency("daily"));
+        assert!(valid_frequency("weekly"));
+        assert!(!valid_frequency("monthly"));
+        assert!(!valid_frequency("Weekly"));
+    }
+}
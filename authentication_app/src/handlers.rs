@@ -6,6 +6,7 @@ use serde::{Serialize, Deserialize};
 use sqlx::Error;
 use crate::state::AppState;
 use crate::middlewares::{create_authorization_header, hash_password, verify_password};
+use crate::workspaces::{create_workspace, personal_workspace};
 
 #[derive(Serialize, Deserialize)]
 pub struct ErrorResponse{
@@ -48,7 +49,11 @@ pub async fn sign_in_handler(State(state):State<AppState> ,Path((username, passw
                 Ok(is_same) => {
                     if is_same {
                         tracing::info!("Correct credentials {:?}", row) ;
-                        let header = create_authorization_header(String::from(&state.jwt_secret),row.0, row.1);
+                        let workspace_id = personal_workspace(row.0, &row.1, &state.db_pool).await.map_err(|error| {
+                            tracing::error!("error was {}", error);
+                            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new("Internal Server Error Occurred".to_string())))
+                        })?;
+                        let header = create_authorization_header(String::from(&state.jwt_secret),row.0, row.1, workspace_id);
                         let mut headers = HeaderMap::new() ;
                         headers.insert("Authorization", HeaderValue::from_str(&header).unwrap()) ;
                         headers.insert("Access-Control-Expose-Headers" , HeaderValue::from_str("authorization").unwrap()) ;
@@ -113,7 +118,11 @@ pub async fn sign_up_handler(State(state):State<AppState> ,Path((username, passw
                              -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)>
 {
     let hash_password = hash_password(&password) ;
-    // here we call the service that access the database
+    // the user and their personal workspace go in together
+    let mut transaction = state.db_pool.begin().await.map_err(|error| {
+        tracing::error!("error was {}", error) ;
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new("Database Error".to_string())))
+    })?;
     let user: Result<(i32,),_> = sqlx::query_as(
         "INSERT INTO users (username, mail_id, password, mobile, country_id)
          VALUES ($1, $2, $3, $4, $5)
@@ -124,12 +133,19 @@ pub async fn sign_up_handler(State(state):State<AppState> ,Path((username, passw
         .bind(&hash_password)
         .bind(&mobile)
         .bind(&country_id)
-        .fetch_one(&state.db_pool)  // Fetch the row
+        .fetch_one(&mut *transaction)  // Fetch the row
         .await;
+    let user = match user {
+        Ok(user) => match create_workspace(user.0, &username, true, &mut transaction).await {
+            Ok(workspace_id) => transaction.commit().await.map(|_| (user.0, workspace_id)),
+            Err(error) => Err(error)
+        },
+        Err(error) => Err(error)
+    };
 
    match user {
        Ok(user)=> {
-           let header = create_authorization_header(String::from(&state.jwt_secret),user.0,username);
+           let header = create_authorization_header(String::from(&state.jwt_secret),user.0,username,user.1);
            tracing::info!("New User Created Successfully");
            let mut headers = HeaderMap::new();
            headers.insert("Authorization", HeaderValue::from_str(&header).unwrap()) ;
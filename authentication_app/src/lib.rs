@@ -15,10 +15,16 @@ from your tests!
 
 mod handlers;
 pub mod middlewares;
+pub mod digest_settings;
+pub mod workspaces;
+pub mod api_keys;
 use axum::Router;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post};
 use sqlx::{PgPool, Pool, Postgres};
 use handlers::*;
+use digest_settings::{digest_sent_handler, get_digest_recipients_handler, get_digest_settings_handler, update_digest_settings_handler};
+use api_keys::{create_api_key_handler, delete_api_key_handler, get_api_keys_handler, verify_api_key_handler};
+use workspaces::{create_workspace_handler, get_workspace_members_handler, get_workspace_owner_handler, get_workspace_role_handler, get_workspaces_handler, remove_workspace_member_handler, set_workspace_member_handler, switch_workspace_handler};
 mod state;
 use state::AppState;
 
@@ -30,6 +36,22 @@ pub fn create_app(rds_connection: Pool<Postgres>, jwt_secret: String) -> Router
         .route("/sign-in/{username}/{password}", get(sign_in_handler))
         .route("/sign-up/{username}/{password}/{mail_id}/{mobile}/{country_id}", get(sign_up_handler))
         .route("/get-countries", get(get_countries_handler))
+        .route("/digest-settings/{user_id}", get(get_digest_settings_handler))
+        .route("/digest-settings/{user_id}/{enabled}/{frequency}", post(update_digest_settings_handler))
+        .route("/digest-recipients", get(get_digest_recipients_handler))
+        .route("/digest-sent/{user_id}", post(digest_sent_handler))
+        .route("/workspaces/{user_id}", get(get_workspaces_handler))
+        .route("/workspaces/{user_id}/{name}", post(create_workspace_handler))
+        .route("/workspace-role/{workspace_id}/{user_id}", get(get_workspace_role_handler))
+        .route("/workspace-owner/{workspace_id}", get(get_workspace_owner_handler))
+        .route("/workspace-members/{workspace_id}/{user_id}", get(get_workspace_members_handler))
+        .route("/workspace-members/{workspace_id}/{user_id}/{member}/{role}", post(set_workspace_member_handler))
+        .route("/workspace-members/{workspace_id}/{user_id}/{member_id}", delete(remove_workspace_member_handler))
+        .route("/switch-workspace/{workspace_id}/{user_id}", get(switch_workspace_handler))
+        .route("/api-keys/{user_id}", get(get_api_keys_handler))
+        .route("/api-keys/{user_id}/{workspace_id}/{name}/{scopes}/{expires_in_days}", post(create_api_key_handler))
+        .route("/api-keys/{user_id}/{id}", delete(delete_api_key_handler))
+        .route("/verify-api-key", get(verify_api_key_handler))
         .with_state(AppState::new(rds_connection, jwt_secret))
 }
 
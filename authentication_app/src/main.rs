@@ -1,7 +1,3 @@
-mod middlewares;
-mod handlers;
-mod state;
-
 
 use aws_config::meta::region::RegionProviderChain;
 use aws_sdk_ssm::Client;
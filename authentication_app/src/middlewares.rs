@@ -7,23 +7,24 @@ use jsonwebtoken::{encode, Header, EncodingKey};
 pub struct Claims{
     user_id:i32,
     username:String,
+    workspace_id:i32, // the active workspace, the links of it are the ones the user works with
     exp: usize, // expiration timestamp in unix
 }
 
 impl Claims{
-    fn new(user_id:i32, username:String, exp: usize) -> Self{
+    fn new(user_id:i32, username:String, workspace_id:i32, exp: usize) -> Self{
         Claims{
-            user_id, username, exp
+            user_id, username, workspace_id, exp
         }
     }
 }
 
-pub fn create_authorization_header(jwt_secret: String,user_id: i32, username: String) -> String {
+pub fn create_authorization_header(jwt_secret: String,user_id: i32, username: String, workspace_id: i32) -> String {
     let expiration = Utc::now()
         .checked_add_signed(chrono::Duration::days(7)).expect("error in expiration time")
         .timestamp() as usize;
 
-    let claims = Claims::new(user_id, username, expiration) ;
+    let claims = Claims::new(user_id, username, workspace_id, expiration) ;
     format!("Bearer {}", encode(
         &Header::default(),
         &claims,
@@ -73,7 +74,7 @@ mod tests {
         tracing_subscriber::fmt().init();
         tracing::info!("Creating authorization header...");
         let header = create_authorization_header(
-            String::from("phani"), 1, String::from("phanidhar"));
+            String::from("phani"), 1, String::from("phanidhar"), 1);
         tracing::info!("Header was : {:?}", header);
     }
 
@@ -0,0 +1,239 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, Pool, Postgres, Transaction};
+use crate::handlers::ErrorResponse;
+use crate::middlewares::create_authorization_header;
+use crate::state::AppState;
+
+// an owner manages the members, an editor changes the links and a viewer only reads them
+pub const ROLES: [&str; 3] = ["owner", "editor", "viewer"];
+const MAX_NAME_LENGTH: usize = 100;
+
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug, PartialEq)]
+pub struct Workspace {
+    pub id: i32,
+    pub name: String,
+    pub role: String, // the role of the user asking for it
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Workspaces {
+    pub workspaces: Vec<Workspace>,
+}
+
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct WorkspaceMember {
+    pub user_id: i32,
+    pub username: String,
+    pub role: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorkspaceMembers {
+    pub members: Vec<WorkspaceMember>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WorkspaceRole {
+    pub role: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WorkspaceOwner {
+    pub created_by: i32,
+}
+
+type HandlerResult<T> = Result<(StatusCode, Json<T>), (StatusCode, Json<ErrorResponse>)>;
+
+fn database_error(error: Error) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::error!("error was {}", error) ;
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new("Database Error".to_string())))
+}
+
+fn not_a_member() -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::NOT_FOUND, Json(ErrorResponse::new("Workspace doesn't exists".to_string())))
+}
+
+pub fn valid_role(role: &str) -> bool {
+    ROLES.contains(&role)
+}
+
+pub fn workspace_name(name: &str) -> Option<String> {
+    let name = name.trim();
+    (!name.is_empty() && name.chars().count() <= MAX_NAME_LENGTH).then(|| name.to_string())
+}
+
+// the workspace along with its creator as the owner, personal for the one made on sign up
+pub async fn create_workspace(user_id: i32, name: &str, personal: bool, transaction: &mut Transaction<'_, Postgres>) -> Result<i32, Error> {
+    let (id,): (i32,) = sqlx::query_as("INSERT INTO workspaces (name, created_by, personal) VALUES ($1, $2, $3) RETURNING id")
+        .bind(name).bind(user_id).bind(personal).fetch_one(&mut **transaction).await?;
+    sqlx::query("INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, 'owner')")
+        .bind(id).bind(user_id).execute(&mut **transaction).await?;
+    Ok(id)
+}
+
+// the workspace the user lands in after signing in, the personal one they made on sign up and are still a member of.
+// being the owner of a team workspace doesn't make it personal. users who were added without signing up get theirs here
+pub async fn personal_workspace(user_id: i32, username: &str, db: &Pool<Postgres>) -> Result<i32, Error> {
+    let personal: Option<(i32,)> = sqlx::query_as("select w.id from workspaces w JOIN workspace_members m ON m.workspace_id = w.id \
+        where w.created_by=$1 AND w.personal AND m.user_id=$1 ORDER BY w.id LIMIT 1")
+        .bind(user_id).fetch_optional(db).await?;
+    if let Some((id,)) = personal {
+        return Ok(id)
+    }
+    let mut transaction = db.begin().await?;
+    let id = create_workspace(user_id, username, true, &mut transaction).await?;
+    transaction.commit().await?;
+    Ok(id)
+}
+
+pub(crate) async fn role_of(workspace_id: i32, user_id: i32, db: &Pool<Postgres>) -> Result<Option<String>, Error> {
+    let role: Option<(String,)> = sqlx::query_as("select role from workspace_members where workspace_id=$1 AND user_id=$2")
+        .bind(workspace_id).bind(user_id).fetch_optional(db).await?;
+    Ok(role.map(|(role,)| role))
+}
+
+// the members are changed under a lock on the workspace, so two owners can't demote each other at the same time
+async fn change_members(workspace_id: i32, user_id: i32, db: &Pool<Postgres>) -> Result<Transaction<'static, Postgres>, (StatusCode, Json<ErrorResponse>)> {
+    let mut transaction = db.begin().await.map_err(database_error)?;
+    let role: Option<(String,)> = sqlx::query_as("select m.role from workspaces w JOIN workspace_members m ON m.workspace_id = w.id where w.id=$1 AND m.user_id=$2 FOR UPDATE OF w")
+        .bind(workspace_id).bind(user_id).fetch_optional(&mut *transaction).await.map_err(database_error)?;
+    match role {
+        Some((role,)) if role == "owner" => Ok(transaction),
+        Some(_) => Err((StatusCode::FORBIDDEN, Json(ErrorResponse::new("Only the owners can manage the members".to_string())))),
+        None => Err(not_a_member())
+    }
+}
+
+async fn commit_members(workspace_id: i32, mut transaction: Transaction<'_, Postgres>) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let (owners,): (i64,) = sqlx::query_as("select COUNT(*) from workspace_members where workspace_id=$1 AND role='owner'")
+        .bind(workspace_id).fetch_one(&mut *transaction).await.map_err(database_error)?;
+    if owners == 0 {
+        return Err((StatusCode::CONFLICT, Json(ErrorResponse::new("A workspace needs at least one owner".to_string()))))
+    }
+    transaction.commit().await.map_err(database_error)
+}
+
+pub async fn get_workspaces_handler(State(state): State<AppState>, Path(user_id): Path<i32>) -> HandlerResult<Workspaces> {
+    let workspaces = sqlx::query_as::<_, Workspace>("select w.id, w.name, m.role from workspace_members m
+         JOIN workspaces w ON w.id = m.workspace_id where m.user_id=$1 ORDER BY w.id")
+        .bind(user_id).fetch_all(&state.db_pool).await.map_err(database_error)?;
+    Ok((StatusCode::OK, Json(Workspaces { workspaces })))
+}
+
+pub async fn create_workspace_handler(State(state): State<AppState>, Path((user_id, name)): Path<(i32, String)>) -> HandlerResult<Workspace> {
+    let Some(name) = workspace_name(&name) else {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse::new(format!("name must be 1 to {} characters long", MAX_NAME_LENGTH)))))
+    };
+    let mut transaction = state.db_pool.begin().await.map_err(database_error)?;
+    let id = match create_workspace(user_id, &name, false, &mut transaction).await {
+        Ok(id) => id,
+        Err(Error::Database(err)) if err.constraint() == Some("workspaces_created_by_fkey") => {
+            return Err((StatusCode::NOT_FOUND, Json(ErrorResponse::new("User doesn't exists".to_string()))))
+        },
+        Err(error) => return Err(database_error(error))
+    };
+    transaction.commit().await.map_err(database_error)?;
+    tracing::info!("workspace {} was created by the user {}", id, user_id);
+    Ok((StatusCode::CREATED, Json(Workspace { id, name, role: "owner".to_string() })))
+}
+
+// used by the url shortener to authorize every call on the links of the workspace
+pub async fn get_workspace_role_handler(State(state): State<AppState>, Path((workspace_id, user_id)): Path<(i32, i32)>) -> HandlerResult<WorkspaceRole> {
+    match role_of(workspace_id, user_id, &state.db_pool).await.map_err(database_error)? {
+        Some(role) => Ok((StatusCode::OK, Json(WorkspaceRole { role }))),
+        None => Err(not_a_member())
+    }
+}
+
+// used by the url shortener for the premium features, they come with the plan of the user who made the workspace
+pub async fn get_workspace_owner_handler(State(state): State<AppState>, Path(workspace_id): Path<i32>) -> HandlerResult<WorkspaceOwner> {
+    let owner: Option<(i32,)> = sqlx::query_as("select created_by from workspaces where id=$1")
+        .bind(workspace_id).fetch_optional(&state.db_pool).await.map_err(database_error)?;
+    match owner {
+        Some((created_by,)) => Ok((StatusCode::OK, Json(WorkspaceOwner { created_by }))),
+        None => Err(not_a_member())
+    }
+}
+
+pub async fn get_workspace_members_handler(State(state): State<AppState>, Path((workspace_id, user_id)): Path<(i32, i32)>) -> HandlerResult<WorkspaceMembers> {
+    if role_of(workspace_id, user_id, &state.db_pool).await.map_err(database_error)?.is_none() {
+        return Err(not_a_member())
+    }
+    let members = sqlx::query_as::<_, WorkspaceMember>("select m.user_id, u.username, m.role from workspace_members m
+         JOIN users u ON u.id = m.user_id where m.workspace_id=$1 ORDER BY m.added_at, m.user_id")
+        .bind(workspace_id).fetch_all(&state.db_pool).await.map_err(database_error)?;
+    Ok((StatusCode::OK, Json(WorkspaceMembers { members })))
+}
+
+// adds the member by their username or mail id, or changes the role when they are already in
+pub async fn set_workspace_member_handler(State(state): State<AppState>, Path((workspace_id, user_id, member, role)): Path<(i32, i32, String, String)>) -> HandlerResult<WorkspaceMember> {
+    if !valid_role(&role) {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse::new(format!("role must be one of {}", ROLES.join(", "))))))
+    }
+    let mut transaction = change_members(workspace_id, user_id, &state.db_pool).await?;
+    let member: Option<(i32, String)> = sqlx::query_as("select id, username from users where username=$1 OR mail_id=$1")
+        .bind(&member).fetch_optional(&mut *transaction).await.map_err(database_error)?;
+    let Some((member_id, username)) = member else {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse::new("User doesn't exists".to_string()))))
+    };
+    sqlx::query("INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, $3)
+         ON CONFLICT (workspace_id, user_id) DO UPDATE SET role=$3")
+        .bind(workspace_id).bind(member_id).bind(&role).execute(&mut *transaction).await.map_err(database_error)?;
+    commit_members(workspace_id, transaction).await?;
+    tracing::info!("the user {} is now {} of the workspace {}", member_id, role, workspace_id);
+    Ok((StatusCode::OK, Json(WorkspaceMember { user_id: member_id, username, role })))
+}
+
+// the owners remove the members, and anyone can leave on their own
+pub async fn remove_workspace_member_handler(State(state): State<AppState>, Path((workspace_id, user_id, member_id)): Path<(i32, i32, i32)>) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let mut transaction = if member_id == user_id {
+        let mut transaction = state.db_pool.begin().await.map_err(database_error)?;
+        sqlx::query("select id from workspaces where id=$1 FOR UPDATE")
+            .bind(workspace_id).execute(&mut *transaction).await.map_err(database_error)?;
+        transaction
+    } else {
+        change_members(workspace_id, user_id, &state.db_pool).await?
+    };
+    let result = sqlx::query("DELETE FROM workspace_members where workspace_id=$1 AND user_id=$2")
+        .bind(workspace_id).bind(member_id).execute(&mut *transaction).await.map_err(database_error)?;
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse::new("Member doesn't exists".to_string()))))
+    }
+    commit_members(workspace_id, transaction).await?;
+    tracing::info!("the user {} was removed from the workspace {}", member_id, workspace_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// a new token with the workspace as the active one, the old token keeps working until it expires
+pub async fn switch_workspace_handler(State(state): State<AppState>, Path((workspace_id, user_id)): Path<(i32, i32)>) -> Result<(StatusCode, HeaderMap), (StatusCode, Json<ErrorResponse>)> {
+    let member: Option<(String,)> = sqlx::query_as("select u.username from workspace_members m JOIN users u ON u.id = m.user_id
+         where m.workspace_id=$1 AND m.user_id=$2")
+        .bind(workspace_id).bind(user_id).fetch_optional(&state.db_pool).await.map_err(database_error)?;
+    let Some((username,)) = member else {
+        return Err(not_a_member())
+    };
+    let header = create_authorization_header(String::from(&state.jwt_secret), user_id, username, workspace_id);
+    let mut headers = HeaderMap::new();
+    headers.insert("Authorization", HeaderValue::from_str(&header).unwrap()) ;
+    headers.insert("Access-Control-Expose-Headers" , HeaderValue::from_str("authorization").unwrap()) ;
+    Ok((StatusCode::OK, headers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_and_names() {
+        assert!(valid_role("owner") && valid_role("editor") && valid_role("viewer"));
+        assert!(!valid_role("admin"));
+        assert!(!valid_role("Owner"));
+
+        assert_eq!(workspace_name("  Marketing "), Some("Marketing".to_string()));
+        assert_eq!(workspace_name("   "), None);
+        assert_eq!(workspace_name(&"a".repeat(MAX_NAME_LENGTH + 1)), None);
+    }
+}
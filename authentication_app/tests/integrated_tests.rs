@@ -85,6 +85,29 @@ async fn sign_up_check() {
 }
 
 
+#[tokio::test]
+async fn digest_settings_check() {
+    let db_connection = create_db().await ;
+    let app = create_app(db_connection.clone(), "phani".to_string());
+
+    create_user(db_connection.clone(), "digest".to_string(), "password".to_string(), "digest@gmail.com".to_string(), "8885858761".to_string()).await;
+    let (user_id,): (i32,) = sqlx::query_as("select id from users where username='digest'").fetch_one(&db_connection).await.unwrap();
+
+    let request = |method: &str, uri: String| axum::http::Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let invalid = app.clone().oneshot(request("POST", format!("/digest-settings/{}/true/monthly", user_id))).await.unwrap();
+    let opted_in = app.clone().oneshot(request("POST", format!("/digest-settings/{}/true/daily", user_id))).await.unwrap();
+    let due = app.clone().oneshot(request("GET", "/digest-recipients".to_string())).await.unwrap();
+    let sent = app.clone().oneshot(request("POST", format!("/digest-sent/{}", user_id))).await.unwrap();
+    let body = axum::body::to_bytes(app.oneshot(request("GET", "/digest-recipients".to_string())).await.unwrap().into_body(), usize::MAX).await.unwrap();
+
+    assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(opted_in.status(), StatusCode::OK);
+    assert_eq!(due.status(), StatusCode::OK);
+    assert_eq!(sent.status(), StatusCode::NO_CONTENT);
+    // it was just sent, so it isn't due again until tomorrow
+    assert!(!String::from_utf8_lossy(&body).contains("digest@gmail.com"));
+}
+
 
 /*
  no more rules for integrated tests , just the folder name is tests , then cargo test
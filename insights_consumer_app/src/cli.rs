@@ -0,0 +1,120 @@
+use snipsight_events::events::Envelope;
+use crate::dead_letters::{DeadLetter, DeadLetterStore};
+use crate::queue::MessageQueue;
+
+pub const USAGE: &str = "usage: insights_consumer_app dead-letters <list | inspect <id> | replay <id | --all> | purge <id | --all>>";
+
+// the lane the replayed message goes down, the malformed ones have none of their own
+const REPLAY_GROUP: &str = "replayed-event";
+
+fn targets<'a>(dead_letters: &'a [DeadLetter], id: &str) -> Result<Vec<&'a DeadLetter>, String> {
+    if id == "--all" {
+        return Ok(dead_letters.iter().collect())
+    }
+    match dead_letters.iter().find(|dead_letter| dead_letter.id == id) {
+        Some(dead_letter) => Ok(vec![dead_letter]),
+        None => Err(format!("no dead letter with the id {}", id))
+    }
+}
+
+async fn replay(dead_letter: &DeadLetter, dead_letters: &dyn DeadLetterStore, queue: &dyn MessageQueue) -> Result<(), String> {
+    let group_id = Envelope::parse(&dead_letter.body).map(|envelope| envelope.event.group_id()).unwrap_or(REPLAY_GROUP);
+    queue.send(&dead_letter.body, group_id).await?;
+    // removed only once it is back on the queue, a failed replay can be tried again
+    dead_letters.remove(&dead_letter.id).await?;
+    Ok(())
+}
+
+// the `dead-letters` subcommand, returns what has to be printed
+pub async fn dead_letters_command(args: &[String], dead_letters: &dyn DeadLetterStore, queue: &dyn MessageQueue) -> Result<String, String> {
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    match args.as_slice() {
+        ["list"] => {
+            let lines = dead_letters.list().await?.iter()
+                .map(|dead_letter| format!("{}\t{}\t{} receives\t{}", dead_letter.id, dead_letter.failed_at.to_rfc3339(), dead_letter.receive_count, dead_letter.reason))
+                .collect::<Vec<_>>();
+            Ok(if lines.is_empty() { "no dead letters".to_string() } else { lines.join("\n") })
+        },
+        ["inspect", id] => match dead_letters.get(id).await? {
+            Some(dead_letter) => Ok(serde_json::to_string_pretty(&dead_letter).unwrap()),
+            None => Err(format!("no dead letter with the id {}", id))
+        },
+        ["replay", id] => {
+            let all = dead_letters.list().await?;
+            let targets = targets(&all, id)?;
+            for dead_letter in &targets {
+                replay(dead_letter, dead_letters, queue).await.map_err(|err| format!("unable to replay {} : {}", dead_letter.id, err))?;
+            }
+            Ok(format!("replayed {} dead letters", targets.len()))
+        },
+        ["purge", id] => {
+            let all = dead_letters.list().await?;
+            let targets = targets(&all, id)?;
+            for dead_letter in &targets {
+                dead_letters.remove(&dead_letter.id).await?;
+            }
+            Ok(format!("purged {} dead letters", targets.len()))
+        },
+        _ => Err(USAGE.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use super::*;
+    use crate::dead_letters::InMemoryDeadLetterStore;
+    use crate::queue::InMemoryQueue;
+
+    async fn run(command: &str, dead_letters: &InMemoryDeadLetterStore, queue: &InMemoryQueue) -> Result<String, String> {
+        let args = command.split_whitespace().map(String::from).collect::<Vec<_>>();
+        dead_letters_command(&args, dead_letters, queue).await
+    }
+
+    async fn dead_lettered(ids: &[&str]) -> InMemoryDeadLetterStore {
+        let dead_letters = InMemoryDeadLetterStore::default();
+        for id in ids {
+            dead_letters.add(&DeadLetter {
+                id: id.to_string(),
+                body: r#"{"message_type":"DELETE_INSIGHT","shorten_url":"launch"}"#.to_string(),
+                reason: "throttled".to_string(),
+                receive_count: 5,
+                failed_at: Utc::now(),
+            }).await.unwrap();
+        }
+        dead_letters
+    }
+
+    #[tokio::test]
+    async fn dead_letters_are_listed_and_inspected() {
+        let (dead_letters, queue) = (dead_lettered(&["m1", "m2"]).await, InMemoryQueue::default());
+        let listed = run("list", &dead_letters, &queue).await.unwrap();
+        assert_eq!(listed.lines().count(), 2);
+        assert!(listed.starts_with("m1\t") && listed.contains("5 receives\tthrottled"));
+        assert!(run("inspect m2", &dead_letters, &queue).await.unwrap().contains(r#""reason": "throttled""#));
+        assert!(run("inspect m3", &dead_letters, &queue).await.is_err());
+        assert_eq!(run("list", &InMemoryDeadLetterStore::default(), &queue).await.unwrap(), "no dead letters");
+        assert_eq!(run("replay", &dead_letters, &queue).await.unwrap_err(), USAGE);
+    }
+
+    #[tokio::test]
+    async fn replayed_ones_go_back_on_the_queue() {
+        let (dead_letters, queue) = (dead_lettered(&["m1", "m2", "m3"]).await, InMemoryQueue::default());
+        assert_eq!(run("replay m2", &dead_letters, &queue).await.unwrap(), "replayed 1 dead letters");
+        assert_eq!(queue.len(), 1);
+        assert!(dead_letters.get("m2").await.unwrap().is_none());
+
+        assert_eq!(run("replay --all", &dead_letters, &queue).await.unwrap(), "replayed 2 dead letters");
+        assert_eq!(queue.len(), 3);
+        assert!(dead_letters.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn purged_ones_are_gone() {
+        let (dead_letters, queue) = (dead_lettered(&["m1", "m2"]).await, InMemoryQueue::default());
+        assert_eq!(run("purge m1", &dead_letters, &queue).await.unwrap(), "purged 1 dead letters");
+        assert!(run("purge m1", &dead_letters, &queue).await.is_err());
+        assert_eq!(run("purge --all", &dead_letters, &queue).await.unwrap(), "purged 1 dead letters");
+        assert!(queue.is_empty() && dead_letters.list().await.unwrap().is_empty());
+    }
+}
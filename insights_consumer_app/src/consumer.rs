@@ -0,0 +1,208 @@
+use std::time::Duration;
+use chrono::Utc;
+use snipsight_events::events::{Envelope, SnipSightEvent};
+use crate::dead_letters::{DeadLetter, DeadLetterStore};
+use crate::models::InsightRecord;
+use crate::queue::{MessageQueue, QueueMessage};
+use crate::store::InsightStore;
+
+// a message is received at most this many times, then it goes to the dead letters
+pub const MAX_RECEIVES: u32 = 5;
+
+enum Failure {
+    // the message itself was wrong, retrying won't help
+    Permanent(String),
+    Transient(String),
+}
+
+async fn process_message(message: &QueueMessage, store: &dyn InsightStore) -> Result<(), Failure> {
+    let envelope = Envelope::parse(&message.body).map_err(Failure::Permanent)?;
+    match envelope.event {
+        SnipSightEvent::CreateInsight(event) => {
+            // the messages from before the versioning have no click time, the time SQS got them is close enough
+            let occurred_at = envelope.occurred_at.or(message.sent_at).unwrap_or_else(Utc::now);
+            let record = InsightRecord::new(event, occurred_at, &message.id);
+            store.put_insight(&record).await.map_err(Failure::Transient)?;
+            tracing::info!("stored the insight of {} at {}", record.shorten_url, record.insight_time) ;
+        },
+        SnipSightEvent::DeleteInsight(event) => {
+            let deleted = store.delete_insights(&event.shorten_url).await.map_err(Failure::Transient)?;
+            tracing::info!("deleted {} insights of {}", deleted, event.shorten_url) ;
+        },
+        other => {
+            tracing::warn!("skipping the message {} of type {}, it was not for the consumer", message.id, other.message_type()) ;
+        }
+    }
+    Ok(())
+}
+
+// moves the message off the queue, it stays on the queue when it couldn't be stored as a dead letter
+async fn dead_letter(message: &QueueMessage, reason: String, queue: &dyn MessageQueue, dead_letters: &dyn DeadLetterStore) {
+    tracing::error!("dead lettering the message {} after {} receives : {}", message.id, message.receive_count, reason) ;
+    let dead_letter = DeadLetter {
+        id: message.id.clone(),
+        body: message.body.clone(),
+        reason,
+        receive_count: message.receive_count,
+        failed_at: Utc::now(),
+    };
+    if let Err(err) = dead_letters.add(&dead_letter).await {
+        tracing::error!("unable to store the dead letter {} : {}", message.id, err) ;
+        return
+    }
+    if let Err(err) = queue.delete(&message.receipt).await {
+        tracing::error!("dead lettered {} but unable to delete it : {}", message.id, err) ;
+    }
+}
+
+// receives one batch and processes it, a message is deleted only when it was processed successfully,
+// so the failed ones are received again after the visibility timeout. the malformed ones and the ones which
+// failed MAX_RECEIVES times go to the dead letters. returns how many were processed
+pub async fn consume_batch(queue: &dyn MessageQueue, store: &dyn InsightStore, dead_letters: &dyn DeadLetterStore) -> Result<usize, String> {
+    let messages = queue.receive().await?;
+    let mut processed = 0;
+    for message in messages {
+        match process_message(&message, store).await {
+            Ok(()) => match queue.delete(&message.receipt).await {
+                Ok(()) => processed += 1,
+                Err(err) => tracing::error!("processed {} but unable to delete it : {}", message.id, err)
+            },
+            Err(Failure::Permanent(reason)) => dead_letter(&message, reason, queue, dead_letters).await,
+            Err(Failure::Transient(reason)) if message.receive_count >= MAX_RECEIVES => dead_letter(&message, reason, queue, dead_letters).await,
+            Err(Failure::Transient(reason)) => {
+                tracing::error!("unable to process the message {} on receive {} of {} : {}", message.id, message.receive_count, MAX_RECEIVES, reason)
+            }
+        }
+    }
+    Ok(processed)
+}
+
+pub async fn run(queue: &dyn MessageQueue, store: &dyn InsightStore, dead_letters: &dyn DeadLetterStore) {
+    loop {
+        if let Err(err) = consume_batch(queue, store, dead_letters).await {
+            tracing::error!("unable to receive the messages : {}", err) ;
+            // the queue itself is failing, not hammering it
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use async_trait::async_trait;
+    use super::*;
+    use crate::dead_letters::InMemoryDeadLetterStore;
+    use crate::queue::InMemoryQueue;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        records: Mutex<Vec<InsightRecord>>,
+        failing: AtomicBool,
+    }
+
+    #[async_trait]
+    impl InsightStore for MemoryStore {
+        async fn put_insight(&self, record: &InsightRecord) -> Result<(), String> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err("table is not reachable".to_string())
+            }
+            self.records.lock().unwrap().push(record.clone());
+            Ok(())
+        }
+
+        async fn delete_insights(&self, shorten_url: &str) -> Result<usize, String> {
+            let mut records = self.records.lock().unwrap();
+            let before = records.len();
+            records.retain(|record| record.shorten_url != shorten_url);
+            Ok(before - records.len())
+        }
+    }
+
+    fn insight(shorten_url: &str) -> String {
+        format!(r#"{{"message_type":"CREATE_INSIGHT","shorten_url":"{}","ip_address":"1.2.3.4","refferal_source":"twitter.com","device_type":"Mobile","browser":"Chrome","os":"Android"}}"#, shorten_url)
+    }
+
+    #[tokio::test]
+    async fn insights_are_written_and_deleted() {
+        let (queue, store, dead_letters) = (InMemoryQueue::default(), MemoryStore::default(), InMemoryDeadLetterStore::default());
+        queue.push(&insight("launch"));
+        queue.push(&insight("launch"));
+        queue.push(&insight("go.example.com/launch"));
+        assert_eq!(consume_batch(&queue, &store, &dead_letters).await.unwrap(), 3);
+        assert_eq!(queue.len(), 0);
+        {
+            let records = store.records.lock().unwrap();
+            assert_eq!(records.len(), 3);
+            assert_eq!((records[0].browser.as_str(), records[0].location.as_str()), ("Chrome", ""));
+            assert_ne!(records[0].insight_time, records[1].insight_time);
+        }
+
+        queue.push(r#"{"message_type":"DELETE_INSIGHT","shorten_url":"launch"}"#);
+        assert_eq!(consume_batch(&queue, &store, &dead_letters).await.unwrap(), 1);
+        let records = store.records.lock().unwrap();
+        assert_eq!(records.iter().map(|record| record.shorten_url.as_str()).collect::<Vec<_>>(), vec!["go.example.com/launch"]);
+    }
+
+    #[tokio::test]
+    async fn versioned_insights_keep_their_click_time() {
+        let (queue, store, dead_letters) = (InMemoryQueue::default(), MemoryStore::default(), InMemoryDeadLetterStore::default());
+        queue.push(r#"{"event_id":"e1","schema_version":1,"occurred_at":"2026-03-01T10:00:00.123Z","message_type":"CREATE_INSIGHT","shorten_url":"launch","ip_address":"1.2.3.4","refferal_source":"https://l.facebook.com/","source":"facebook.com","channel":"social","device_type":"Mobile","browser":"Chrome","os":"Android"}"#);
+        assert_eq!(consume_batch(&queue, &store, &dead_letters).await.unwrap(), 1);
+        let record = store.records.lock().unwrap()[0].clone();
+        assert!(record.insight_time.starts_with("2026-03-01T10:00:00.123"));
+        // both the raw referrer and the normalised one are kept
+        assert_eq!((record.refferal_source.as_str(), record.source.as_str(), record.channel.as_str()), ("https://l.facebook.com/", "facebook.com", "social"));
+    }
+
+    #[tokio::test]
+    async fn failed_messages_stay_on_the_queue() {
+        let (queue, store, dead_letters) = (InMemoryQueue::default(), MemoryStore::default(), InMemoryDeadLetterStore::default());
+        store.failing.store(true, Ordering::SeqCst);
+        queue.push(&insight("launch"));
+        assert_eq!(consume_batch(&queue, &store, &dead_letters).await.unwrap(), 0);
+        assert_eq!(queue.len(), 1);
+
+        // back after the visibility timeout, the table is reachable again
+        store.failing.store(false, Ordering::SeqCst);
+        queue.expire_in_flight();
+        assert_eq!(consume_batch(&queue, &store, &dead_letters).await.unwrap(), 1);
+        assert!(queue.is_empty());
+        assert_eq!(store.records.lock().unwrap().len(), 1);
+        assert!(dead_letters.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn malformed_and_exhausted_messages_are_dead_lettered() {
+        let (queue, store, dead_letters) = (InMemoryQueue::default(), MemoryStore::default(), InMemoryDeadLetterStore::default());
+        queue.push("not json");
+        assert_eq!(consume_batch(&queue, &store, &dead_letters).await.unwrap(), 0);
+        assert!(queue.is_empty());
+        let malformed = dead_letters.list().await.unwrap();
+        assert_eq!((malformed[0].body.as_str(), malformed[0].receive_count), ("not json", 1));
+        assert!(malformed[0].reason.starts_with("invalid event"));
+
+        store.failing.store(true, Ordering::SeqCst);
+        queue.push(&insight("launch"));
+        for _ in 1..MAX_RECEIVES {
+            consume_batch(&queue, &store, &dead_letters).await.unwrap();
+            queue.expire_in_flight();
+            assert_eq!(queue.len(), 1);
+        }
+        consume_batch(&queue, &store, &dead_letters).await.unwrap();
+        assert!(queue.is_empty());
+        let exhausted = dead_letters.list().await.unwrap().pop().unwrap();
+        assert_eq!((exhausted.receive_count, exhausted.reason.as_str()), (MAX_RECEIVES, "table is not reachable"));
+    }
+
+    #[tokio::test]
+    async fn other_message_types_are_skipped() {
+        let (queue, store, dead_letters) = (InMemoryQueue::default(), MemoryStore::default(), InMemoryDeadLetterStore::default());
+        queue.push(r#"{"message_type":"BROKEN_LINK","url_id":7,"user_id":1,"shorten_url":"launch","original_url":"https://example.com","status":404,"error":"","failure_streak":3}"#);
+        queue.push(r#"{"schema_version":1,"message_type":"LINK_ARCHIVED","shorten_url":"launch"}"#);
+        assert_eq!(consume_batch(&queue, &store, &dead_letters).await.unwrap(), 2);
+        assert_eq!(queue.len(), 0);
+        assert!(store.records.lock().unwrap().is_empty());
+    }
+}
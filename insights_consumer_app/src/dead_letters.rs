@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// a message the consumer gave up on, kept with the reason until it was replayed or purged
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    pub id: String, // the queue's message id
+    pub body: String,
+    pub reason: String,
+    pub receive_count: u32,
+    pub failed_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait DeadLetterStore: Send + Sync {
+    async fn add(&self, dead_letter: &DeadLetter) -> Result<(), String>;
+    // oldest first
+    async fn list(&self) -> Result<Vec<DeadLetter>, String>;
+    async fn get(&self, id: &str) -> Result<Option<DeadLetter>, String>;
+    // returns false when there was no dead letter with the id
+    async fn remove(&self, id: &str) -> Result<bool, String>;
+}
+
+// in-process dead letters, used along with the InMemoryQueue
+#[derive(Default)]
+pub struct InMemoryDeadLetterStore {
+    dead_letters: Mutex<Vec<DeadLetter>>,
+}
+
+#[async_trait]
+impl DeadLetterStore for InMemoryDeadLetterStore {
+    async fn add(&self, dead_letter: &DeadLetter) -> Result<(), String> {
+        let mut dead_letters = self.dead_letters.lock().unwrap();
+        dead_letters.retain(|existing| existing.id != dead_letter.id);
+        dead_letters.push(dead_letter.clone());
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<DeadLetter>, String> {
+        Ok(self.dead_letters.lock().unwrap().clone())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<DeadLetter>, String> {
+        Ok(self.dead_letters.lock().unwrap().iter().find(|dead_letter| dead_letter.id == id).cloned())
+    }
+
+    async fn remove(&self, id: &str) -> Result<bool, String> {
+        let mut dead_letters = self.dead_letters.lock().unwrap();
+        let before = dead_letters.len();
+        dead_letters.retain(|dead_letter| dead_letter.id != id);
+        Ok(dead_letters.len() < before)
+    }
+}
+
+// the dead letters of the SQS backend, a table with `id` as the partition key
+pub struct DynamoDeadLetterStore {
+    client: Client,
+    table: String,
+}
+
+impl DynamoDeadLetterStore {
+    pub fn new(client: Client, table: String) -> Self {
+        Self { client, table }
+    }
+}
+
+fn from_item(item: &HashMap<String, AttributeValue>) -> Option<DeadLetter> {
+    let text = |name: &str| item.get(name).and_then(|value| value.as_s().ok()).cloned();
+    Some(DeadLetter {
+        id: text("id")?,
+        body: text("body")?,
+        reason: text("reason").unwrap_or_default(),
+        receive_count: item.get("receive_count").and_then(|value| value.as_n().ok()).and_then(|count| count.parse().ok()).unwrap_or_default(),
+        failed_at: text("failed_at").and_then(|failed_at| failed_at.parse().ok()).unwrap_or_default(),
+    })
+}
+
+#[async_trait]
+impl DeadLetterStore for DynamoDeadLetterStore {
+    async fn add(&self, dead_letter: &DeadLetter) -> Result<(), String> {
+        self.client.put_item()
+            .table_name(&self.table)
+            .item("id", AttributeValue::S(dead_letter.id.clone()))
+            .item("body", AttributeValue::S(dead_letter.body.clone()))
+            .item("reason", AttributeValue::S(dead_letter.reason.clone()))
+            .item("receive_count", AttributeValue::N(dead_letter.receive_count.to_string()))
+            .item("failed_at", AttributeValue::S(dead_letter.failed_at.to_rfc3339()))
+            .send().await
+            .map_err(|e| format!("unable to put the dead letter: {:?}", e))?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<DeadLetter>, String> {
+        let mut dead_letters = Vec::new();
+        let mut start_key = None;
+        loop {
+            let output = self.client.scan().table_name(&self.table).set_exclusive_start_key(start_key).send().await
+                .map_err(|e| format!("unable to scan the dead letters: {:?}", e))?;
+            dead_letters.extend(output.items().iter().filter_map(from_item));
+            match output.last_evaluated_key() {
+                Some(key) => start_key = Some(key.clone()),
+                None => break
+            }
+        }
+        dead_letters.sort_by_key(|dead_letter| dead_letter.failed_at);
+        Ok(dead_letters)
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<DeadLetter>, String> {
+        let output = self.client.get_item().table_name(&self.table).key("id", AttributeValue::S(id.to_string())).send().await
+            .map_err(|e| format!("unable to get the dead letter: {:?}", e))?;
+        Ok(output.item().and_then(from_item))
+    }
+
+    async fn remove(&self, id: &str) -> Result<bool, String> {
+        let output = self.client.delete_item().table_name(&self.table).key("id", AttributeValue::S(id.to_string()))
+            .return_values(ReturnValue::AllOld).send().await
+            .map_err(|e| format!("unable to delete the dead letter: {:?}", e))?;
+        Ok(output.attributes().is_some())
+    }
+}
+
+// the dead letters of the file backend, one json file per dead letter
+pub struct FileDeadLetterStore {
+    dir: PathBuf,
+}
+
+impl FileDeadLetterStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf, String> {
+        // the id ends up in a file name, it shouldn't be able to point outside the directory
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("{:?} is not a valid dead letter id", id))
+        }
+        Ok(self.dir.join(format!("{}.json", id)))
+    }
+}
+
+#[async_trait]
+impl DeadLetterStore for FileDeadLetterStore {
+    async fn add(&self, dead_letter: &DeadLetter) -> Result<(), String> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| format!("unable to create {:?}: {}", self.dir, e))?;
+        let path = self.path(&dead_letter.id)?;
+        std::fs::write(&path, serde_json::to_string_pretty(dead_letter).unwrap()).map_err(|e| format!("unable to write {:?}: {}", path, e))
+    }
+
+    async fn list(&self) -> Result<Vec<DeadLetter>, String> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            // nothing was dead lettered yet
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("unable to read {:?}: {}", self.dir, e)),
+        };
+        let mut dead_letters = entries.filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .filter_map(|path| serde_json::from_str::<DeadLetter>(&std::fs::read_to_string(path).ok()?).ok())
+            .collect::<Vec<_>>();
+        dead_letters.sort_by_key(|dead_letter| dead_letter.failed_at);
+        Ok(dead_letters)
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<DeadLetter>, String> {
+        let path = self.path(id)?;
+        match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).map(Some).map_err(|e| format!("{:?} was not a dead letter: {}", path, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("unable to read {:?}: {}", path, e)),
+        }
+    }
+
+    async fn remove(&self, id: &str) -> Result<bool, String> {
+        let path = self.path(id)?;
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(format!("unable to remove {:?}: {}", path, e)),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+// the queue and store backends are public, so the other services' tests can run the pipeline in-process
+pub mod cli;
+pub mod consumer;
+pub mod dead_letters;
+pub mod models;
+pub mod queue;
+pub mod store;
@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+use aws_config::BehaviorVersion;
+use aws_sdk_dynamodb::Client as DynamoClient;
+use aws_sdk_sqs::Client as SqsClient;
+use insights_consumer_app::{cli, consumer};
+use insights_consumer_app::dead_letters::{DeadLetterStore, DynamoDeadLetterStore, FileDeadLetterStore};
+use insights_consumer_app::queue::{FileQueue, MessageQueue, SqsQueue};
+use insights_consumer_app::store::DynamoInsightStore;
+
+const DEFAULT_QUEUE_URL: &str = "https://sqs.ap-south-1.amazonaws.com/637423550786/snipsightmessages.fifo";
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+
+    // the file backend lets the pipeline run locally without SQS, the insights still go to DynamoDB
+    let (queue, dead_letters): (Box<dyn MessageQueue>, Box<dyn DeadLetterStore>) = match std::env::var("QUEUE_BACKEND").unwrap_or("sqs".to_string()).as_str() {
+        "file" => {
+            let dir = PathBuf::from(std::env::var("FILE_QUEUE_DIR").unwrap_or("./queue".to_string()));
+            let dead_letter_dir = PathBuf::from(std::env::var("DEAD_LETTER_DIR").unwrap_or("./dead-letters".to_string()));
+            tracing::info!("consuming the messages from the directory {:?}", dir) ;
+            (Box::new(FileQueue::new(dir)), Box::new(FileDeadLetterStore::new(dead_letter_dir)))
+        },
+        _ => {
+            let queue_url = std::env::var("SQS_QUEUE_URL").unwrap_or(DEFAULT_QUEUE_URL.to_string());
+            let dead_letter_table = std::env::var("DEAD_LETTER_TABLE").unwrap_or("SnipSightDeadLetters".to_string());
+            tracing::info!("consuming the messages from {}", queue_url) ;
+            (Box::new(SqsQueue::new(SqsClient::new(&config), queue_url)), Box::new(DynamoDeadLetterStore::new(DynamoClient::new(&config), dead_letter_table)))
+        }
+    };
+
+    // `insights_consumer_app dead-letters ...` manages the dead letters instead of consuming
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.first().is_some_and(|command| command == "dead-letters") {
+        match cli::dead_letters_command(&args[1..], dead_letters.as_ref(), queue.as_ref()).await {
+            Ok(output) => println!("{}", output),
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        }
+        return
+    }
+
+    let table = std::env::var("INSIGHTS_TABLE").unwrap_or("ShortenURLInsights".to_string());
+    let store = DynamoInsightStore::new(DynamoClient::new(&config), table);
+
+    consumer::run(queue.as_ref(), &store, dead_letters.as_ref()).await;
+}
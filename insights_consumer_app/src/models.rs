@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use snipsight_events::events::CreateInsight;
+
+// one item of the ShortenURLInsights table, shorten_url is the partition key and insight_time the sort key
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsightRecord {
+    pub shorten_url: String,
+    pub insight_time: String,
+    pub ip_address: String,
+    pub refferal_source: String,
+    pub source: String, // the normalised referrer or utm_source, empty for the events from before it
+    pub channel: String,
+    pub device_type: String,
+    pub browser: String,
+    pub os: String,
+    pub location: String,
+}
+
+impl InsightRecord {
+    pub fn new(event: CreateInsight, occurred_at: DateTime<Utc>, message_id: &str) -> Self {
+        InsightRecord {
+            shorten_url: event.shorten_url,
+            insight_time: insight_time(occurred_at, message_id),
+            ip_address: event.ip_address,
+            refferal_source: event.referral_source,
+            source: event.source,
+            channel: event.channel,
+            device_type: event.device_type,
+            browser: event.browser,
+            os: event.os,
+            // the location of the visitor isn't collected yet, the clicks show up as "Unknown" in the rollups and digests
+            location: String::new(),
+        }
+    }
+}
+
+// sortable, so the newest insights come first with scan_index_forward(false). SQS only gives the sent
+// time in milliseconds, the microseconds come from the message id, so two clicks in the same millisecond
+// don't overwrite each other and a redelivered message overwrites its own item
+pub fn insight_time(sent_at: DateTime<Utc>, message_id: &str) -> String {
+    let micros = message_id.bytes().fold(0u32, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte as u32)) % 1000;
+    format!("{}{:03}Z", sent_at.format("%Y-%m-%dT%H:%M:%S%.3f"), micros)
+}
@@ -0,0 +1,233 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use aws_sdk_sqs::Client;
+use aws_sdk_sqs::types::MessageSystemAttributeName;
+use chrono::{DateTime, Utc};
+
+// SQS allows at most 10 messages per receive and 20 seconds of long polling
+const MAX_MESSAGES: i32 = 10;
+const WAIT_TIME_SECONDS: i32 = 20;
+// the file queue's stand-in for the SQS visibility timeout
+const FILE_VISIBILITY_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueMessage {
+    pub id: String,
+    pub receipt: String, // used to delete the message once it was processed
+    pub body: String,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub receive_count: u32, // this receive included, the consumer gives up on a message after a few
+}
+
+#[async_trait]
+pub trait MessageQueue: Send + Sync {
+    // waits for the messages, an empty list means nothing came in the polling time
+    async fn receive(&self) -> Result<Vec<QueueMessage>, String>;
+    async fn delete(&self, receipt: &str) -> Result<(), String>;
+    // puts a message on the queue, used while replaying the dead letters
+    async fn send(&self, body: &str, group_id: &str) -> Result<(), String>;
+}
+
+pub struct SqsQueue {
+    client: Client,
+    queue_url: String,
+}
+
+impl SqsQueue {
+    pub fn new(client: Client, queue_url: String) -> Self {
+        Self { client, queue_url }
+    }
+}
+
+#[async_trait]
+impl MessageQueue for SqsQueue {
+    async fn receive(&self) -> Result<Vec<QueueMessage>, String> {
+        let output = self.client.receive_message()
+            .queue_url(&self.queue_url)
+            .max_number_of_messages(MAX_MESSAGES)
+            .wait_time_seconds(WAIT_TIME_SECONDS)
+            .message_system_attribute_names(MessageSystemAttributeName::SentTimestamp)
+            .message_system_attribute_names(MessageSystemAttributeName::ApproximateReceiveCount)
+            .send().await
+            .map_err(|e| format!("unable to receive from SQS: {:?}", e))?;
+        Ok(output.messages().iter().map(|message| QueueMessage {
+            id: message.message_id().unwrap_or_default().to_string(),
+            receipt: message.receipt_handle().unwrap_or_default().to_string(),
+            body: message.body().unwrap_or_default().to_string(),
+            sent_at: message.attributes()
+                .and_then(|attributes| attributes.get(&MessageSystemAttributeName::SentTimestamp))
+                .and_then(|millis| millis.parse::<i64>().ok())
+                .and_then(DateTime::from_timestamp_millis),
+            receive_count: message.attributes()
+                .and_then(|attributes| attributes.get(&MessageSystemAttributeName::ApproximateReceiveCount))
+                .and_then(|count| count.parse().ok())
+                .unwrap_or(1),
+        }).collect())
+    }
+
+    async fn delete(&self, receipt: &str) -> Result<(), String> {
+        self.client.delete_message().queue_url(&self.queue_url).receipt_handle(receipt).send().await
+            .map_err(|e| format!("unable to delete from SQS: {:?}", e))?;
+        Ok(())
+    }
+
+    async fn send(&self, body: &str, group_id: &str) -> Result<(), String> {
+        self.client.send_message().queue_url(&self.queue_url).message_body(body).message_group_id(group_id)
+            .message_deduplication_id(uuid::Uuid::new_v4().to_string()).send().await
+            .map_err(|e| format!("unable to send to SQS: {:?}", e))?;
+        Ok(())
+    }
+}
+
+// in-process queue, the messages which were received but not deleted stay in flight like in SQS
+#[derive(Default)]
+pub struct InMemoryQueue {
+    pending: Mutex<VecDeque<QueueMessage>>,
+    in_flight: Mutex<HashMap<String, QueueMessage>>,
+}
+
+impl InMemoryQueue {
+    pub fn push(&self, body: &str) {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.pending.lock().unwrap().push_back(QueueMessage { id: id.clone(), receipt: id, body: body.to_string(), sent_at: Some(Utc::now()), receive_count: 0 });
+    }
+
+    // puts the undeleted messages back, what the visibility timeout does in SQS
+    pub fn expire_in_flight(&self) {
+        let expired = std::mem::take(&mut *self.in_flight.lock().unwrap());
+        self.pending.lock().unwrap().extend(expired.into_values());
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.lock().unwrap().len() + self.in_flight.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl MessageQueue for InMemoryQueue {
+    async fn receive(&self) -> Result<Vec<QueueMessage>, String> {
+        let mut pending = self.pending.lock().unwrap();
+        let count = pending.len().min(MAX_MESSAGES as usize);
+        let messages: Vec<QueueMessage> = pending.drain(..count)
+            .map(|message| QueueMessage { receive_count: message.receive_count + 1, ..message })
+            .collect();
+        let mut in_flight = self.in_flight.lock().unwrap();
+        for message in &messages {
+            in_flight.insert(message.receipt.clone(), message.clone());
+        }
+        Ok(messages)
+    }
+
+    async fn delete(&self, receipt: &str) -> Result<(), String> {
+        self.in_flight.lock().unwrap().remove(receipt).map(|_| ()).ok_or(format!("no message in flight for {}", receipt))
+    }
+
+    async fn send(&self, body: &str, _group_id: &str) -> Result<(), String> {
+        self.push(body);
+        Ok(())
+    }
+}
+
+// every *.json file in the directory is a message, the file is removed when it was deleted.
+// the receive counts are only kept in memory, they start over when the consumer restarts
+pub struct FileQueue {
+    dir: PathBuf,
+    in_flight: Mutex<HashMap<PathBuf, Instant>>,
+    receive_counts: Mutex<HashMap<PathBuf, u32>>,
+}
+
+impl FileQueue {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, in_flight: Mutex::new(HashMap::new()), receive_counts: Mutex::new(HashMap::new()) }
+    }
+}
+
+#[async_trait]
+impl MessageQueue for FileQueue {
+    async fn receive(&self) -> Result<Vec<QueueMessage>, String> {
+        let mut files: Vec<(std::time::SystemTime, PathBuf)> = std::fs::read_dir(&self.dir)
+            .map_err(|e| format!("unable to read {:?}: {}", self.dir, e))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .filter_map(|path| Some((std::fs::metadata(&path).ok()?.modified().ok()?, path)))
+            .collect();
+        // oldest first, the way they were written
+        files.sort();
+
+        let mut messages = Vec::new();
+        {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            let mut receive_counts = self.receive_counts.lock().unwrap();
+            // the ones which were not deleted in time are visible again
+            in_flight.retain(|_, received| received.elapsed() < FILE_VISIBILITY_TIMEOUT);
+            for (modified, path) in files {
+                if messages.len() == MAX_MESSAGES as usize {
+                    break
+                }
+                if in_flight.contains_key(&path) {
+                    continue
+                }
+                let body = std::fs::read_to_string(&path).map_err(|e| format!("unable to read {:?}: {}", path, e))?;
+                in_flight.insert(path.clone(), Instant::now());
+                let receive_count = receive_counts.entry(path.clone()).or_default();
+                *receive_count += 1;
+                messages.push(QueueMessage {
+                    id: path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+                    receipt: path.to_string_lossy().to_string(),
+                    body,
+                    sent_at: Some(DateTime::<Utc>::from(modified)),
+                    receive_count: *receive_count,
+                });
+            }
+        }
+        if messages.is_empty() {
+            // no long polling on files, just not spinning on an empty directory
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        Ok(messages)
+    }
+
+    async fn delete(&self, receipt: &str) -> Result<(), String> {
+        let path = PathBuf::from(receipt);
+        std::fs::remove_file(&path).map_err(|e| format!("unable to remove {:?}: {}", path, e))?;
+        self.in_flight.lock().unwrap().remove(&path);
+        self.receive_counts.lock().unwrap().remove(&path);
+        Ok(())
+    }
+
+    async fn send(&self, body: &str, _group_id: &str) -> Result<(), String> {
+        let path = self.dir.join(format!("{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, body).map_err(|e| format!("unable to write {:?}: {}", path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn file_queue_hides_received_files_until_deleted() {
+        let dir = std::env::temp_dir().join(format!("snipsight-queue-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("1.json"), r#"{"message_type":"DELETE_INSIGHT","shorten_url":"launch"}"#).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a message").unwrap();
+        let queue = FileQueue::new(dir.clone());
+
+        let messages = queue.receive().await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!((messages[0].id.as_str(), messages[0].receive_count), ("1", 1));
+        assert!(queue.receive().await.unwrap().is_empty());
+
+        queue.delete(&messages[0].receipt).await.unwrap();
+        assert!(!dir.join("1.json").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
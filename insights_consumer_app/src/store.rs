@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use snipsight_events::insights::delete_insights;
+use crate::models::InsightRecord;
+
+#[async_trait]
+pub trait InsightStore: Send + Sync {
+    async fn put_insight(&self, record: &InsightRecord) -> Result<(), String>;
+    // returns how many items were deleted
+    async fn delete_insights(&self, shorten_url: &str) -> Result<usize, String>;
+}
+
+pub struct DynamoInsightStore {
+    client: Client,
+    table: String,
+}
+
+impl DynamoInsightStore {
+    pub fn new(client: Client, table: String) -> Self {
+        Self { client, table }
+    }
+
+    async fn insight_keys(&self, shorten_url: &str) -> Result<Vec<HashMap<String, AttributeValue>>, String> {
+        let mut keys = Vec::new();
+        let mut start_key = None;
+        loop {
+            let output = self.client.query()
+                .table_name(&self.table)
+                .key_condition_expression("shorten_url = :s")
+                .expression_attribute_values(":s", AttributeValue::S(shorten_url.to_string()))
+                .projection_expression("shorten_url, insight_time")
+                .set_exclusive_start_key(start_key)
+                .send().await
+                .map_err(|e| format!("unable to query the insights: {:?}", e))?;
+            keys.extend(output.items().iter().cloned());
+            match output.last_evaluated_key() {
+                Some(key) => start_key = Some(key.clone()),
+                None => return Ok(keys)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl InsightStore for DynamoInsightStore {
+    async fn put_insight(&self, record: &InsightRecord) -> Result<(), String> {
+        self.client.put_item()
+            .table_name(&self.table)
+            .item("shorten_url", AttributeValue::S(record.shorten_url.clone()))
+            .item("insight_time", AttributeValue::S(record.insight_time.clone()))
+            .item("ip_address", AttributeValue::S(record.ip_address.clone()))
+            .item("refferal_source", AttributeValue::S(record.refferal_source.clone()))
+            .item("source", AttributeValue::S(record.source.clone()))
+            .item("channel", AttributeValue::S(record.channel.clone()))
+            .item("device_type", AttributeValue::S(record.device_type.clone()))
+            .item("browser", AttributeValue::S(record.browser.clone()))
+            .item("os", AttributeValue::S(record.os.clone()))
+            .item("location", AttributeValue::S(record.location.clone()))
+            .send().await
+            .map_err(|e| format!("unable to put the insight: {:?}", e))?;
+        Ok(())
+    }
+
+    async fn delete_insights(&self, shorten_url: &str) -> Result<usize, String> {
+        let keys = self.insight_keys(shorten_url).await?;
+        delete_insights(&self.client, &self.table, &keys).await?;
+        Ok(keys.len())
+    }
+}
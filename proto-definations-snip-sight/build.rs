@@ -1,10 +1,47 @@
+// the Timestamp fields, prost_types::Timestamp has no serde so they go through crate::timestamp
+const TIMESTAMP_FIELDS: [&str; 12] = [
+    "Insight.insight_time",
+    "ExportedInsight.insight_time",
+    "Urls.created_at",
+    "Urls.health_checked_at",
+    "CustomDomain.created_at",
+    "Folder.created_at",
+    "LinkHealth.checked_at",
+    "WebhookEndpoint.created_at",
+    "WebhookRule.created_at",
+    "WebhookDelivery.created_at",
+    "WebhookDelivery.delivered_at",
+    "BioPage.created_at",
+];
+
+// the deprecated string fields of the times, only for the old gRPC clients so they are left out of the json
+const LEGACY_TIME_FIELDS: [&str; 11] = [
+    "Insight.legacy_insight_time",
+    "ExportedInsight.legacy_insight_time",
+    "Urls.legacy_created_at",
+    "Urls.legacy_health_checked_at",
+    "CustomDomain.legacy_created_at",
+    "Folder.legacy_created_at",
+    "LinkHealth.legacy_checked_at",
+    "WebhookEndpoint.legacy_created_at",
+    "WebhookRule.legacy_created_at",
+    "WebhookDelivery.legacy_created_at",
+    "WebhookDelivery.legacy_delivered_at",
+];
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tonic_build::configure()
+    let mut config = tonic_build::configure()
         .build_server(true)
         .build_client(true)
         .out_dir("src/generated") // every thing goes over here
-        .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]")
-        .compile_protos(
+        .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]");
+    for field in TIMESTAMP_FIELDS {
+        config = config.field_attribute(format!(".url_shortner.{}", field), "#[serde(default, with = \"crate::timestamp\")]");
+    }
+    for field in LEGACY_TIME_FIELDS {
+        config = config.field_attribute(format!(".url_shortner.{}", field), "#[serde(skip)]");
+    }
+    config.compile_protos(
             &["proto/shortner.proto",
                 "proto/file_sharing.proto",
                 "proto/payments.proto",
@@ -6,16 +6,20 @@ pub struct KeyInsights {
     pub list: ::prost::alloc::vec::Vec<Insight>,
     #[prost(string, tag = "2")]
     pub shorten_url: ::prost::alloc::string::String,
+    /// passed as the page_token of the next page, empty on the last page
     #[prost(string, tag = "3")]
-    pub insight_time: ::prost::alloc::string::String,
+    pub next_page_token: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Insight {
     #[prost(string, tag = "1")]
     pub ip_address: ::prost::alloc::string::String,
+    /// the old string insight_time, still filled for the old clients
+    #[deprecated]
     #[prost(string, tag = "2")]
-    pub insight_time: ::prost::alloc::string::String,
+    #[serde(skip)]
+    pub legacy_insight_time: ::prost::alloc::string::String,
     #[prost(string, tag = "3")]
     pub browser: ::prost::alloc::string::String,
     #[prost(string, tag = "4")]
@@ -24,8 +28,19 @@ pub struct Insight {
     pub location: ::prost::alloc::string::String,
     #[prost(string, tag = "6")]
     pub os: ::prost::alloc::string::String,
+    /// the raw referer header, "Direct" without one
     #[prost(string, tag = "7")]
     pub refferal_source: ::prost::alloc::string::String,
+    /// the referrer's registrable domain or the utm_source, empty for the insights from before it
+    #[prost(string, tag = "8")]
+    pub source: ::prost::alloc::string::String,
+    /// social, search, email, qr, direct or other
+    #[prost(string, tag = "9")]
+    pub channel: ::prost::alloc::string::String,
+    /// the click time, missing when it wasn't a valid time
+    #[prost(message, optional, tag = "10")]
+    #[serde(default, with = "crate::timestamp")]
+    pub insight_time: ::core::option::Option<::prost_types::Timestamp>,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -33,6 +48,24 @@ pub struct Url {
     /// in response it returns original url in request it passes shorten Url
     #[prost(string, tag = "1")]
     pub url: ::prost::alloc::string::String,
+    /// host the short url was requested on, in response the custom domain it was resolved to (empty for ours)
+    #[prost(string, tag = "2")]
+    pub domain: ::prost::alloc::string::String,
+    /// in response, why the destination was flagged as unsafe (empty when it was safe)
+    #[prost(string, tag = "3")]
+    pub disabled_reason: ::prost::alloc::string::String,
+    /// in response, empty when the link is live, "upcoming" before its window and "ended" after it
+    #[prost(string, tag = "4")]
+    pub activation_state: ::prost::alloc::string::String,
+    /// in response, start of the window (RFC 3339, empty for none)
+    #[prost(string, tag = "5")]
+    pub active_from: ::prost::alloc::string::String,
+    /// in response, where the upcoming link sends the visitors, empty shows the coming soon page
+    #[prost(string, tag = "6")]
+    pub fallback_url: ::prost::alloc::string::String,
+    /// in response, shown on the coming soon page
+    #[prost(string, tag = "7")]
+    pub coming_soon_message: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -41,11 +74,37 @@ pub struct GetInsights {
     pub page_size: u32,
     #[prost(string, tag = "2")]
     pub shorten_url: ::prost::alloc::string::String,
+    /// empty for the first page
     #[prost(string, tag = "3")]
-    pub last_evaluated_key: ::prost::alloc::string::String,
+    pub page_token: ::prost::alloc::string::String,
+    /// custom domain of the shorten url, empty for ours
+    #[prost(string, tag = "4")]
+    pub domain: ::prost::alloc::string::String,
+    /// RFC 3339, inclusive, empty for no lower bound
+    #[prost(string, tag = "5")]
+    pub from: ::prost::alloc::string::String,
+    /// RFC 3339, inclusive, empty for no upper bound
+    #[prost(string, tag = "6")]
+    pub to: ::prost::alloc::string::String,
+    /// only the insights with exactly these values, the empty ones don't filter
+    #[prost(string, tag = "7")]
+    pub browser: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub os: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub device_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub referrer: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub location: ::prost::alloc::string::String,
+    /// the caller, only the members of the link's workspace get its insights
+    #[prost(int32, tag = "12")]
+    pub user_id: i32,
+    #[prost(int32, tag = "13")]
+    pub workspace_id: i32,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct User {
     #[prost(int32, tag = "1")]
     pub user_id: i32,
@@ -53,6 +112,14 @@ pub struct User {
     pub page_number: u32,
     #[prost(uint32, tag = "3")]
     pub page_size: u32,
+    /// only the links with this tag, empty for all
+    #[prost(string, tag = "4")]
+    pub tag: ::prost::alloc::string::String,
+    /// only the links in this folder, 0 for all
+    #[prost(int32, tag = "5")]
+    pub folder_id: i32,
+    #[prost(int32, tag = "6")]
+    pub workspace_id: i32,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -73,9 +140,246 @@ pub struct Urls {
     pub shorten_url: ::prost::alloc::string::String,
     #[prost(int32, tag = "4")]
     pub view_count: i32,
-    /// here we need to change the type to timestamp
+    /// the old string created_at, still filled for the old clients
+    #[deprecated]
+    #[prost(string, tag = "5")]
+    #[serde(skip)]
+    pub legacy_created_at: ::prost::alloc::string::String,
+    /// custom domain of the link, empty when it was on our domain
+    #[prost(string, tag = "6")]
+    pub domain: ::prost::alloc::string::String,
+    /// why the destination was flagged as unsafe, empty when it was safe
+    #[prost(string, tag = "7")]
+    pub disabled_reason: ::prost::alloc::string::String,
+    /// last http status of the destination, 0 when it was not checked or didn't respond
+    #[prost(int32, tag = "8")]
+    pub health_status: i32,
+    #[prost(int32, tag = "9")]
+    pub health_latency_ms: i32,
+    /// consecutive failed checks
+    #[prost(int32, tag = "10")]
+    pub health_failure_streak: i32,
+    #[deprecated]
+    #[prost(string, tag = "11")]
+    #[serde(skip)]
+    pub legacy_health_checked_at: ::prost::alloc::string::String,
+    /// RFC 3339, empty when the link was live from the creation
+    #[prost(string, tag = "12")]
+    pub active_from: ::prost::alloc::string::String,
+    /// RFC 3339, empty when the link never ends
+    #[prost(string, tag = "13")]
+    pub active_until: ::prost::alloc::string::String,
+    #[prost(string, tag = "14")]
+    pub fallback_url: ::prost::alloc::string::String,
+    #[prost(string, tag = "15")]
+    pub coming_soon_message: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "16")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// 0 when it was not in any folder
+    #[prost(int32, tag = "17")]
+    pub folder_id: i32,
+    #[prost(string, tag = "18")]
+    pub folder: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "19")]
+    #[serde(default, with = "crate::timestamp")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// missing when it was not checked yet
+    #[prost(message, optional, tag = "20")]
+    #[serde(default, with = "crate::timestamp")]
+    pub health_checked_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// only in the response of getUrlPreview, the side of the activation window as for the redirects
+    #[prost(message, optional, tag = "21")]
+    pub window: ::core::option::Option<Url>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CustomDomain {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    #[prost(string, tag = "3")]
+    pub domain: ::prost::alloc::string::String,
+    /// needs to be added as TXT record "snipsight-verification=<token>" on _snipsight.<domain>
+    #[prost(string, tag = "4")]
+    pub verification_token: ::prost::alloc::string::String,
+    #[prost(bool, tag = "5")]
+    pub verified: bool,
+    #[deprecated]
+    #[prost(string, tag = "6")]
+    #[serde(skip)]
+    pub legacy_created_at: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "7")]
+    #[serde(default, with = "crate::timestamp")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(int32, tag = "8")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CustomDomainsList {
+    #[prost(message, repeated, tag = "1")]
+    pub list: ::prost::alloc::vec::Vec<CustomDomain>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct VerifiedDomainsRequest {}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockedName {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    /// exact name or a regex when is_pattern, both are matched against the normalised custom name
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub is_pattern: bool,
+    #[prost(string, tag = "4")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockedNamesList {
+    #[prost(message, repeated, tag = "1")]
+    pub list: ::prost::alloc::vec::Vec<BlockedName>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BlockedNamesRequest {}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Tag {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    /// stored lowercased, unique per workspace
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(int32, tag = "4")]
+    pub link_count: i32,
+    #[prost(int32, tag = "5")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TagsList {
+    #[prost(message, repeated, tag = "1")]
+    pub list: ::prost::alloc::vec::Vec<Tag>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UrlTags {
+    /// id of the url
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    /// replaces the tags of the link, missing tags are created
+    #[prost(string, repeated, tag = "3")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(int32, tag = "4")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Folder {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    /// unique per workspace
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(int32, tag = "4")]
+    pub link_count: i32,
+    /// sum of the view counts of its links
+    #[prost(int64, tag = "5")]
+    pub total_clicks: i64,
+    #[deprecated]
+    #[prost(string, tag = "6")]
+    #[serde(skip)]
+    pub legacy_created_at: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "7")]
+    #[serde(default, with = "crate::timestamp")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(int32, tag = "8")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FoldersList {
+    #[prost(message, repeated, tag = "1")]
+    pub list: ::prost::alloc::vec::Vec<Folder>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct UrlFolder {
+    /// id of the url
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    /// 0 takes the link out of its folder
+    #[prost(int32, tag = "3")]
+    pub folder_id: i32,
+    #[prost(int32, tag = "4")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CampaignInsights {
+    #[prost(message, optional, tag = "1")]
+    pub folder: ::core::option::Option<Folder>,
+    /// the links of the campaign with the most clicks first
+    #[prost(message, repeated, tag = "2")]
+    pub top_links: ::prost::alloc::vec::Vec<Urls>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ActivationWindow {
+    /// id of the url
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    /// RFC 3339, empty clears it
+    #[prost(string, tag = "3")]
+    pub active_from: ::prost::alloc::string::String,
+    /// RFC 3339, empty clears it
+    #[prost(string, tag = "4")]
+    pub active_until: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub fallback_url: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub coming_soon_message: ::prost::alloc::string::String,
+    #[prost(int32, tag = "7")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LinkHealth {
+    /// id of the url
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    /// 0 when the destination didn't respond
+    #[prost(int32, tag = "2")]
+    pub status: i32,
+    #[prost(int32, tag = "3")]
+    pub latency_ms: i32,
+    #[prost(int32, tag = "4")]
+    pub failure_streak: i32,
+    #[deprecated]
     #[prost(string, tag = "5")]
-    pub created_at: ::prost::alloc::string::String,
+    #[serde(skip)]
+    pub legacy_checked_at: ::prost::alloc::string::String,
+    /// timeout, connection or status error of the last failed check
+    #[prost(string, tag = "6")]
+    pub error: ::prost::alloc::string::String,
+    /// missing when it was not checked yet
+    #[prost(message, optional, tag = "7")]
+    #[serde(default, with = "crate::timestamp")]
+    pub checked_at: ::core::option::Option<::prost_types::Timestamp>,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -87,6 +391,8 @@ pub struct CustomName {
     /// the new custom name, before that we need to check whether the user was premium member or having credits at least
     #[prost(string, tag = "3")]
     pub custom_name: ::prost::alloc::string::String,
+    #[prost(int32, tag = "4")]
+    pub workspace_id: i32,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -103,9 +409,11 @@ pub struct UrlId {
     /// id of the url
     #[prost(int32, tag = "1")]
     pub id: i32,
-    /// it helps to check whether the id belongs to the specific user-id or not
+    /// the caller, it has to be a member of the workspace the link belongs to
     #[prost(int32, tag = "2")]
     pub user_id: i32,
+    #[prost(int32, tag = "3")]
+    pub workspace_id: i32,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -128,6 +436,48 @@ pub struct CreateShortenUrlPayload {
     pub custom_url: ::prost::alloc::string::String,
     #[prost(int32, tag = "3")]
     pub user_id: i32,
+    /// when true the destination's own og tags are used as the default social preview
+    #[prost(bool, tag = "4")]
+    pub fetch_social_preview: bool,
+    /// verified custom domain of the user, empty means our domain
+    #[prost(string, tag = "5")]
+    pub domain: ::prost::alloc::string::String,
+    /// RFC 3339, empty means live from now
+    #[prost(string, tag = "6")]
+    pub active_from: ::prost::alloc::string::String,
+    /// RFC 3339, empty means it never ends
+    #[prost(string, tag = "7")]
+    pub active_until: ::prost::alloc::string::String,
+    /// the visitors are sent here before active_from, empty shows the coming soon page
+    #[prost(string, tag = "8")]
+    pub fallback_url: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub coming_soon_message: ::prost::alloc::string::String,
+    #[prost(int32, tag = "10")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SocialPreview {
+    /// id of the url, used while updating
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    #[prost(string, tag = "3")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub image_url: ::prost::alloc::string::String,
+    /// only filled in the response of getSocialPreview, empty outside the activation window
+    #[prost(string, tag = "6")]
+    pub original_url: ::prost::alloc::string::String,
+    #[prost(int32, tag = "7")]
+    pub workspace_id: i32,
+    /// only in the response of getSocialPreview, the side of the activation window as for the redirects
+    #[prost(message, optional, tag = "8")]
+    pub window: ::core::option::Option<Url>,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -137,127 +487,482 @@ pub struct Shorten {
     #[prost(int32, tag = "2")]
     pub id: i32,
 }
-/// Generated client implementations.
-pub mod url_shortner_service_client {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value,
-    )]
-    use tonic::codegen::*;
-    use tonic::codegen::http::Uri;
-    #[derive(Debug, Clone)]
-    pub struct UrlShortnerServiceClient<T> {
-        inner: tonic::client::Grpc<T>,
-    }
-    impl UrlShortnerServiceClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
-        }
-    }
-    impl<T> UrlShortnerServiceClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::Body>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
-        }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
-            Self { inner }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> UrlShortnerServiceClient<InterceptedService<T, F>>
-        where
-            F: tonic::service::Interceptor,
-            T::ResponseBody: Default,
-            T: tonic::codegen::Service<
-                http::Request<tonic::body::Body>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
-                >,
-            >,
-            <T as tonic::codegen::Service<
-                http::Request<tonic::body::Body>,
-            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
-        {
-            UrlShortnerServiceClient::new(InterceptedService::new(inner, interceptor))
-        }
-        /// Compress requests with the given encoding.
-        ///
-        /// This requires the server to support it otherwise it might respond with an
-        /// error.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.send_compressed(encoding);
-            self
-        }
-        /// Enable decompressing responses.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.accept_compressed(encoding);
-            self
-        }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_decoding_message_size(limit);
-            self
-        }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_encoding_message_size(limit);
-            self
-        }
-        pub async fn create_shorten_url(
-            &mut self,
-            request: impl tonic::IntoRequest<super::CreateShortenUrlPayload>,
-        ) -> std::result::Result<tonic::Response<super::Shorten>, tonic::Status> {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/url_shortner.UrlShortnerService/CreateShortenUrl",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(
-                    GrpcMethod::new(
-                        "url_shortner.UrlShortnerService",
-                        "CreateShortenUrl",
-                    ),
-                );
-            self.inner.unary(req, path, codec).await
-        }
-        pub async fn delete_shorten_url(
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AnalyticsRange {
+    /// id of the url
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    /// YYYY-MM-DD in UTC, empty means 29 days before `to`
+    #[prost(string, tag = "3")]
+    pub from: ::prost::alloc::string::String,
+    /// YYYY-MM-DD in UTC and inclusive, empty means today
+    #[prost(string, tag = "4")]
+    pub to: ::prost::alloc::string::String,
+    #[prost(int32, tag = "5")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DimensionCount {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(int64, tag = "2")]
+    pub count: i64,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DailyClicks {
+    /// YYYY-MM-DD in UTC
+    #[prost(string, tag = "1")]
+    pub day: ::prost::alloc::string::String,
+    #[prost(int64, tag = "2")]
+    pub clicks: i64,
+    #[prost(int64, tag = "3")]
+    pub unique_visitors: i64,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AnalyticsSummary {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub from: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub to: ::prost::alloc::string::String,
+    #[prost(int64, tag = "4")]
+    pub clicks: i64,
+    /// sum of the daily unique visitors, a visitor of two days is counted twice
+    #[prost(int64, tag = "5")]
+    pub daily_unique_visitors: i64,
+    /// only the days which had clicks
+    #[prost(message, repeated, tag = "6")]
+    pub daily: ::prost::alloc::vec::Vec<DailyClicks>,
+    /// most clicks first
+    #[prost(message, repeated, tag = "7")]
+    pub browsers: ::prost::alloc::vec::Vec<DimensionCount>,
+    #[prost(message, repeated, tag = "8")]
+    pub os: ::prost::alloc::vec::Vec<DimensionCount>,
+    #[prost(message, repeated, tag = "9")]
+    pub devices: ::prost::alloc::vec::Vec<DimensionCount>,
+    #[prost(message, repeated, tag = "10")]
+    pub referrers: ::prost::alloc::vec::Vec<DimensionCount>,
+    #[prost(message, repeated, tag = "11")]
+    pub locations: ::prost::alloc::vec::Vec<DimensionCount>,
+    /// social, search, email, qr, direct, other
+    #[prost(message, repeated, tag = "12")]
+    pub channels: ::prost::alloc::vec::Vec<DimensionCount>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AnalyticsComparisonRequest {
+    /// the url, or the folder when campaign is set
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    #[prost(bool, tag = "3")]
+    pub campaign: bool,
+    /// YYYY-MM-DD, 6 days before `to` when empty
+    #[prost(string, tag = "4")]
+    pub from: ::prost::alloc::string::String,
+    /// YYYY-MM-DD and inclusive, today when empty
+    #[prost(string, tag = "5")]
+    pub to: ::prost::alloc::string::String,
+    /// YYYY-MM-DD, empty for the same number of days right before `from`
+    #[prost(string, tag = "6")]
+    pub previous_from: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub previous_to: ::prost::alloc::string::String,
+    #[prost(int32, tag = "8")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct MetricDelta {
+    #[prost(int64, tag = "1")]
+    pub current: i64,
+    #[prost(int64, tag = "2")]
+    pub previous: i64,
+    #[prost(int64, tag = "3")]
+    pub change: i64,
+    /// not set when there was nothing in the previous range
+    #[prost(double, optional, tag = "4")]
+    pub change_percent: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DimensionShare {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(int64, tag = "2")]
+    pub current_clicks: i64,
+    #[prost(int64, tag = "3")]
+    pub previous_clicks: i64,
+    /// percent of the clicks of the range
+    #[prost(double, tag = "4")]
+    pub current_share: f64,
+    #[prost(double, tag = "5")]
+    pub previous_share: f64,
+    /// in percentage points
+    #[prost(double, tag = "6")]
+    pub share_change: f64,
+    #[prost(double, optional, tag = "7")]
+    pub clicks_change_percent: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AnalyticsComparison {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(bool, tag = "2")]
+    pub campaign: bool,
+    #[prost(string, tag = "3")]
+    pub from: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub to: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub previous_from: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub previous_to: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "7")]
+    pub clicks: ::core::option::Option<MetricDelta>,
+    /// sums of the daily unique visitors
+    #[prost(message, optional, tag = "8")]
+    pub daily_unique_visitors: ::core::option::Option<MetricDelta>,
+    /// most current clicks first
+    #[prost(message, repeated, tag = "9")]
+    pub browsers: ::prost::alloc::vec::Vec<DimensionShare>,
+    #[prost(message, repeated, tag = "10")]
+    pub os: ::prost::alloc::vec::Vec<DimensionShare>,
+    #[prost(message, repeated, tag = "11")]
+    pub devices: ::prost::alloc::vec::Vec<DimensionShare>,
+    #[prost(message, repeated, tag = "12")]
+    pub referrers: ::prost::alloc::vec::Vec<DimensionShare>,
+    #[prost(message, repeated, tag = "13")]
+    pub channels: ::prost::alloc::vec::Vec<DimensionShare>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebhookEndpoint {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    #[prost(string, tag = "3")]
+    pub url: ::prost::alloc::string::String,
+    /// the payloads are signed with it, only returned when the endpoint was created
+    #[prost(string, tag = "4")]
+    pub secret: ::prost::alloc::string::String,
+    #[deprecated]
+    #[prost(string, tag = "5")]
+    #[serde(skip)]
+    pub legacy_created_at: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "6")]
+    #[serde(default, with = "crate::timestamp")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(int32, tag = "7")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebhookEndpointsList {
+    #[prost(message, repeated, tag = "1")]
+    pub list: ::prost::alloc::vec::Vec<WebhookEndpoint>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebhookRule {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    /// 0 for all the links of the workspace
+    #[prost(int32, tag = "3")]
+    pub url_id: i32,
+    /// click_threshold, spike or no_clicks
+    #[prost(string, tag = "4")]
+    pub rule_type: ::prost::alloc::string::String,
+    /// click_threshold: the clicks the link has to pass
+    #[prost(int64, tag = "5")]
+    pub threshold: i64,
+    /// spike: how many times the trailing daily average today's clicks have to be
+    #[prost(double, tag = "6")]
+    pub spike_factor: f64,
+    /// spike: the days of the trailing average, no_clicks: the days without a click
+    #[prost(int32, tag = "7")]
+    pub days: i32,
+    #[deprecated]
+    #[prost(string, tag = "8")]
+    #[serde(skip)]
+    pub legacy_created_at: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "9")]
+    #[serde(default, with = "crate::timestamp")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(int32, tag = "10")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebhookRulesList {
+    #[prost(message, repeated, tag = "1")]
+    pub list: ::prost::alloc::vec::Vec<WebhookRule>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebhookDelivery {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    #[prost(int32, tag = "2")]
+    pub endpoint_id: i32,
+    /// 0 when the rule was deleted since
+    #[prost(int32, tag = "3")]
+    pub rule_id: i32,
+    #[prost(string, tag = "4")]
+    pub event_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub payload: ::prost::alloc::string::String,
+    /// pending, delivered or failed
+    #[prost(string, tag = "6")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(int32, tag = "7")]
+    pub attempts: i32,
+    /// 0 when there was no response
+    #[prost(int32, tag = "8")]
+    pub response_status: i32,
+    #[prost(string, tag = "9")]
+    pub last_error: ::prost::alloc::string::String,
+    #[deprecated]
+    #[prost(string, tag = "10")]
+    #[serde(skip)]
+    pub legacy_created_at: ::prost::alloc::string::String,
+    #[deprecated]
+    #[prost(string, tag = "11")]
+    #[serde(skip)]
+    pub legacy_delivered_at: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "12")]
+    #[serde(default, with = "crate::timestamp")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// missing until it was delivered
+    #[prost(message, optional, tag = "13")]
+    #[serde(default, with = "crate::timestamp")]
+    pub delivered_at: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebhookDeliveriesList {
+    /// the latest first
+    #[prost(message, repeated, tag = "1")]
+    pub list: ::prost::alloc::vec::Vec<WebhookDelivery>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BioPage {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub user_id: i32,
+    /// lowercase letters, numbers and -, unique across all the workspaces
+    #[prost(string, tag = "3")]
+    pub slug: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub title: ::prost::alloc::string::String,
+    /// empty for none
+    #[prost(string, tag = "5")]
+    pub avatar_url: ::prost::alloc::string::String,
+    /// light or dark
+    #[prost(string, tag = "6")]
+    pub theme: ::prost::alloc::string::String,
+    /// the links of the workspace in the order they are shown
+    #[prost(int32, repeated, tag = "7")]
+    pub url_ids: ::prost::alloc::vec::Vec<i32>,
+    #[prost(message, optional, tag = "8")]
+    #[serde(default, with = "crate::timestamp")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(int32, tag = "9")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BioPagesList {
+    #[prost(message, repeated, tag = "1")]
+    pub list: ::prost::alloc::vec::Vec<BioPage>,
+}
+/// what the public page shows, the disabled links and the ones whose window ended are left out
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PublicBioPage {
+    #[prost(string, tag = "1")]
+    pub slug: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub avatar_url: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub theme: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "5")]
+    pub links: ::prost::alloc::vec::Vec<BioPageLink>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BioPageLink {
+    #[prost(string, tag = "1")]
+    pub shorten_url: ::prost::alloc::string::String,
+    /// custom domain of the link, empty for ours
+    #[prost(string, tag = "2")]
+    pub domain: ::prost::alloc::string::String,
+    /// the social preview title of the link, empty when it was not set
+    #[prost(string, tag = "3")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub original_url: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InsightExportRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    /// 0 for all the links of the workspace
+    #[prost(int32, tag = "2")]
+    pub url_id: i32,
+    /// YYYY-MM-DD, 29 days before `to` when empty
+    #[prost(string, tag = "3")]
+    pub from: ::prost::alloc::string::String,
+    /// YYYY-MM-DD and inclusive, today when empty
+    #[prost(string, tag = "4")]
+    pub to: ::prost::alloc::string::String,
+    #[prost(int32, tag = "5")]
+    pub workspace_id: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExportedInsight {
+    /// "domain/code" for the links on custom domains
+    #[prost(string, tag = "1")]
+    pub shorten_url: ::prost::alloc::string::String,
+    #[deprecated]
+    #[prost(string, tag = "2")]
+    #[serde(skip)]
+    pub legacy_insight_time: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub ip_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub browser: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub os: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub device_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub referrer: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub location: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "9")]
+    #[serde(default, with = "crate::timestamp")]
+    pub insight_time: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InsightExportBatch {
+    #[prost(message, repeated, tag = "1")]
+    pub rows: ::prost::alloc::vec::Vec<ExportedInsight>,
+}
+/// Generated client implementations.
+pub mod url_shortner_service_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct UrlShortnerServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl UrlShortnerServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> UrlShortnerServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::Body>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> UrlShortnerServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            UrlShortnerServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn create_shorten_url(
             &mut self,
-            request: impl tonic::IntoRequest<super::UrlId>,
-        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::CreateShortenUrlPayload>,
+        ) -> std::result::Result<tonic::Response<super::Shorten>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -268,23 +973,49 @@ pub mod url_shortner_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/url_shortner.UrlShortnerService/DeleteShortenUrl",
+                "/url_shortner.UrlShortnerService/CreateShortenUrl",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
                         "url_shortner.UrlShortnerService",
-                        "DeleteShortenUrl",
+                        "CreateShortenUrl",
                     ),
                 );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn get_shorten_urls_list(
+        pub async fn delete_shorten_url(
             &mut self,
-            request: impl tonic::IntoRequest<super::User>,
-        ) -> std::result::Result<tonic::Response<super::UrlsList>, tonic::Status> {
-            self.inner
+            request: impl tonic::IntoRequest<super::UrlId>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/DeleteShortenUrl",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "DeleteShortenUrl",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_shorten_urls_list(
+            &mut self,
+            request: impl tonic::IntoRequest<super::User>,
+        ) -> std::result::Result<tonic::Response<super::UrlsList>, tonic::Status> {
+            self.inner
                 .ready()
                 .await
                 .map_err(|e| {
@@ -352,11 +1083,11 @@ pub mod url_shortner_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
-        /// from here we need to design the key insights sharing , how it gonna reach other side
-        pub async fn get_key_insights(
+        /// used by the link preview page, it only reads the details, view_count is not incremented
+        pub async fn get_url_preview(
             &mut self,
-            request: impl tonic::IntoRequest<super::GetInsights>,
-        ) -> std::result::Result<tonic::Response<super::KeyInsights>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::Url>,
+        ) -> std::result::Result<tonic::Response<super::Urls>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -367,151 +1098,2489 @@ pub mod url_shortner_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/url_shortner.UrlShortnerService/getKeyInsights",
+                "/url_shortner.UrlShortnerService/getUrlPreview",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
-                    GrpcMethod::new("url_shortner.UrlShortnerService", "getKeyInsights"),
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "getUrlPreview"),
                 );
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod url_shortner_service_server {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value,
-    )]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with UrlShortnerServiceServer.
-    #[async_trait]
-    pub trait UrlShortnerService: std::marker::Send + std::marker::Sync + 'static {
-        async fn create_shorten_url(
-            &self,
-            request: tonic::Request<super::CreateShortenUrlPayload>,
-        ) -> std::result::Result<tonic::Response<super::Shorten>, tonic::Status>;
-        async fn delete_shorten_url(
-            &self,
-            request: tonic::Request<super::UrlId>,
-        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
-        async fn get_shorten_urls_list(
-            &self,
-            request: tonic::Request<super::User>,
-        ) -> std::result::Result<tonic::Response<super::UrlsList>, tonic::Status>;
-        async fn increment_count(
-            &self,
-            request: tonic::Request<super::Url>,
-        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
-        async fn get_original_url(
-            &self,
-            request: tonic::Request<super::Url>,
-        ) -> std::result::Result<tonic::Response<super::Url>, tonic::Status>;
-        /// from here we need to design the key insights sharing , how it gonna reach other side
-        async fn get_key_insights(
-            &self,
-            request: tonic::Request<super::GetInsights>,
-        ) -> std::result::Result<tonic::Response<super::KeyInsights>, tonic::Status>;
-    }
-    #[derive(Debug)]
-    pub struct UrlShortnerServiceServer<T> {
-        inner: Arc<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
-    }
-    impl<T> UrlShortnerServiceServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
-        }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
-            InterceptedService::new(Self::new(inner), interceptor)
-        }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
+        /// title, description and image shown when the link was shared on social apps
+        pub async fn update_social_preview(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SocialPreview>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/updateSocialPreview",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "updateSocialPreview",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
+        pub async fn get_social_preview(
+            &mut self,
+            request: impl tonic::IntoRequest<super::Url>,
+        ) -> std::result::Result<tonic::Response<super::SocialPreview>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getSocialPreview",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "getSocialPreview",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.max_decoding_message_size = Some(limit);
-            self
+        /// branded custom domains, verified through a DNS TXT record
+        pub async fn add_custom_domain(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CustomDomain>,
+        ) -> std::result::Result<tonic::Response<super::CustomDomain>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/addCustomDomain",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "addCustomDomain"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.max_encoding_message_size = Some(limit);
-            self
+        pub async fn verify_custom_domain(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CustomDomain>,
+        ) -> std::result::Result<tonic::Response<super::CustomDomain>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/verifyCustomDomain",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "verifyCustomDomain",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
         }
-    }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for UrlShortnerServiceServer<T>
-    where
-        T: UrlShortnerService,
-        B: Body + std::marker::Send + 'static,
-        B::Error: Into<StdError> + std::marker::Send + 'static,
-    {
-        type Response = http::Response<tonic::body::Body>;
-        type Error = std::convert::Infallible;
-        type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(
+        pub async fn get_custom_domains(
             &mut self,
-            _cx: &mut Context<'_>,
-        ) -> Poll<std::result::Result<(), Self::Error>> {
-            Poll::Ready(Ok(()))
+            request: impl tonic::IntoRequest<super::User>,
+        ) -> std::result::Result<
+            tonic::Response<super::CustomDomainsList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getCustomDomains",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "getCustomDomains",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
-            match req.uri().path() {
-                "/url_shortner.UrlShortnerService/CreateShortenUrl" => {
+        pub async fn get_verified_domains(
+            &mut self,
+            request: impl tonic::IntoRequest<super::VerifiedDomainsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CustomDomainsList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getVerifiedDomains",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "getVerifiedDomains",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// reserved and abusive custom names, the rows are managed by the admins in the blocked_names table
+        pub async fn get_blocked_names(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BlockedNamesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BlockedNamesList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getBlockedNames",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "getBlockedNames"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// last result of the background destination health checker for a link of the user
+        pub async fn get_link_health(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UrlId>,
+        ) -> std::result::Result<tonic::Response<super::LinkHealth>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getLinkHealth",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "getLinkHealth"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// the window in which the link redirects, before it a coming soon page (or the fallback url) and after it an ended page
+        pub async fn update_activation_window(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ActivationWindow>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/updateActivationWindow",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "updateActivationWindow",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// tags (many per link, names unique per workspace) and folders (one per link, used as the campaigns)
+        pub async fn get_tags(
+            &mut self,
+            request: impl tonic::IntoRequest<super::User>,
+        ) -> std::result::Result<tonic::Response<super::TagsList>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getTags",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("url_shortner.UrlShortnerService", "getTags"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_tag(
+            &mut self,
+            request: impl tonic::IntoRequest<super::Tag>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/deleteTag",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("url_shortner.UrlShortnerService", "deleteTag"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_url_tags(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UrlTags>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/setUrlTags",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "setUrlTags"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn create_folder(
+            &mut self,
+            request: impl tonic::IntoRequest<super::Folder>,
+        ) -> std::result::Result<tonic::Response<super::Folder>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/createFolder",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "createFolder"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_folders(
+            &mut self,
+            request: impl tonic::IntoRequest<super::User>,
+        ) -> std::result::Result<tonic::Response<super::FoldersList>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getFolders",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "getFolders"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_folder(
+            &mut self,
+            request: impl tonic::IntoRequest<super::Folder>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/deleteFolder",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "deleteFolder"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_url_folder(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UrlFolder>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/setUrlFolder",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "setUrlFolder"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_campaign_insights(
+            &mut self,
+            request: impl tonic::IntoRequest<super::Folder>,
+        ) -> std::result::Result<
+            tonic::Response<super::CampaignInsights>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getCampaignInsights",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "getCampaignInsights",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// clicks of a link over a date range, the complete days are read from the daily rollups and only today from the raw insights
+        pub async fn get_analytics_summary(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AnalyticsRange>,
+        ) -> std::result::Result<
+            tonic::Response<super::AnalyticsSummary>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getAnalyticsSummary",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "getAnalyticsSummary",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// two date ranges of a link or of the links of a campaign side by side, like this week against the last one
+        pub async fn compare_analytics(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AnalyticsComparisonRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AnalyticsComparison>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/compareAnalytics",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "compareAnalytics",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// notifications posted to the user's webhook endpoints when the rules match the clicks of their links
+        pub async fn create_webhook_endpoint(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WebhookEndpoint>,
+        ) -> std::result::Result<
+            tonic::Response<super::WebhookEndpoint>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/createWebhookEndpoint",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "createWebhookEndpoint",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_webhook_endpoints(
+            &mut self,
+            request: impl tonic::IntoRequest<super::User>,
+        ) -> std::result::Result<
+            tonic::Response<super::WebhookEndpointsList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getWebhookEndpoints",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "getWebhookEndpoints",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_webhook_endpoint(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WebhookEndpoint>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/deleteWebhookEndpoint",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "deleteWebhookEndpoint",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn create_webhook_rule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WebhookRule>,
+        ) -> std::result::Result<tonic::Response<super::WebhookRule>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/createWebhookRule",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "createWebhookRule",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_webhook_rules(
+            &mut self,
+            request: impl tonic::IntoRequest<super::User>,
+        ) -> std::result::Result<
+            tonic::Response<super::WebhookRulesList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getWebhookRules",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "getWebhookRules"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_webhook_rule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WebhookRule>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/deleteWebhookRule",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "deleteWebhookRule",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_webhook_deliveries(
+            &mut self,
+            request: impl tonic::IntoRequest<super::User>,
+        ) -> std::result::Result<
+            tonic::Response<super::WebhookDeliveriesList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getWebhookDeliveries",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "getWebhookDeliveries",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// link-in-bio pages, a public page at /p/{slug} with an ordered list of the user's short links
+        pub async fn create_bio_page(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BioPage>,
+        ) -> std::result::Result<tonic::Response<super::BioPage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/createBioPage",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "createBioPage"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn update_bio_page(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BioPage>,
+        ) -> std::result::Result<tonic::Response<super::BioPage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/updateBioPage",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "updateBioPage"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_bio_pages(
+            &mut self,
+            request: impl tonic::IntoRequest<super::User>,
+        ) -> std::result::Result<tonic::Response<super::BioPagesList>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getBioPages",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "getBioPages"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_bio_page(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BioPage>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/deleteBioPage",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "deleteBioPage"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_public_bio_page(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BioPage>,
+        ) -> std::result::Result<tonic::Response<super::PublicBioPage>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getPublicBioPage",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "url_shortner.UrlShortnerService",
+                        "getPublicBioPage",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// from here we need to design the key insights sharing , how it gonna reach other side
+        pub async fn get_key_insights(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetInsights>,
+        ) -> std::result::Result<tonic::Response<super::KeyInsights>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/getKeyInsights",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "getKeyInsights"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// every raw insight of a link (or of all the links of the user) within a date range, oldest first, a page at a time
+        pub async fn export_insights(
+            &mut self,
+            request: impl tonic::IntoRequest<super::InsightExportRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::InsightExportBatch>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/url_shortner.UrlShortnerService/exportInsights",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("url_shortner.UrlShortnerService", "exportInsights"),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod url_shortner_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with UrlShortnerServiceServer.
+    #[async_trait]
+    pub trait UrlShortnerService: std::marker::Send + std::marker::Sync + 'static {
+        async fn create_shorten_url(
+            &self,
+            request: tonic::Request<super::CreateShortenUrlPayload>,
+        ) -> std::result::Result<tonic::Response<super::Shorten>, tonic::Status>;
+        async fn delete_shorten_url(
+            &self,
+            request: tonic::Request<super::UrlId>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        async fn get_shorten_urls_list(
+            &self,
+            request: tonic::Request<super::User>,
+        ) -> std::result::Result<tonic::Response<super::UrlsList>, tonic::Status>;
+        async fn increment_count(
+            &self,
+            request: tonic::Request<super::Url>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        async fn get_original_url(
+            &self,
+            request: tonic::Request<super::Url>,
+        ) -> std::result::Result<tonic::Response<super::Url>, tonic::Status>;
+        /// used by the link preview page, it only reads the details, view_count is not incremented
+        async fn get_url_preview(
+            &self,
+            request: tonic::Request<super::Url>,
+        ) -> std::result::Result<tonic::Response<super::Urls>, tonic::Status>;
+        /// title, description and image shown when the link was shared on social apps
+        async fn update_social_preview(
+            &self,
+            request: tonic::Request<super::SocialPreview>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        async fn get_social_preview(
+            &self,
+            request: tonic::Request<super::Url>,
+        ) -> std::result::Result<tonic::Response<super::SocialPreview>, tonic::Status>;
+        /// branded custom domains, verified through a DNS TXT record
+        async fn add_custom_domain(
+            &self,
+            request: tonic::Request<super::CustomDomain>,
+        ) -> std::result::Result<tonic::Response<super::CustomDomain>, tonic::Status>;
+        async fn verify_custom_domain(
+            &self,
+            request: tonic::Request<super::CustomDomain>,
+        ) -> std::result::Result<tonic::Response<super::CustomDomain>, tonic::Status>;
+        async fn get_custom_domains(
+            &self,
+            request: tonic::Request<super::User>,
+        ) -> std::result::Result<
+            tonic::Response<super::CustomDomainsList>,
+            tonic::Status,
+        >;
+        async fn get_verified_domains(
+            &self,
+            request: tonic::Request<super::VerifiedDomainsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CustomDomainsList>,
+            tonic::Status,
+        >;
+        /// reserved and abusive custom names, the rows are managed by the admins in the blocked_names table
+        async fn get_blocked_names(
+            &self,
+            request: tonic::Request<super::BlockedNamesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BlockedNamesList>,
+            tonic::Status,
+        >;
+        /// last result of the background destination health checker for a link of the user
+        async fn get_link_health(
+            &self,
+            request: tonic::Request<super::UrlId>,
+        ) -> std::result::Result<tonic::Response<super::LinkHealth>, tonic::Status>;
+        /// the window in which the link redirects, before it a coming soon page (or the fallback url) and after it an ended page
+        async fn update_activation_window(
+            &self,
+            request: tonic::Request<super::ActivationWindow>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        /// tags (many per link, names unique per workspace) and folders (one per link, used as the campaigns)
+        async fn get_tags(
+            &self,
+            request: tonic::Request<super::User>,
+        ) -> std::result::Result<tonic::Response<super::TagsList>, tonic::Status>;
+        async fn delete_tag(
+            &self,
+            request: tonic::Request<super::Tag>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        async fn set_url_tags(
+            &self,
+            request: tonic::Request<super::UrlTags>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        async fn create_folder(
+            &self,
+            request: tonic::Request<super::Folder>,
+        ) -> std::result::Result<tonic::Response<super::Folder>, tonic::Status>;
+        async fn get_folders(
+            &self,
+            request: tonic::Request<super::User>,
+        ) -> std::result::Result<tonic::Response<super::FoldersList>, tonic::Status>;
+        async fn delete_folder(
+            &self,
+            request: tonic::Request<super::Folder>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        async fn set_url_folder(
+            &self,
+            request: tonic::Request<super::UrlFolder>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        async fn get_campaign_insights(
+            &self,
+            request: tonic::Request<super::Folder>,
+        ) -> std::result::Result<
+            tonic::Response<super::CampaignInsights>,
+            tonic::Status,
+        >;
+        /// clicks of a link over a date range, the complete days are read from the daily rollups and only today from the raw insights
+        async fn get_analytics_summary(
+            &self,
+            request: tonic::Request<super::AnalyticsRange>,
+        ) -> std::result::Result<
+            tonic::Response<super::AnalyticsSummary>,
+            tonic::Status,
+        >;
+        /// two date ranges of a link or of the links of a campaign side by side, like this week against the last one
+        async fn compare_analytics(
+            &self,
+            request: tonic::Request<super::AnalyticsComparisonRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AnalyticsComparison>,
+            tonic::Status,
+        >;
+        /// notifications posted to the user's webhook endpoints when the rules match the clicks of their links
+        async fn create_webhook_endpoint(
+            &self,
+            request: tonic::Request<super::WebhookEndpoint>,
+        ) -> std::result::Result<tonic::Response<super::WebhookEndpoint>, tonic::Status>;
+        async fn get_webhook_endpoints(
+            &self,
+            request: tonic::Request<super::User>,
+        ) -> std::result::Result<
+            tonic::Response<super::WebhookEndpointsList>,
+            tonic::Status,
+        >;
+        async fn delete_webhook_endpoint(
+            &self,
+            request: tonic::Request<super::WebhookEndpoint>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        async fn create_webhook_rule(
+            &self,
+            request: tonic::Request<super::WebhookRule>,
+        ) -> std::result::Result<tonic::Response<super::WebhookRule>, tonic::Status>;
+        async fn get_webhook_rules(
+            &self,
+            request: tonic::Request<super::User>,
+        ) -> std::result::Result<
+            tonic::Response<super::WebhookRulesList>,
+            tonic::Status,
+        >;
+        async fn delete_webhook_rule(
+            &self,
+            request: tonic::Request<super::WebhookRule>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        async fn get_webhook_deliveries(
+            &self,
+            request: tonic::Request<super::User>,
+        ) -> std::result::Result<
+            tonic::Response<super::WebhookDeliveriesList>,
+            tonic::Status,
+        >;
+        /// link-in-bio pages, a public page at /p/{slug} with an ordered list of the user's short links
+        async fn create_bio_page(
+            &self,
+            request: tonic::Request<super::BioPage>,
+        ) -> std::result::Result<tonic::Response<super::BioPage>, tonic::Status>;
+        async fn update_bio_page(
+            &self,
+            request: tonic::Request<super::BioPage>,
+        ) -> std::result::Result<tonic::Response<super::BioPage>, tonic::Status>;
+        async fn get_bio_pages(
+            &self,
+            request: tonic::Request<super::User>,
+        ) -> std::result::Result<tonic::Response<super::BioPagesList>, tonic::Status>;
+        async fn delete_bio_page(
+            &self,
+            request: tonic::Request<super::BioPage>,
+        ) -> std::result::Result<tonic::Response<super::SuccessMessage>, tonic::Status>;
+        async fn get_public_bio_page(
+            &self,
+            request: tonic::Request<super::BioPage>,
+        ) -> std::result::Result<tonic::Response<super::PublicBioPage>, tonic::Status>;
+        /// from here we need to design the key insights sharing , how it gonna reach other side
+        async fn get_key_insights(
+            &self,
+            request: tonic::Request<super::GetInsights>,
+        ) -> std::result::Result<tonic::Response<super::KeyInsights>, tonic::Status>;
+        /// Server streaming response type for the exportInsights method.
+        type exportInsightsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::InsightExportBatch, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// every raw insight of a link (or of all the links of the user) within a date range, oldest first, a page at a time
+        async fn export_insights(
+            &self,
+            request: tonic::Request<super::InsightExportRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::exportInsightsStream>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct UrlShortnerServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> UrlShortnerServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for UrlShortnerServiceServer<T>
+    where
+        T: UrlShortnerService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/url_shortner.UrlShortnerService/CreateShortenUrl" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateShortenUrlSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::CreateShortenUrlPayload>
+                    for CreateShortenUrlSvc<T> {
+                        type Response = super::Shorten;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateShortenUrlPayload>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::create_shorten_url(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateShortenUrlSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/DeleteShortenUrl" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteShortenUrlSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::UrlId>
+                    for DeleteShortenUrlSvc<T> {
+                        type Response = super::SuccessMessage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UrlId>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::delete_shorten_url(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteShortenUrlSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getShortenUrlsList" => {
+                    #[allow(non_camel_case_types)]
+                    struct getShortenUrlsListSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::User>
+                    for getShortenUrlsListSvc<T> {
+                        type Response = super::UrlsList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::User>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_shorten_urls_list(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getShortenUrlsListSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/incrementCount" => {
+                    #[allow(non_camel_case_types)]
+                    struct incrementCountSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::Url>
+                    for incrementCountSvc<T> {
+                        type Response = super::SuccessMessage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Url>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::increment_count(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = incrementCountSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getOriginalUrl" => {
+                    #[allow(non_camel_case_types)]
+                    struct getOriginalUrlSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::Url>
+                    for getOriginalUrlSvc<T> {
+                        type Response = super::Url;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Url>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_original_url(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getOriginalUrlSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getUrlPreview" => {
+                    #[allow(non_camel_case_types)]
+                    struct getUrlPreviewSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::Url>
+                    for getUrlPreviewSvc<T> {
+                        type Response = super::Urls;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Url>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_url_preview(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getUrlPreviewSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/updateSocialPreview" => {
+                    #[allow(non_camel_case_types)]
+                    struct updateSocialPreviewSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::SocialPreview>
+                    for updateSocialPreviewSvc<T> {
+                        type Response = super::SuccessMessage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SocialPreview>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::update_social_preview(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = updateSocialPreviewSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getSocialPreview" => {
+                    #[allow(non_camel_case_types)]
+                    struct getSocialPreviewSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::Url>
+                    for getSocialPreviewSvc<T> {
+                        type Response = super::SocialPreview;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Url>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_social_preview(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getSocialPreviewSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/addCustomDomain" => {
+                    #[allow(non_camel_case_types)]
+                    struct addCustomDomainSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::CustomDomain>
+                    for addCustomDomainSvc<T> {
+                        type Response = super::CustomDomain;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CustomDomain>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::add_custom_domain(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = addCustomDomainSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/verifyCustomDomain" => {
+                    #[allow(non_camel_case_types)]
+                    struct verifyCustomDomainSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::CustomDomain>
+                    for verifyCustomDomainSvc<T> {
+                        type Response = super::CustomDomain;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CustomDomain>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::verify_custom_domain(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = verifyCustomDomainSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getCustomDomains" => {
+                    #[allow(non_camel_case_types)]
+                    struct getCustomDomainsSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::User>
+                    for getCustomDomainsSvc<T> {
+                        type Response = super::CustomDomainsList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::User>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_custom_domains(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getCustomDomainsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getVerifiedDomains" => {
+                    #[allow(non_camel_case_types)]
+                    struct getVerifiedDomainsSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::VerifiedDomainsRequest>
+                    for getVerifiedDomainsSvc<T> {
+                        type Response = super::CustomDomainsList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::VerifiedDomainsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_verified_domains(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getVerifiedDomainsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getBlockedNames" => {
+                    #[allow(non_camel_case_types)]
+                    struct getBlockedNamesSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::BlockedNamesRequest>
+                    for getBlockedNamesSvc<T> {
+                        type Response = super::BlockedNamesList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BlockedNamesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_blocked_names(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getBlockedNamesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getLinkHealth" => {
+                    #[allow(non_camel_case_types)]
+                    struct getLinkHealthSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::UrlId>
+                    for getLinkHealthSvc<T> {
+                        type Response = super::LinkHealth;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UrlId>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_link_health(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getLinkHealthSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/updateActivationWindow" => {
+                    #[allow(non_camel_case_types)]
+                    struct updateActivationWindowSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::ActivationWindow>
+                    for updateActivationWindowSvc<T> {
+                        type Response = super::SuccessMessage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ActivationWindow>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::update_activation_window(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = updateActivationWindowSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getTags" => {
+                    #[allow(non_camel_case_types)]
+                    struct getTagsSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::User>
+                    for getTagsSvc<T> {
+                        type Response = super::TagsList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::User>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_tags(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getTagsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/deleteTag" => {
+                    #[allow(non_camel_case_types)]
+                    struct deleteTagSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::Tag>
+                    for deleteTagSvc<T> {
+                        type Response = super::SuccessMessage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Tag>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::delete_tag(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = deleteTagSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/setUrlTags" => {
+                    #[allow(non_camel_case_types)]
+                    struct setUrlTagsSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::UrlTags> for setUrlTagsSvc<T> {
+                        type Response = super::SuccessMessage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UrlTags>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::set_url_tags(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = setUrlTagsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/createFolder" => {
+                    #[allow(non_camel_case_types)]
+                    struct createFolderSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::Folder> for createFolderSvc<T> {
+                        type Response = super::Folder;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Folder>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::create_folder(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = createFolderSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getFolders" => {
+                    #[allow(non_camel_case_types)]
+                    struct getFoldersSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::User>
+                    for getFoldersSvc<T> {
+                        type Response = super::FoldersList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::User>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_folders(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getFoldersSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/deleteFolder" => {
+                    #[allow(non_camel_case_types)]
+                    struct deleteFolderSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::Folder> for deleteFolderSvc<T> {
+                        type Response = super::SuccessMessage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Folder>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::delete_folder(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = deleteFolderSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/setUrlFolder" => {
+                    #[allow(non_camel_case_types)]
+                    struct setUrlFolderSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::UrlFolder>
+                    for setUrlFolderSvc<T> {
+                        type Response = super::SuccessMessage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UrlFolder>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::set_url_folder(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = setUrlFolderSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getCampaignInsights" => {
+                    #[allow(non_camel_case_types)]
+                    struct getCampaignInsightsSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::Folder>
+                    for getCampaignInsightsSvc<T> {
+                        type Response = super::CampaignInsights;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Folder>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_campaign_insights(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getCampaignInsightsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getAnalyticsSummary" => {
+                    #[allow(non_camel_case_types)]
+                    struct getAnalyticsSummarySvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::AnalyticsRange>
+                    for getAnalyticsSummarySvc<T> {
+                        type Response = super::AnalyticsSummary;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AnalyticsRange>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_analytics_summary(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getAnalyticsSummarySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/compareAnalytics" => {
+                    #[allow(non_camel_case_types)]
+                    struct compareAnalyticsSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::AnalyticsComparisonRequest>
+                    for compareAnalyticsSvc<T> {
+                        type Response = super::AnalyticsComparison;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AnalyticsComparisonRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::compare_analytics(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = compareAnalyticsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/createWebhookEndpoint" => {
+                    #[allow(non_camel_case_types)]
+                    struct createWebhookEndpointSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::WebhookEndpoint>
+                    for createWebhookEndpointSvc<T> {
+                        type Response = super::WebhookEndpoint;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WebhookEndpoint>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::create_webhook_endpoint(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = createWebhookEndpointSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getWebhookEndpoints" => {
+                    #[allow(non_camel_case_types)]
+                    struct getWebhookEndpointsSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::User>
+                    for getWebhookEndpointsSvc<T> {
+                        type Response = super::WebhookEndpointsList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::User>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_webhook_endpoints(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getWebhookEndpointsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/deleteWebhookEndpoint" => {
+                    #[allow(non_camel_case_types)]
+                    struct deleteWebhookEndpointSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::WebhookEndpoint>
+                    for deleteWebhookEndpointSvc<T> {
+                        type Response = super::SuccessMessage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WebhookEndpoint>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::delete_webhook_endpoint(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = deleteWebhookEndpointSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/createWebhookRule" => {
                     #[allow(non_camel_case_types)]
-                    struct CreateShortenUrlSvc<T: UrlShortnerService>(pub Arc<T>);
+                    struct createWebhookRuleSvc<T: UrlShortnerService>(pub Arc<T>);
                     impl<
                         T: UrlShortnerService,
-                    > tonic::server::UnaryService<super::CreateShortenUrlPayload>
-                    for CreateShortenUrlSvc<T> {
-                        type Response = super::Shorten;
+                    > tonic::server::UnaryService<super::WebhookRule>
+                    for createWebhookRuleSvc<T> {
+                        type Response = super::WebhookRule;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CreateShortenUrlPayload>,
+                            request: tonic::Request<super::WebhookRule>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as UrlShortnerService>::create_shorten_url(
+                                <T as UrlShortnerService>::create_webhook_rule(
                                         &inner,
                                         request,
                                     )
@@ -526,7 +3595,7 @@ pub mod url_shortner_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = CreateShortenUrlSvc(inner);
+                        let method = createWebhookRuleSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -542,11 +3611,60 @@ pub mod url_shortner_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/url_shortner.UrlShortnerService/DeleteShortenUrl" => {
+                "/url_shortner.UrlShortnerService/getWebhookRules" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteShortenUrlSvc<T: UrlShortnerService>(pub Arc<T>);
-                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::UrlId>
-                    for DeleteShortenUrlSvc<T> {
+                    struct getWebhookRulesSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::User>
+                    for getWebhookRulesSvc<T> {
+                        type Response = super::WebhookRulesList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::User>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_webhook_rules(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getWebhookRulesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/deleteWebhookRule" => {
+                    #[allow(non_camel_case_types)]
+                    struct deleteWebhookRuleSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::WebhookRule>
+                    for deleteWebhookRuleSvc<T> {
                         type Response = super::SuccessMessage;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -554,11 +3672,11 @@ pub mod url_shortner_service_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UrlId>,
+                            request: tonic::Request<super::WebhookRule>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as UrlShortnerService>::delete_shorten_url(
+                                <T as UrlShortnerService>::delete_webhook_rule(
                                         &inner,
                                         request,
                                     )
@@ -573,7 +3691,7 @@ pub mod url_shortner_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = DeleteShortenUrlSvc(inner);
+                        let method = deleteWebhookRuleSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -589,12 +3707,12 @@ pub mod url_shortner_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/url_shortner.UrlShortnerService/getShortenUrlsList" => {
+                "/url_shortner.UrlShortnerService/getWebhookDeliveries" => {
                     #[allow(non_camel_case_types)]
-                    struct getShortenUrlsListSvc<T: UrlShortnerService>(pub Arc<T>);
+                    struct getWebhookDeliveriesSvc<T: UrlShortnerService>(pub Arc<T>);
                     impl<T: UrlShortnerService> tonic::server::UnaryService<super::User>
-                    for getShortenUrlsListSvc<T> {
-                        type Response = super::UrlsList;
+                    for getWebhookDeliveriesSvc<T> {
+                        type Response = super::WebhookDeliveriesList;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
@@ -605,7 +3723,7 @@ pub mod url_shortner_service_server {
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as UrlShortnerService>::get_shorten_urls_list(
+                                <T as UrlShortnerService>::get_webhook_deliveries(
                                         &inner,
                                         request,
                                     )
@@ -620,7 +3738,7 @@ pub mod url_shortner_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = getShortenUrlsListSvc(inner);
+                        let method = getWebhookDeliveriesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -636,11 +3754,149 @@ pub mod url_shortner_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/url_shortner.UrlShortnerService/incrementCount" => {
+                "/url_shortner.UrlShortnerService/createBioPage" => {
                     #[allow(non_camel_case_types)]
-                    struct incrementCountSvc<T: UrlShortnerService>(pub Arc<T>);
-                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::Url>
-                    for incrementCountSvc<T> {
+                    struct createBioPageSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::BioPage>
+                    for createBioPageSvc<T> {
+                        type Response = super::BioPage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BioPage>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::create_bio_page(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = createBioPageSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/updateBioPage" => {
+                    #[allow(non_camel_case_types)]
+                    struct updateBioPageSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::BioPage>
+                    for updateBioPageSvc<T> {
+                        type Response = super::BioPage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BioPage>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::update_bio_page(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = updateBioPageSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/getBioPages" => {
+                    #[allow(non_camel_case_types)]
+                    struct getBioPagesSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::User>
+                    for getBioPagesSvc<T> {
+                        type Response = super::BioPagesList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::User>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::get_bio_pages(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = getBioPagesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/url_shortner.UrlShortnerService/deleteBioPage" => {
+                    #[allow(non_camel_case_types)]
+                    struct deleteBioPageSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::BioPage>
+                    for deleteBioPageSvc<T> {
                         type Response = super::SuccessMessage;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -648,11 +3904,11 @@ pub mod url_shortner_service_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::Url>,
+                            request: tonic::Request<super::BioPage>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as UrlShortnerService>::increment_count(&inner, request)
+                                <T as UrlShortnerService>::delete_bio_page(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -664,7 +3920,7 @@ pub mod url_shortner_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = incrementCountSvc(inner);
+                        let method = deleteBioPageSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -680,23 +3936,28 @@ pub mod url_shortner_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/url_shortner.UrlShortnerService/getOriginalUrl" => {
+                "/url_shortner.UrlShortnerService/getPublicBioPage" => {
                     #[allow(non_camel_case_types)]
-                    struct getOriginalUrlSvc<T: UrlShortnerService>(pub Arc<T>);
-                    impl<T: UrlShortnerService> tonic::server::UnaryService<super::Url>
-                    for getOriginalUrlSvc<T> {
-                        type Response = super::Url;
+                    struct getPublicBioPageSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::UnaryService<super::BioPage>
+                    for getPublicBioPageSvc<T> {
+                        type Response = super::PublicBioPage;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::Url>,
+                            request: tonic::Request<super::BioPage>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as UrlShortnerService>::get_original_url(&inner, request)
+                                <T as UrlShortnerService>::get_public_bio_page(
+                                        &inner,
+                                        request,
+                                    )
                                     .await
                             };
                             Box::pin(fut)
@@ -708,7 +3969,7 @@ pub mod url_shortner_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = getOriginalUrlSvc(inner);
+                        let method = getPublicBioPageSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -770,6 +4031,53 @@ pub mod url_shortner_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/url_shortner.UrlShortnerService/exportInsights" => {
+                    #[allow(non_camel_case_types)]
+                    struct exportInsightsSvc<T: UrlShortnerService>(pub Arc<T>);
+                    impl<
+                        T: UrlShortnerService,
+                    > tonic::server::ServerStreamingService<super::InsightExportRequest>
+                    for exportInsightsSvc<T> {
+                        type Response = super::InsightExportBatch;
+                        type ResponseStream = T::exportInsightsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::InsightExportRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UrlShortnerService>::export_insights(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = exportInsightsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
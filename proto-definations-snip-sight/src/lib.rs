@@ -1 +1,3 @@
-pub mod generated;
\ No newline at end of file
+pub mod generated;
+pub mod name_blocklist;
+pub mod timestamp;
@@ -1,4 +1,5 @@
 mod generated;
+mod timestamp;
 
 fn main() {
     println!("Hello, world!");
@@ -0,0 +1,143 @@
+use std::collections::{HashMap, HashSet};
+use regex::Regex;
+use crate::generated::url_shortner::BlockedName;
+
+// the forms a custom name is compared in: lowercase, leetspeak turned back to letters and without the
+// '-' and '_' separators. '1' can stand for both 'i' and 'l', so both forms are returned
+pub fn normalise_name(name: &str) -> Vec<String> {
+    normalise(name).iter().map(|name| name.replace(['-', '_'], "")).collect()
+}
+
+// same as normalise_name but the separators are kept, so the words of the name can still be told apart
+fn normalise(name: &str) -> Vec<String> {
+    let base = name.to_lowercase().chars()
+        .map(|c| match c {
+            '0' => 'o',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            '8' => 'b',
+            '9' => 'g',
+            '!' => 'i',
+            other => other,
+        })
+        .collect::<String>();
+    let with_i = base.replace('1', "i");
+    let with_l = base.replace('1', "l");
+    if with_i == with_l {
+        vec![with_i]
+    } else {
+        vec![with_i, with_l]
+    }
+}
+
+// every run of consecutive words of the name joined together, "pay-pal-login" gives "pay", "paypal",
+// "paypallogin", "pal" and so on. the patterns are matched against these and not against any substring,
+// so "pineapple-sale" doesn't match a brand like "apple" while "apple-store" and "app-le" still do
+fn word_runs(name: &str) -> HashSet<String> {
+    let mut runs = HashSet::new();
+    for form in normalise(name) {
+        let words = form.split(['-', '_']).filter(|word| !word.is_empty()).collect::<Vec<_>>();
+        for start in 0..words.len() {
+            for end in start + 1..=words.len() {
+                runs.insert(words[start..end].concat());
+            }
+        }
+    }
+    runs
+}
+
+// the blocked_names table of the url shortener, the gateway keeps a copy to reject the names early
+#[derive(Debug, Default)]
+pub struct NameBlocklist {
+    exact: HashMap<String, String>, // normalised name -> reason
+    patterns: Vec<(Regex, String)>,
+}
+
+impl NameBlocklist {
+    pub fn new(entries: Vec<BlockedName>) -> Self {
+        let mut blocklist = NameBlocklist::default();
+        for entry in entries {
+            if entry.is_pattern {
+                match Regex::new(&format!("(?i){}", entry.name)) {
+                    Ok(pattern) => blocklist.patterns.push((pattern, entry.reason)),
+                    Err(err) => tracing::error!("skipping the invalid blocked name pattern {} : {}", entry.name, err),
+                }
+            } else {
+                for name in normalise_name(&entry.name) {
+                    blocklist.exact.insert(name, entry.reason.clone());
+                }
+            }
+        }
+        blocklist
+    }
+
+    // the reason when the custom name was blocked
+    pub fn blocked_reason(&self, name: &str) -> Option<&str> {
+        normalise_name(name).iter()
+            .find_map(|name| self.exact.get(name))
+            .or_else(|| {
+                let runs = word_runs(name);
+                self.patterns.iter()
+                    .find(|(pattern, _)| runs.iter().any(|run| pattern.is_match(run)))
+                    .map(|(_, reason)| reason)
+            })
+            .map(|reason| reason.as_str())
+    }
+
+    pub fn is_blocked(&self, name: &str) -> bool {
+        self.blocked_reason(name).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, is_pattern: bool, reason: &str) -> BlockedName {
+        BlockedName { id: 0, name: name.to_string(), is_pattern, reason: reason.to_string() }
+    }
+
+    fn blocklist() -> NameBlocklist {
+        NameBlocklist::new(vec![
+            entry("sign-in", false, "reserved"),
+            entry("authentication", false, "reserved"),
+            entry("^admin", true, "reserved"),
+            entry("^snipsight$", true, "reserved"),
+            entry("^(paypal|google|apple)$", true, "brand"),
+            entry("^(verify|secure)(your)?(account|login)$", true, "abuse"),
+            entry("(invalid", true, "broken"),
+        ])
+    }
+
+    #[test]
+    fn exact_names_match_case_and_separators_insensitively() {
+        let blocklist = blocklist();
+        assert_eq!(blocklist.blocked_reason("Sign_In"), Some("reserved"));
+        assert_eq!(blocklist.blocked_reason("signin"), Some("reserved"));
+        assert_eq!(blocklist.blocked_reason("AUTHENTICATION"), Some("reserved"));
+        assert_eq!(blocklist.blocked_reason("sign-in-sheet"), None);
+    }
+
+    #[test]
+    fn leetspeak_is_normalised_before_matching() {
+        let blocklist = blocklist();
+        assert_eq!(blocklist.blocked_reason("s1gn-1n"), Some("reserved"));
+        assert_eq!(blocklist.blocked_reason("4dm1n-panel"), Some("reserved"));
+        assert_eq!(blocklist.blocked_reason("pay-pa1-login"), Some("brand"));
+        assert_eq!(blocklist.blocked_reason("G00GLE"), Some("brand"));
+        assert_eq!(blocklist.blocked_reason("5n1p51ght-login"), Some("reserved"));
+        assert_eq!(blocklist.blocked_reason("my-launch-2025"), None);
+    }
+
+    #[test]
+    fn patterns_match_whole_words_and_not_parts_of_them() {
+        let blocklist = blocklist();
+        assert_eq!(blocklist.blocked_reason("apple-store"), Some("brand"));
+        assert_eq!(blocklist.blocked_reason("verify-your-account"), Some("abuse"));
+        assert_eq!(blocklist.blocked_reason("pineapple-sale"), None);
+        assert_eq!(blocklist.blocked_reason("googleplex-tour"), None);
+        assert_eq!(blocklist.blocked_reason("snipsighted"), None);
+    }
+}
@@ -0,0 +1,19 @@
+use prost_types::Timestamp;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+// the Timestamps are RFC 3339 strings in the json, a missing one is null
+pub fn serialize<S: Serializer>(timestamp: &Option<Timestamp>, serializer: S) -> Result<S::Ok, S::Error> {
+    match timestamp {
+        Some(timestamp) => serializer.serialize_some(&timestamp.to_string()),
+        None => serializer.serialize_none()
+    }
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Timestamp>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .filter(|timestamp| !timestamp.is_empty())
+        .map(|timestamp| timestamp.parse().map_err(D::Error::custom))
+        .transpose()
+}
+
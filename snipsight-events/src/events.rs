@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::publisher::OutgoingMessage;
+
+// bumped when a change to the events is not backward compatible, the consumers check it before reading the fields.
+// the messages published before the versioning have no version, they read as 0
+pub const SCHEMA_VERSION: u32 = 1;
+
+// every message on the queue, the event's fields sit next to these ones in the same json object
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Envelope {
+    #[serde(default)]
+    pub event_id: String,
+    #[serde(default)]
+    pub schema_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occurred_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub event: SnipSightEvent,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "message_type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SnipSightEvent {
+    CreateInsight(CreateInsight),
+    DeleteInsight(DeleteInsight),
+    BrokenLink(BrokenLink),
+    // a type added by a newer service, the consumers acknowledge and skip it
+    #[serde(other)]
+    Unknown,
+}
+
+// a visit of the short url, published by the gateway on every redirection
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CreateInsight {
+    pub shorten_url: String, // "domain/code" for the links on custom domains
+    pub ip_address: String,
+    #[serde(rename = "refferal_source")] // the name the first messages went out with
+    pub referral_source: String, // the raw referer header, "Direct" without one
+    // the referrer's registrable domain or the utm_source of the short url, and its channel (social, search, email,
+    // qr, direct or other). empty in the messages from before they were added
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub channel: String,
+    pub device_type: String,
+    pub browser: String,
+    pub os: String,
+}
+
+// all the insights of the short url have to go, published by the url shortner when the link was deleted
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeleteInsight {
+    pub shorten_url: String,
+}
+
+// the destination failed the health check a few times in a row
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BrokenLink {
+    pub url_id: i32,
+    pub user_id: i32,
+    pub shorten_url: String,
+    pub original_url: String,
+    pub status: i32, // 0 when there was no response at all
+    pub error: String,
+    pub failure_streak: i32,
+}
+
+impl SnipSightEvent {
+    pub fn message_type(&self) -> &'static str {
+        match self {
+            SnipSightEvent::CreateInsight(_) => "CREATE_INSIGHT",
+            SnipSightEvent::DeleteInsight(_) => "DELETE_INSIGHT",
+            SnipSightEvent::BrokenLink(_) => "BROKEN_LINK",
+            SnipSightEvent::Unknown => "UNKNOWN",
+        }
+    }
+
+    // the FIFO lane, the events of one type keep their order
+    pub fn group_id(&self) -> &'static str {
+        match self {
+            SnipSightEvent::CreateInsight(_) => "insight-event",
+            SnipSightEvent::DeleteInsight(_) => "delete-event",
+            SnipSightEvent::BrokenLink(_) => "broken-link-event",
+            SnipSightEvent::Unknown => "unknown-event",
+        }
+    }
+}
+
+impl Envelope {
+    pub fn new(event: SnipSightEvent) -> Self {
+        Self { event_id: uuid::Uuid::new_v4().to_string(), schema_version: SCHEMA_VERSION, occurred_at: Some(Utc::now()), event }
+    }
+
+    pub fn parse(body: &str) -> Result<Self, String> {
+        let envelope: Envelope = serde_json::from_str(body).map_err(|err| format!("invalid event: {}", err))?;
+        if envelope.schema_version > SCHEMA_VERSION {
+            return Err(format!("event {} has the schema version {}, only upto {} is known", envelope.event_id, envelope.schema_version, SCHEMA_VERSION))
+        }
+        Ok(envelope)
+    }
+
+    pub fn to_json(&self) -> String {
+        // the events are plain structs, they always serialize
+        serde_json::to_string(self).unwrap()
+    }
+
+    // the event id is the deduplication id, so publishing the same event twice sends it once
+    pub fn to_message(&self) -> OutgoingMessage {
+        OutgoingMessage::with_deduplication_id(self.event.group_id(), self.event_id.clone(), self.to_json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn fixed(event: SnipSightEvent) -> Envelope {
+        Envelope {
+            event_id: "0b6f8a52-3c1e-4d47-9a7e-2f4d5c6b7a81".to_string(),
+            schema_version: SCHEMA_VERSION,
+            occurred_at: Some("2026-03-01T10:00:00.123Z".parse().unwrap()),
+            event,
+        }
+    }
+
+    fn create_insight() -> CreateInsight {
+        CreateInsight {
+            shorten_url: "go.example.com/launch".to_string(),
+            ip_address: "203.0.113.7".to_string(),
+            referral_source: "https://news.ycombinator.com/".to_string(),
+            source: "ycombinator.com".to_string(),
+            channel: "social".to_string(),
+            device_type: "Other".to_string(),
+            browser: "Firefox".to_string(),
+            os: "Linux".to_string(),
+        }
+    }
+
+    // the fixtures are what is on the queue, a change that breaks them breaks the consumers
+    fn assert_golden(envelope: &Envelope, fixture: &str) {
+        assert_eq!(&Envelope::parse(fixture).unwrap(), envelope);
+        assert_eq!(serde_json::from_str::<Value>(&envelope.to_json()).unwrap(), serde_json::from_str::<Value>(fixture).unwrap());
+    }
+
+    #[test]
+    fn current_events_match_the_golden_json() {
+        assert_golden(&fixed(SnipSightEvent::CreateInsight(create_insight())), include_str!("../fixtures/v1_create_insight.json"));
+        assert_golden(&fixed(SnipSightEvent::DeleteInsight(DeleteInsight { shorten_url: "launch".to_string() })), include_str!("../fixtures/v1_delete_insight.json"));
+        assert_golden(&fixed(SnipSightEvent::BrokenLink(BrokenLink {
+            url_id: 42,
+            user_id: 7,
+            shorten_url: "launch".to_string(),
+            original_url: "https://example.com/launch".to_string(),
+            status: 404,
+            error: String::new(),
+            failure_streak: 3,
+        })), include_str!("../fixtures/v1_broken_link.json"));
+    }
+
+    #[test]
+    fn messages_from_before_the_versioning_still_parse() {
+        let envelope = Envelope::parse(include_str!("../fixtures/v0_create_insight.json")).unwrap();
+        assert_eq!((envelope.schema_version, envelope.event_id.as_str(), envelope.occurred_at), (0, "", None));
+        let unclassified = CreateInsight { source: String::new(), channel: String::new(), ..create_insight() };
+        assert_eq!(envelope.event, SnipSightEvent::CreateInsight(unclassified));
+
+        let envelope = Envelope::parse(include_str!("../fixtures/v0_delete_insight.json")).unwrap();
+        assert_eq!(envelope.event, SnipSightEvent::DeleteInsight(DeleteInsight { shorten_url: "launch".to_string() }));
+    }
+
+    #[test]
+    fn unknown_types_are_kept_and_newer_versions_rejected() {
+        let envelope = Envelope::parse(r#"{"message_type":"LINK_CLICKED_TWICE","shorten_url":"launch"}"#).unwrap();
+        assert_eq!(envelope.event, SnipSightEvent::Unknown);
+        assert!(Envelope::parse(r#"{"schema_version":2,"message_type":"DELETE_INSIGHT","shorten_url":"launch"}"#).is_err());
+        assert!(Envelope::parse(r#"{"message_type":"DELETE_INSIGHT"}"#).is_err());
+        assert!(Envelope::parse("not json").is_err());
+    }
+
+    #[test]
+    fn messages_carry_the_event_id_and_lane() {
+        let envelope = Envelope::new(SnipSightEvent::DeleteInsight(DeleteInsight { shorten_url: "launch".to_string() }));
+        let message = envelope.to_message();
+        assert_eq!((message.group_id.as_str(), &message.deduplication_id), ("delete-event", &envelope.event_id));
+        assert_eq!(Envelope::parse(&message.body).unwrap(), envelope);
+    }
+}
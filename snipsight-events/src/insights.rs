@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::{AttributeValue, DeleteRequest, WriteRequest};
+
+// BatchWriteItem takes at most 25 requests
+const BATCH_SIZE: usize = 25;
+const MAX_BATCH_ATTEMPTS: u32 = 5;
+
+// insights of the links on custom domains are stored under "<domain>/<shorten_url>", so the same
+// code on two domains doesn't mix up the insights
+pub fn insight_key(shorten_url: &str, domain: &str) -> String {
+    if domain.is_empty() {
+        shorten_url.to_string()
+    } else {
+        format!("{}/{}", domain, shorten_url)
+    }
+}
+
+async fn write_batch(client: &Client, table: &str, mut requests: Vec<WriteRequest>) -> Result<(), String> {
+    for attempt in 0..MAX_BATCH_ATTEMPTS {
+        let output = client.batch_write_item()
+            .request_items(table, requests)
+            .send().await
+            .map_err(|e| format!("unable to batch delete the insights: {:?}", e))?;
+        requests = output.unprocessed_items()
+            .and_then(|items| items.get(table))
+            .cloned()
+            .unwrap_or_default();
+        if requests.is_empty() {
+            return Ok(())
+        }
+        // throttled, backing off before sending the leftovers again
+        tokio::time::sleep(Duration::from_millis(100 * 2u64.pow(attempt))).await;
+    }
+    Err(format!("{} insights were left unprocessed", requests.len()))
+}
+
+// deletes the insights of the keys (shorten_url and insight_time) in batches, the url shortener expires
+// the insights past the retention with it and the consumer deletes the insights of the deleted links
+pub async fn delete_insights(client: &Client, table: &str, keys: &[HashMap<String, AttributeValue>]) -> Result<(), String> {
+    for chunk in keys.chunks(BATCH_SIZE) {
+        let requests = chunk.iter()
+            .map(|key| DeleteRequest::builder().set_key(Some(key.clone())).build()
+                .map(|request| WriteRequest::builder().delete_request(request).build()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        write_batch(client, table, requests).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insights_of_custom_domains_are_kept_apart() {
+        assert_eq!(insight_key("launch", "go.example.com"), "go.example.com/launch");
+        assert_eq!(insight_key("launch", ""), "launch");
+    }
+}
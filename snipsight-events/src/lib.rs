@@ -0,0 +1,30 @@
+pub mod events;
+pub mod insights;
+pub mod publisher;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use aws_config::SdkConfig;
+use crate::publisher::{EventPublisher, InMemoryPublisher, NdjsonPublisher, SqsPublisher};
+
+pub const DEFAULT_QUEUE_URL: &str = "https://sqs.ap-south-1.amazonaws.com/637423550786/snipsightmessages.fifo";
+
+// the backend comes from EVENT_BUS_BACKEND, "sqs" (the default), "file" to append to EVENT_BUS_FILE or "memory"
+pub fn publisher_from_env(config: &SdkConfig) -> Arc<dyn EventPublisher> {
+    match std::env::var("EVENT_BUS_BACKEND").unwrap_or("sqs".to_string()).as_str() {
+        "file" => {
+            let path = PathBuf::from(std::env::var("EVENT_BUS_FILE").unwrap_or("./events.ndjson".to_string()));
+            tracing::info!("publishing the events to the file {:?}", path) ;
+            Arc::new(NdjsonPublisher::new(path))
+        },
+        "memory" => {
+            tracing::info!("publishing the events in memory, they are not sent anywhere") ;
+            Arc::new(InMemoryPublisher::default())
+        },
+        _ => {
+            let queue_url = std::env::var("SQS_QUEUE_URL").unwrap_or(DEFAULT_QUEUE_URL.to_string());
+            tracing::info!("publishing the events to {}", queue_url) ;
+            Arc::new(SqsPublisher::new(aws_sdk_sqs::Client::new(config), queue_url))
+        }
+    }
+}
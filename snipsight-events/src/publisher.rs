@@ -0,0 +1,226 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use async_trait::async_trait;
+use aws_sdk_sqs::Client;
+use aws_sdk_sqs::types::SendMessageBatchRequestEntry;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+// SQS takes at most 10 messages in a SendMessageBatch
+pub const MAX_BATCH_SIZE: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutgoingMessage {
+    pub group_id: String, // the FIFO lane the message goes down
+    pub deduplication_id: String,
+    pub body: String,
+}
+
+impl OutgoingMessage {
+    pub fn new(group_id: &str, body: String) -> Self {
+        Self { group_id: group_id.to_string(), deduplication_id: uuid::Uuid::new_v4().to_string(), body }
+    }
+
+    // used when a retry of the same message has to be dropped by the queue, like the outbox rows
+    pub fn with_deduplication_id(group_id: &str, deduplication_id: String, body: String) -> Self {
+        Self { group_id: group_id.to_string(), deduplication_id, body }
+    }
+}
+
+#[async_trait]
+pub trait EventPublisher: Send + Sync + std::fmt::Debug {
+    // sends the messages together, the result of every message comes back in the same order
+    async fn publish_batch(&self, messages: &[OutgoingMessage]) -> Vec<Result<(), String>>;
+}
+
+#[derive(Debug)]
+pub struct SqsPublisher {
+    client: Client,
+    queue_url: String,
+}
+
+impl SqsPublisher {
+    pub fn new(client: Client, queue_url: String) -> Self {
+        Self { client, queue_url }
+    }
+
+    async fn send_chunk(&self, messages: &[OutgoingMessage]) -> Vec<Result<(), String>> {
+        let entries = messages.iter().enumerate().map(|(index, message)| SendMessageBatchRequestEntry::builder()
+            .id(index.to_string()).message_body(&message.body)
+            .message_group_id(&message.group_id).message_deduplication_id(&message.deduplication_id)
+            .build().map_err(|err| err.to_string()))
+            .collect::<Result<Vec<_>, _>>();
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(err) => return messages.iter().map(|_| Err(err.clone())).collect(),
+        };
+        match self.client.send_message_batch().queue_url(&self.queue_url).set_entries(Some(entries)).send().await {
+            Ok(output) => {
+                let mut results: Vec<Result<(), String>> = messages.iter().map(|_| Ok(())).collect();
+                for failed in output.failed() {
+                    if let Some(result) = failed.id().parse::<usize>().ok().and_then(|index| results.get_mut(index)) {
+                        *result = Err(format!("{} : {}", failed.code(), failed.message().unwrap_or_default()));
+                    }
+                }
+                results
+            },
+            Err(err) => {
+                let err = format!("unable to send the batch to SQS: {:?}", err);
+                messages.iter().map(|_| Err(err.clone())).collect()
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for SqsPublisher {
+    async fn publish_batch(&self, messages: &[OutgoingMessage]) -> Vec<Result<(), String>> {
+        let mut results = Vec::with_capacity(messages.len());
+        for chunk in messages.chunks(MAX_BATCH_SIZE) {
+            results.extend(self.send_chunk(chunk).await);
+        }
+        results
+    }
+}
+
+// keeps the messages, used in the tests and while running without a queue
+#[derive(Debug, Default)]
+pub struct InMemoryPublisher {
+    messages: Mutex<Vec<OutgoingMessage>>,
+}
+
+impl InMemoryPublisher {
+    pub fn messages(&self) -> Vec<OutgoingMessage> {
+        self.messages.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl EventPublisher for InMemoryPublisher {
+    async fn publish_batch(&self, messages: &[OutgoingMessage]) -> Vec<Result<(), String>> {
+        self.messages.lock().unwrap().extend_from_slice(messages);
+        messages.iter().map(|_| Ok(())).collect()
+    }
+}
+
+// appends every message as a json line to the file
+#[derive(Debug)]
+pub struct NdjsonPublisher {
+    path: PathBuf,
+    lock: tokio::sync::Mutex<()>, // the lines of two batches shouldn't interleave
+}
+
+impl NdjsonPublisher {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, lock: tokio::sync::Mutex::new(()) }
+    }
+
+    async fn append(&self, lines: &str) -> Result<(), String> {
+        let _guard = self.lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&self.path).await
+            .map_err(|err| format!("unable to open {:?}: {}", self.path, err))?;
+        file.write_all(lines.as_bytes()).await.map_err(|err| format!("unable to write to {:?}: {}", self.path, err))?;
+        file.flush().await.map_err(|err| format!("unable to write to {:?}: {}", self.path, err))
+    }
+}
+
+#[async_trait]
+impl EventPublisher for NdjsonPublisher {
+    async fn publish_batch(&self, messages: &[OutgoingMessage]) -> Vec<Result<(), String>> {
+        let mut lines = String::new();
+        for message in messages {
+            // a struct of strings always serializes
+            lines.push_str(&serde_json::to_string(message).unwrap());
+            lines.push('\n');
+        }
+        let result = self.append(&lines).await;
+        messages.iter().map(|_| result.clone()).collect()
+    }
+}
+
+// publishing from the request path, the messages are put in a bounded buffer and a background task sends them
+// in batches. when the buffer is full the message is dropped, a redirect never waits for the queue
+#[derive(Debug, Clone)]
+pub struct BufferedPublisher {
+    sender: mpsc::Sender<OutgoingMessage>,
+}
+
+impl BufferedPublisher {
+    // has to be called inside the tokio runtime, the batches wait at most `linger` to fill up
+    pub fn spawn(publisher: Arc<dyn EventPublisher>, capacity: usize, linger: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel(capacity);
+        tokio::spawn(send_batches(publisher, receiver, linger));
+        Self { sender }
+    }
+
+    // returns false when the message was dropped
+    pub fn publish(&self, message: OutgoingMessage) -> bool {
+        match self.sender.try_send(message) {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::error!("the event buffer was full or closed, dropping the message : {}", err) ;
+                false
+            }
+        }
+    }
+}
+
+async fn send_batches(publisher: Arc<dyn EventPublisher>, mut receiver: mpsc::Receiver<OutgoingMessage>, linger: Duration) {
+    while let Some(first) = receiver.recv().await {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + linger;
+        while batch.len() < MAX_BATCH_SIZE {
+            match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(message)) => batch.push(message),
+                _ => break
+            }
+        }
+        for (message, result) in batch.iter().zip(publisher.publish_batch(&batch).await) {
+            if let Err(err) = result {
+                tracing::error!("unable to publish the message to {} : {}", message.group_id, err) ;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn buffered_messages_are_sent_in_batches() {
+        let publisher = Arc::new(InMemoryPublisher::default());
+        let buffered = BufferedPublisher::spawn(publisher.clone(), 100, Duration::from_millis(50));
+        for index in 0..25 {
+            assert!(buffered.publish(OutgoingMessage::new("insight-event", index.to_string())));
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let bodies = publisher.messages().into_iter().map(|message| message.body).collect::<Vec<_>>();
+        assert_eq!(bodies, (0..25).map(|index| index.to_string()).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn full_buffer_drops_instead_of_waiting() {
+        let (sender, _receiver) = mpsc::channel(1);
+        let buffered = BufferedPublisher { sender };
+        assert!(buffered.publish(OutgoingMessage::new("insight-event", "first".to_string())));
+        assert!(!buffered.publish(OutgoingMessage::new("insight-event", "second".to_string())));
+    }
+
+    #[tokio::test]
+    async fn ndjson_appends_a_line_per_message() {
+        let path = std::env::temp_dir().join(format!("snipsight-events-{}.ndjson", uuid::Uuid::new_v4()));
+        let publisher = NdjsonPublisher::new(path.clone());
+        let messages = vec![OutgoingMessage::new("a", "{}".to_string()), OutgoingMessage::new("b", "[]".to_string())];
+        assert!(publisher.publish_batch(&messages).await.iter().all(Result::is_ok));
+        assert!(publisher.publish_batch(&messages[..1]).await[0].is_ok());
+
+        let lines = std::fs::read_to_string(&path).unwrap();
+        let written = lines.lines().map(|line| serde_json::from_str::<OutgoingMessage>(line).unwrap()).collect::<Vec<_>>();
+        assert_eq!(written, vec![messages[0].clone(), messages[1].clone(), messages[0].clone()]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
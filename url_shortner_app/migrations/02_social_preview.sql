@@ -0,0 +1,5 @@
+-- metadata shown when the short link was unfurled by social apps, NULL means not set
+ALTER TABLE website_urls
+    ADD COLUMN og_title VARCHAR(200),
+    ADD COLUMN og_description VARCHAR(500),
+    ADD COLUMN og_image_url VARCHAR(1200);
@@ -0,0 +1,20 @@
+CREATE TABLE custom_domains (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL,
+    domain VARCHAR(253) NOT NULL, -- stored in lowercase without the port
+    verification_token VARCHAR(64) NOT NULL,
+    verified_at TIMESTAMP, -- NULL until the DNS TXT record was found
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_user_domain UNIQUE (user_id, domain)
+);
+
+-- many users can claim a domain, but only one of them can verify it
+CREATE UNIQUE INDEX custom_domains_verified_domain_key ON custom_domains (domain) WHERE verified_at IS NOT NULL;
+
+-- NULL domain_id means the link lives on our own domain
+ALTER TABLE website_urls ADD COLUMN domain_id INT REFERENCES custom_domains(id) ON DELETE CASCADE;
+
+-- short codes are unique per domain instead of globally
+ALTER TABLE website_urls DROP CONSTRAINT website_urls_shorten_url_key;
+CREATE UNIQUE INDEX website_urls_domain_shorten_url_key ON website_urls (COALESCE(domain_id, 0), shorten_url);
@@ -0,0 +1,36 @@
+-- reserved and abusive custom names, admins add rows over here and it applies without a redeploy
+-- names are compared after normalising (lowercase, leetspeak to letters, '-' and '_' removed),
+-- when is_pattern is true the name is a regex matched against every run of consecutive words of the normalised
+-- custom name (the words being split on '-' and '_'), anchor it with ^ and $ to match whole words only
+CREATE TABLE blocked_names (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(200) NOT NULL,
+    is_pattern BOOLEAN NOT NULL DEFAULT FALSE,
+    reason VARCHAR(100) NOT NULL DEFAULT 'reserved',
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_blocked_name UNIQUE (name, is_pattern)
+);
+
+INSERT INTO blocked_names (name, is_pattern, reason) VALUES
+    ('authentication', FALSE, 'reserved'),
+    ('url-shortner', FALSE, 'reserved'),
+    ('file-sharing', FALSE, 'reserved'),
+    ('payment-routes', FALSE, 'reserved'),
+    ('sign-in', FALSE, 'reserved'),
+    ('sign-up', FALSE, 'reserved'),
+    ('log-in', FALSE, 'reserved'),
+    ('log-out', FALSE, 'reserved'),
+    ('register', FALSE, 'reserved'),
+    ('forgot-credentials', FALSE, 'reserved'),
+    ('reset-password', FALSE, 'reserved'),
+    ('password', FALSE, 'reserved'),
+    ('account', FALSE, 'reserved'),
+    ('settings', FALSE, 'reserved'),
+    ('dashboard', FALSE, 'reserved'),
+    ('support', FALSE, 'reserved'),
+    ('billing', FALSE, 'reserved'),
+    ('^admin', TRUE, 'reserved'),
+    ('^snipsight$', TRUE, 'reserved'),
+    ('^(paypal|google|apple|microsoft|amazon|facebook|instagram|whatsapp|netflix)$', TRUE, 'brand'),
+    ('^(verify|secure|confirm|update)(your)?(account|login|signin|bank|wallet|payment)$', TRUE, 'abuse');
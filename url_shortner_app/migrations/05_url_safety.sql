@@ -0,0 +1,4 @@
+-- links whose destination was flagged by the threat lists, the redirection shows a warning instead
+ALTER TABLE website_urls
+    ADD COLUMN disabled_reason VARCHAR(200),
+    ADD COLUMN disabled_at TIMESTAMP;
@@ -0,0 +1,7 @@
+-- result of the last destination health check, written by the background health checker
+ALTER TABLE website_urls
+    ADD COLUMN health_status INT,
+    ADD COLUMN health_latency_ms INT,
+    ADD COLUMN health_failure_streak INT NOT NULL DEFAULT 0,
+    ADD COLUMN health_checked_at TIMESTAMP,
+    ADD COLUMN health_error VARCHAR(200);
@@ -0,0 +1,7 @@
+-- links can be created ahead of a launch, they only redirect in between active_from and active_until (UTC)
+ALTER TABLE website_urls
+    ADD COLUMN active_from TIMESTAMP,
+    ADD COLUMN active_until TIMESTAMP,
+    ADD COLUMN fallback_url VARCHAR(2048), -- where the visitors go before active_from, NULL shows the coming soon page
+    ADD COLUMN coming_soon_message VARCHAR(500),
+    ADD CONSTRAINT website_urls_activation_window_check CHECK (active_from IS NULL OR active_until IS NULL OR active_from < active_until);
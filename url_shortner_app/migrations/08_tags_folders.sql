@@ -0,0 +1,26 @@
+-- folders group the links of a campaign, a link can be in one folder at most
+CREATE TABLE folders (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_user_folder UNIQUE (user_id, name)
+);
+
+ALTER TABLE website_urls ADD COLUMN folder_id INT REFERENCES folders(id) ON DELETE SET NULL;
+CREATE INDEX website_urls_folder_id_idx ON website_urls (folder_id);
+
+-- tag names are stored lowercased, so they are unique per user irrespective of the case
+CREATE TABLE tags (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL,
+    name VARCHAR(50) NOT NULL,
+    CONSTRAINT unique_user_tag UNIQUE (user_id, name)
+);
+
+CREATE TABLE url_tags (
+    url_id INT NOT NULL REFERENCES website_urls(id) ON DELETE CASCADE,
+    tag_id INT NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (url_id, tag_id)
+);
+CREATE INDEX url_tags_tag_id_idx ON url_tags (tag_id);
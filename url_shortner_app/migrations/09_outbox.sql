@@ -0,0 +1,15 @@
+-- events are written here in the same transaction as the change they are about, the relay task publishes
+-- them to the queue, so an event is never lost when the queue is not reachable
+CREATE TABLE outbox (
+    id BIGSERIAL PRIMARY KEY,
+    message_type VARCHAR(50) NOT NULL,
+    message_group_id VARCHAR(100) NOT NULL, -- the FIFO lane the message goes down
+    body TEXT NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMP
+);
+
+CREATE INDEX outbox_pending_idx ON outbox (next_attempt_at) WHERE sent_at IS NULL;
@@ -0,0 +1,24 @@
+-- the plan of the user decides how long the raw insights are kept, the users without a row are on the free plan.
+-- the rows are written by the payment service
+CREATE TABLE user_plans (
+    user_id INT PRIMARY KEY,
+    plan VARCHAR(20) NOT NULL DEFAULT 'free',
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- the raw insights of a day are rolled up once the day is over, the rollups are kept as long as the link
+CREATE TABLE insight_rollups (
+    url_id INT NOT NULL REFERENCES website_urls(id) ON DELETE CASCADE,
+    day DATE NOT NULL, -- in UTC
+    clicks INT NOT NULL,
+    unique_visitors INT NOT NULL, -- distinct ip addresses of the day
+    browsers JSONB NOT NULL DEFAULT '{}', -- name to clicks, only the top ones and the rest as "Other"
+    os JSONB NOT NULL DEFAULT '{}',
+    devices JSONB NOT NULL DEFAULT '{}',
+    referrers JSONB NOT NULL DEFAULT '{}',
+    locations JSONB NOT NULL DEFAULT '{}',
+    PRIMARY KEY (url_id, day)
+);
+
+-- the last day which was rolled up, the raw insights before the retention are only expired upto it
+ALTER TABLE website_urls ADD COLUMN rolled_up_until DATE;
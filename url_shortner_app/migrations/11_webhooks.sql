@@ -0,0 +1,51 @@
+-- the endpoints the notifications of a user are posted to, every payload is signed with the endpoint's secret
+CREATE TABLE webhook_endpoints (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL,
+    url VARCHAR(2048) NOT NULL,
+    secret VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX webhook_endpoints_user_id_idx ON webhook_endpoints (user_id);
+
+-- when to notify, for one link or for all the links of the user (url_id NULL)
+--   click_threshold: the link passed `threshold` clicks, once per link
+--   spike: today's clicks are `spike_factor` times the daily average of the `days` before
+--   no_clicks: no clicks for `days` days, repeated every `days` days while it stays that way
+CREATE TABLE webhook_rules (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL,
+    url_id INT REFERENCES website_urls(id) ON DELETE CASCADE,
+    rule_type VARCHAR(20) NOT NULL,
+    threshold BIGINT,
+    spike_factor DOUBLE PRECISION,
+    days INT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX webhook_rules_user_id_idx ON webhook_rules (user_id);
+
+-- the last time a rule fired for a link, so the same condition isn't notified on every run
+CREATE TABLE webhook_rule_firings (
+    rule_id INT NOT NULL REFERENCES webhook_rules(id) ON DELETE CASCADE,
+    url_id INT NOT NULL REFERENCES website_urls(id) ON DELETE CASCADE,
+    fired_on DATE NOT NULL,
+    PRIMARY KEY (rule_id, url_id)
+);
+
+-- the delivery log, the pending rows are retried with a backoff like the outbox
+CREATE TABLE webhook_deliveries (
+    id BIGSERIAL PRIMARY KEY,
+    endpoint_id INT NOT NULL REFERENCES webhook_endpoints(id) ON DELETE CASCADE,
+    rule_id INT REFERENCES webhook_rules(id) ON DELETE SET NULL,
+    event_type VARCHAR(20) NOT NULL,
+    payload TEXT NOT NULL,
+    status VARCHAR(10) NOT NULL DEFAULT 'pending', -- pending, delivered or failed
+    attempts INT NOT NULL DEFAULT 0,
+    response_status INT,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMP
+);
+CREATE INDEX webhook_deliveries_pending_idx ON webhook_deliveries (next_attempt_at) WHERE status = 'pending';
+CREATE INDEX webhook_deliveries_endpoint_id_idx ON webhook_deliveries (endpoint_id, id);
@@ -0,0 +1,2 @@
+-- the clicks by traffic channel (social, search, email, qr, direct, other), the days rolled up before it have none
+ALTER TABLE insight_rollups ADD COLUMN channels JSONB NOT NULL DEFAULT '{}';
@@ -0,0 +1,21 @@
+-- link-in-bio pages, served publicly at /p/{slug} so the slugs are unique across all the users
+CREATE TABLE bio_pages (
+    id SERIAL PRIMARY KEY,
+    user_id INT NOT NULL,
+    slug VARCHAR(50) NOT NULL,
+    title VARCHAR(100) NOT NULL,
+    avatar_url VARCHAR(2048),
+    theme VARCHAR(20) NOT NULL DEFAULT 'light',
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_bio_page_slug UNIQUE (slug)
+);
+CREATE INDEX bio_pages_user_id_idx ON bio_pages (user_id);
+
+-- the links of a page in the order they are shown, a deleted link drops out of its pages
+CREATE TABLE bio_page_links (
+    page_id INT NOT NULL REFERENCES bio_pages(id) ON DELETE CASCADE,
+    url_id INT NOT NULL REFERENCES website_urls(id) ON DELETE CASCADE,
+    position INT NOT NULL,
+    PRIMARY KEY (page_id, url_id)
+);
+CREATE INDEX bio_page_links_url_id_idx ON bio_page_links (url_id);
@@ -0,0 +1,42 @@
+-- the links and everything around them belong to a workspace of the authentication service, user_id stays as the creator
+-- every user had a personal workspace with their user id as its id, so the existing rows move in to that one
+ALTER TABLE website_urls ADD COLUMN workspace_id INT;
+UPDATE website_urls SET workspace_id = user_id;
+ALTER TABLE website_urls ALTER COLUMN workspace_id SET NOT NULL;
+CREATE INDEX website_urls_workspace_id_idx ON website_urls (workspace_id);
+ALTER TABLE website_urls DROP CONSTRAINT unique_user_original_url;
+ALTER TABLE website_urls ADD CONSTRAINT unique_workspace_original_url UNIQUE (workspace_id, original_url);
+
+ALTER TABLE custom_domains ADD COLUMN workspace_id INT;
+UPDATE custom_domains SET workspace_id = user_id;
+ALTER TABLE custom_domains ALTER COLUMN workspace_id SET NOT NULL;
+ALTER TABLE custom_domains DROP CONSTRAINT unique_user_domain;
+ALTER TABLE custom_domains ADD CONSTRAINT unique_workspace_domain UNIQUE (workspace_id, domain);
+
+ALTER TABLE folders ADD COLUMN workspace_id INT;
+UPDATE folders SET workspace_id = user_id;
+ALTER TABLE folders ALTER COLUMN workspace_id SET NOT NULL;
+ALTER TABLE folders DROP CONSTRAINT unique_user_folder;
+ALTER TABLE folders ADD CONSTRAINT unique_workspace_folder UNIQUE (workspace_id, name);
+
+-- the tags are shared by the members, whoever added them first
+ALTER TABLE tags ADD COLUMN workspace_id INT;
+UPDATE tags SET workspace_id = user_id;
+ALTER TABLE tags ALTER COLUMN workspace_id SET NOT NULL;
+ALTER TABLE tags DROP CONSTRAINT unique_user_tag;
+ALTER TABLE tags ADD CONSTRAINT unique_workspace_tag UNIQUE (workspace_id, name);
+
+ALTER TABLE webhook_endpoints ADD COLUMN workspace_id INT;
+UPDATE webhook_endpoints SET workspace_id = user_id;
+ALTER TABLE webhook_endpoints ALTER COLUMN workspace_id SET NOT NULL;
+CREATE INDEX webhook_endpoints_workspace_id_idx ON webhook_endpoints (workspace_id);
+
+ALTER TABLE webhook_rules ADD COLUMN workspace_id INT;
+UPDATE webhook_rules SET workspace_id = user_id;
+ALTER TABLE webhook_rules ALTER COLUMN workspace_id SET NOT NULL;
+CREATE INDEX webhook_rules_workspace_id_idx ON webhook_rules (workspace_id);
+
+ALTER TABLE bio_pages ADD COLUMN workspace_id INT;
+UPDATE bio_pages SET workspace_id = user_id;
+ALTER TABLE bio_pages ALTER COLUMN workspace_id SET NOT NULL;
+CREATE INDEX bio_pages_workspace_id_idx ON bio_pages (workspace_id);
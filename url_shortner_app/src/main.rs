@@ -2,15 +2,29 @@ mod server_service;
 mod services;
 mod models;
 
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use aws_config::BehaviorVersion;
 use aws_config::meta::region::RegionProviderChain;
 use aws_sdk_ssm::Client;
 use proto_definations_snip_sight::generated::url_shortner::url_shortner_service_server::UrlShortnerServiceServer;
+use proto_definations_snip_sight::name_blocklist::NameBlocklist;
 use sqlx::{PgPool, Pool, Postgres};
 use server_service::UrlShortnerServerServices;
 use tonic::transport::Server;
 use aws_sdk_dynamodb::Client as DynamoClient;
+use services::social_preview::HttpPageFetcher;
+use services::custom_domains::DnsTxtResolver;
+use services::url_safety::{watch_threat_lists, ThreatLists};
+use services::name_blocklist::refresh_blocked_names;
+use services::link_health::{run_link_health_checker, HostRateLimiter, HttpHealthProbe};
+use services::outbox::run_outbox_relay;
+use services::insight_rollups::{run_insight_rollups, RetentionPolicy};
+use services::webhooks::{run_webhook_deliveries, run_webhook_rules, WebhookSender};
+use services::digests::{run_digests, DigestConfig, DigestSender};
+use services::workspaces::HttpMembershipLookup;
+use snipsight_events::publisher_from_env;
 
 
 
@@ -24,7 +38,42 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let pool = create_database_connections().await;
     let config = aws_config::load_defaults(BehaviorVersion::v2025_01_17()).await;
     let client = DynamoClient::new(&config);
-    let service = UrlShortnerServerServices::new(Arc::new(pool), Arc::new(client));
+    let pool = Arc::new(pool);
+
+    // the threat lists are plain files, they are re-read when they change and the links are screened again
+    let threat_lists_dir = PathBuf::from(std::env::var("THREAT_LISTS_DIR").unwrap_or("/etc/snipsight/threat-lists".to_string()));
+    let threat_lists = Arc::new(RwLock::new(Arc::new(ThreatLists::load(&threat_lists_dir))));
+    tokio::spawn(watch_threat_lists(threat_lists_dir, threat_lists.clone(), pool.clone(), Duration::from_secs(60)));
+
+    // the blocked custom names are read again every minute, the first tick loads them right away
+    let blocklist = Arc::new(RwLock::new(Arc::new(NameBlocklist::default())));
+    tokio::spawn(refresh_blocked_names(blocklist.clone(), pool.clone(), Duration::from_secs(60)));
+
+    // destinations are checked every 30 minutes, a host gets at most one request every 2 seconds
+    let probe = Arc::new(HttpHealthProbe::new(Duration::from_secs(10), 5));
+    tokio::spawn(run_link_health_checker(probe, Arc::new(HostRateLimiter::new(Duration::from_secs(2))), pool.clone(), Duration::from_secs(30 * 60)));
+
+    // the events written to the outbox are published every 5 seconds, the failed ones are retried with a backoff
+    // the outbox is the buffer here, the relay waits for the queue to confirm before marking the rows sent
+    tokio::spawn(run_outbox_relay(publisher_from_env(&config), pool.clone(), Duration::from_secs(5)));
+
+    // the complete days are rolled up and the raw insights past the plan's retention are expired, every 6 hours
+    let client = Arc::new(client);
+    tokio::spawn(run_insight_rollups(RetentionPolicy::from_env(), client.clone(), pool.clone(), Duration::from_secs(6 * 60 * 60)));
+
+    // the webhook rules are checked every 15 minutes, the notifications they queue are sent every 10 seconds
+    tokio::spawn(run_webhook_rules(client.clone(), pool.clone(), Duration::from_secs(15 * 60)));
+    tokio::spawn(run_webhook_deliveries(Arc::new(WebhookSender::new(Duration::from_secs(10))), pool.clone(), Duration::from_secs(10)));
+
+    // the due digests are looked up every hour, the SMTP host comes from SMTP_HOST so it can be a local sink
+    match DigestSender::new(DigestConfig::from_env()) {
+        Ok(sender) => { tokio::spawn(run_digests(sender, client.clone(), pool.clone(), Duration::from_secs(60 * 60))); },
+        Err(err) => tracing::error!("the digests are not sent, unable to set up the SMTP transport {}", err)
+    }
+
+    // every call is authorized by the caller's role in the workspace, the roles are cached for 30 seconds
+    let memberships = Arc::new(HttpMembershipLookup::from_env(Duration::from_secs(30)));
+    let service = UrlShortnerServerServices::new(pool, client, Arc::new(HttpPageFetcher::new()), Arc::new(DnsTxtResolver::new()), memberships, threat_lists, blocklist);
 
     println!("Listening on {}", address);
 
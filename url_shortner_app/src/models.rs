@@ -1,6 +1,17 @@
-use chrono::NaiveDateTime;
+use std::collections::BTreeMap;
+use chrono::{NaiveDate, NaiveDateTime};
+use prost_types::Timestamp;
+use sqlx::types::Json;
+use proto_definations_snip_sight::generated::url_shortner::{BioPage, BioPageLink, BlockedName, CustomDomain, Folder, LinkHealth, Tag, Urls, WebhookDelivery, WebhookEndpoint, WebhookRule};
 use serde::{Deserialize, Serialize};
 use tonic::{Code, Status};
+use crate::services::activation_windows::format_timestamp;
+
+// the columns are UTC timestamps without a zone
+pub fn timestamp(at: NaiveDateTime) -> Timestamp {
+    let at = at.and_utc();
+    Timestamp { seconds: at.timestamp(), nanos: at.timestamp_subsec_nanos() as i32 }
+}
 
 #[derive(sqlx::FromRow)]
 pub struct UrlModel {
@@ -9,32 +20,371 @@ pub struct UrlModel {
     pub shorten_url: String,
     pub view_count: i32,
     pub created_at: NaiveDateTime,
+    pub domain: Option<String>, // custom domain of the link, joined from custom_domains
+    pub disabled_reason: Option<String>, // set when the destination was flagged by the threat lists
+    pub health_status: Option<i32>,
+    pub health_latency_ms: Option<i32>,
+    pub health_failure_streak: i32,
+    pub health_checked_at: Option<NaiveDateTime>,
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+    pub fallback_url: Option<String>,
+    pub coming_soon_message: Option<String>,
+    pub tags: Vec<String>,
+    pub folder_id: Option<i32>,
+    pub folder: Option<String>,
+}
+
+// the deprecated legacy_* fields are still filled for the old clients
+#[allow(deprecated)]
+impl From<UrlModel> for Urls {
+    fn from(url: UrlModel) -> Self {
+        Urls {
+            id: url.id,
+            original_url: url.original_url,
+            shorten_url: url.shorten_url,
+            view_count: url.view_count,
+            legacy_created_at: url.created_at.to_string(),
+            created_at: Some(timestamp(url.created_at)),
+            domain: url.domain.unwrap_or_default(),
+            disabled_reason: url.disabled_reason.unwrap_or_default(),
+            health_status: url.health_status.unwrap_or_default(),
+            health_latency_ms: url.health_latency_ms.unwrap_or_default(),
+            health_failure_streak: url.health_failure_streak,
+            legacy_health_checked_at: url.health_checked_at.map(|at| at.to_string()).unwrap_or_default(),
+            health_checked_at: url.health_checked_at.map(timestamp),
+            active_from: format_timestamp(url.active_from),
+            active_until: format_timestamp(url.active_until),
+            fallback_url: url.fallback_url.unwrap_or_default(),
+            coming_soon_message: url.coming_soon_message.unwrap_or_default(),
+            tags: url.tags,
+            folder_id: url.folder_id.unwrap_or_default(),
+            folder: url.folder.unwrap_or_default(),
+            window: None
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub struct CustomDomainModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub workspace_id: i32,
+    pub domain: String,
+    pub verification_token: String,
+    pub verified_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[allow(deprecated)]
+impl From<CustomDomainModel> for CustomDomain {
+    fn from(domain: CustomDomainModel) -> Self {
+        CustomDomain {
+            id: domain.id,
+            user_id: domain.user_id,
+            workspace_id: domain.workspace_id,
+            domain: domain.domain,
+            verification_token: domain.verification_token,
+            verified: domain.verified_at.is_some(),
+            legacy_created_at: domain.created_at.to_string(),
+            created_at: Some(timestamp(domain.created_at))
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub struct TagModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub workspace_id: i32,
+    pub name: String,
+    pub link_count: i32,
+}
+
+impl From<TagModel> for Tag {
+    fn from(tag: TagModel) -> Self {
+        Tag {
+            id: tag.id,
+            user_id: tag.user_id,
+            workspace_id: tag.workspace_id,
+            name: tag.name,
+            link_count: tag.link_count,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub struct FolderModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub workspace_id: i32,
+    pub name: String,
+    pub link_count: i32,
+    pub total_clicks: i64,
+    pub created_at: NaiveDateTime,
+}
+
+#[allow(deprecated)]
+impl From<FolderModel> for Folder {
+    fn from(folder: FolderModel) -> Self {
+        Folder {
+            id: folder.id,
+            user_id: folder.user_id,
+            workspace_id: folder.workspace_id,
+            name: folder.name,
+            link_count: folder.link_count,
+            total_clicks: folder.total_clicks,
+            legacy_created_at: folder.created_at.to_string(),
+            created_at: Some(timestamp(folder.created_at)),
+        }
+    }
 }
 
 #[derive(sqlx::FromRow, Debug)]
 pub struct ShortenUrl {
     pub shorten_url: String,
+    pub domain: Option<String>,
 }
 
 #[derive(sqlx::FromRow, Debug)]
 pub struct OriginalUrl {
     pub original_url: String,
+    pub domain: Option<String>, // custom domain it was resolved on
+    pub disabled_reason: Option<String>,
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+    pub fallback_url: Option<String>,
+    pub coming_soon_message: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+pub struct BlockedNameModel {
+    pub id: i32,
+    pub name: String,
+    pub is_pattern: bool,
+    pub reason: String,
+}
+
+impl From<BlockedNameModel> for BlockedName {
+    fn from(blocked: BlockedNameModel) -> Self {
+        BlockedName {
+            id: blocked.id,
+            name: blocked.name,
+            is_pattern: blocked.is_pattern,
+            reason: blocked.reason
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct SocialPreviewModel {
+    pub original_url: String,
+    pub og_title: Option<String>,
+    pub og_description: Option<String>,
+    pub og_image_url: Option<String>,
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+    pub fallback_url: Option<String>,
+    pub coming_soon_message: Option<String>,
+}
+
+// og tags read from the destination page itself
+#[derive(Debug, Default, PartialEq)]
+pub struct OpenGraphTags {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+pub struct LinkHealthModel {
+    pub id: i32,
+    pub health_status: Option<i32>,
+    pub health_latency_ms: Option<i32>,
+    pub health_failure_streak: i32,
+    pub health_checked_at: Option<NaiveDateTime>,
+    pub health_error: Option<String>,
+}
+
+#[allow(deprecated)]
+impl From<LinkHealthModel> for LinkHealth {
+    fn from(health: LinkHealthModel) -> Self {
+        LinkHealth {
+            id: health.id,
+            status: health.health_status.unwrap_or_default(),
+            latency_ms: health.health_latency_ms.unwrap_or_default(),
+            failure_streak: health.health_failure_streak,
+            legacy_checked_at: health.health_checked_at.map(|at| at.to_string()).unwrap_or_default(),
+            checked_at: health.health_checked_at.map(timestamp),
+            error: health.health_error.unwrap_or_default(),
+        }
+    }
+}
+
+// the links picked up by the health checker
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct HealthCheckTarget {
+    pub id: i32,
+    pub user_id: i32,
+    pub original_url: String,
+    pub shorten_url: String,
+    pub health_failure_streak: i32,
 }
 
-#[derive(Serialize, Debug)]
-pub struct DeleteInsight{
+// a day of clicks in the insight_rollups table
+#[derive(sqlx::FromRow)]
+pub struct InsightRollupModel {
+    pub day: NaiveDate,
+    pub clicks: i32,
+    pub unique_visitors: i32,
+    pub browsers: Json<BTreeMap<String, i64>>,
+    pub os: Json<BTreeMap<String, i64>>,
+    pub devices: Json<BTreeMap<String, i64>>,
+    pub referrers: Json<BTreeMap<String, i64>>,
+    pub locations: Json<BTreeMap<String, i64>>,
+    pub channels: Json<BTreeMap<String, i64>>,
+}
+
+// a link along with what the rollup job needs to know about it
+#[derive(sqlx::FromRow, Debug, Default)]
+pub struct RollupTarget {
+    pub id: i32,
+    pub shorten_url: String,
+    pub domain: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub rolled_up_until: Option<NaiveDate>,
+    pub plan: String,
+}
+
+// a row of the outbox waiting to be published
+#[derive(sqlx::FromRow, Debug)]
+pub struct OutboxMessage {
+    pub id: i64,
     pub message_type: String,
-    pub shorten_url: String
+    pub message_group_id: String,
+    pub body: String,
+    pub attempts: i32,
+}
+
+#[derive(sqlx::FromRow)]
+pub struct WebhookEndpointModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub workspace_id: i32,
+    pub url: String,
+    pub created_at: NaiveDateTime,
+}
+
+// the secret is left out, it is only shown when the endpoint was created
+#[allow(deprecated)]
+impl From<WebhookEndpointModel> for WebhookEndpoint {
+    fn from(endpoint: WebhookEndpointModel) -> Self {
+        WebhookEndpoint {
+            id: endpoint.id,
+            user_id: endpoint.user_id,
+            workspace_id: endpoint.workspace_id,
+            url: endpoint.url,
+            secret: String::new(),
+            legacy_created_at: endpoint.created_at.to_string(),
+            created_at: Some(timestamp(endpoint.created_at)),
+        }
+    }
 }
 
-#[derive(Clone)]
-pub struct TopInsights{
-    pub unique_views: i32,
-    pub top_location: String,
-    pub top_browser: String,
-    pub top_os: String,
-    pub top_device: String,
-    pub top_referrer: String,
+#[derive(sqlx::FromRow)]
+pub struct WebhookRuleModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub workspace_id: i32,
+    pub url_id: Option<i32>,
+    pub rule_type: String,
+    pub threshold: Option<i64>,
+    pub spike_factor: Option<f64>,
+    pub days: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+#[allow(deprecated)]
+impl From<WebhookRuleModel> for WebhookRule {
+    fn from(rule: WebhookRuleModel) -> Self {
+        WebhookRule {
+            id: rule.id,
+            user_id: rule.user_id,
+            workspace_id: rule.workspace_id,
+            url_id: rule.url_id.unwrap_or_default(),
+            rule_type: rule.rule_type,
+            threshold: rule.threshold.unwrap_or_default(),
+            spike_factor: rule.spike_factor.unwrap_or_default(),
+            days: rule.days.unwrap_or_default(),
+            legacy_created_at: rule.created_at.to_string(),
+            created_at: Some(timestamp(rule.created_at)),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub struct WebhookDeliveryModel {
+    pub id: i64,
+    pub endpoint_id: i32,
+    pub rule_id: Option<i32>,
+    pub event_type: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+#[allow(deprecated)]
+impl From<WebhookDeliveryModel> for WebhookDelivery {
+    fn from(delivery: WebhookDeliveryModel) -> Self {
+        WebhookDelivery {
+            id: delivery.id,
+            endpoint_id: delivery.endpoint_id,
+            rule_id: delivery.rule_id.unwrap_or_default(),
+            event_type: delivery.event_type,
+            payload: delivery.payload,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            response_status: delivery.response_status.unwrap_or_default(),
+            last_error: delivery.last_error.unwrap_or_default(),
+            legacy_created_at: delivery.created_at.to_string(),
+            legacy_delivered_at: delivery.delivered_at.map(|at| at.to_string()).unwrap_or_default(),
+            created_at: Some(timestamp(delivery.created_at)),
+            delivered_at: delivery.delivered_at.map(timestamp),
+        }
+    }
+}
+
+// a delivery waiting to be sent, along with where it goes
+#[derive(sqlx::FromRow, Debug)]
+pub struct PendingWebhookDelivery {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub event_type: String,
+    pub payload: String,
+    pub attempts: i32,
+}
+
+// a rule along with one of the links it watches, picked up by the rules worker
+#[derive(sqlx::FromRow, Debug)]
+pub struct WebhookRuleTarget {
+    pub rule_id: i32,
+    pub workspace_id: i32,
+    pub rule_type: String,
+    pub threshold: Option<i64>,
+    pub spike_factor: Option<f64>,
+    pub days: Option<i32>,
+    pub url_id: i32,
+    pub shorten_url: String,
+    pub domain: Option<String>,
+    pub rolled_up_until: Option<NaiveDate>,
+    pub view_count: i32,
+    pub created_at: NaiveDateTime,
+    pub fired_on: Option<NaiveDate>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -68,3 +418,50 @@ impl From<ErrorMessage> for Status {
     }
 }
 
+#[derive(sqlx::FromRow)]
+pub struct BioPageModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub workspace_id: i32,
+    pub slug: String,
+    pub title: String,
+    pub avatar_url: Option<String>,
+    pub theme: String,
+    pub url_ids: Vec<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<BioPageModel> for BioPage {
+    fn from(page: BioPageModel) -> Self {
+        BioPage {
+            id: page.id,
+            user_id: page.user_id,
+            workspace_id: page.workspace_id,
+            slug: page.slug,
+            title: page.title,
+            avatar_url: page.avatar_url.unwrap_or_default(),
+            theme: page.theme,
+            url_ids: page.url_ids,
+            created_at: Some(timestamp(page.created_at)),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub struct BioPageLinkModel {
+    pub shorten_url: String,
+    pub domain: Option<String>,
+    pub og_title: Option<String>,
+    pub original_url: String,
+}
+
+impl From<BioPageLinkModel> for BioPageLink {
+    fn from(link: BioPageLinkModel) -> Self {
+        BioPageLink {
+            shorten_url: link.shorten_url,
+            domain: link.domain.unwrap_or_default(),
+            title: link.og_title.unwrap_or_default(),
+            original_url: link.original_url,
+        }
+    }
+}
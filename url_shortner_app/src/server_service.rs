@@ -1,23 +1,46 @@
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 use proto_definations_snip_sight::generated::url_shortner::url_shortner_service_server::{UrlShortnerService};
-use proto_definations_snip_sight::generated::url_shortner::{CreateShortenUrlPayload, GetInsights, KeyInsights, Shorten, Url};
-use proto_definations_snip_sight::generated::url_shortner::{SuccessMessage, UrlId, UrlsList, User};
+use proto_definations_snip_sight::generated::url_shortner::{ActivationWindow, BioPage, BioPagesList, PublicBioPage, AnalyticsComparison, AnalyticsComparisonRequest, AnalyticsRange, AnalyticsSummary, CampaignInsights, CreateShortenUrlPayload, BlockedNamesList, BlockedNamesRequest, CustomDomain, CustomDomainsList, GetInsights, InsightExportBatch, InsightExportRequest, KeyInsights, LinkHealth, Shorten, SocialPreview, Url, VerifiedDomainsRequest, WebhookDeliveriesList, WebhookEndpoint, WebhookEndpointsList, WebhookRule, WebhookRulesList};
+use proto_definations_snip_sight::generated::url_shortner::{Folder, FoldersList, SuccessMessage, Tag, TagsList, UrlFolder, UrlId, UrlTags, Urls, UrlsList, User};
 use sqlx::{Pool, Postgres};
-use crate::services::dynamo_db_operations::{get_insights, delete_insights};
-use crate::services::shorten_url_write::{delete_url, get_original_url_service, get_urls, increase_view_count, store_new_url, update_shorten_url_name};
+use crate::services::dynamo_db_operations::get_insights;
+use crate::services::custom_domains::{add_custom_domain, get_custom_domains, get_verified_domains, verify_custom_domain, TxtResolver};
+use crate::services::name_blocklist::{get_blocked_names, SharedBlocklist};
+use crate::services::social_preview::{fetch_and_store_social_preview, get_social_preview, update_social_preview, PageFetcher};
+use crate::services::url_safety::SharedThreatLists;
+use crate::services::link_health::get_link_health;
+use crate::services::activation_windows::update_activation_window;
+use crate::services::analytics::{compare_analytics, get_analytics_summary};
+use crate::services::insight_export::export_insights;
+use crate::services::link_groups::{create_folder, delete_folder, delete_tag, get_campaign_insights, get_folders, get_tags, set_url_folder, set_url_tags};
+use crate::services::webhooks::{create_webhook_endpoint, create_webhook_rule, delete_webhook_endpoint, delete_webhook_rule, get_webhook_deliveries, get_webhook_endpoints, get_webhook_rules};
+use crate::services::bio_pages::{create_bio_page, delete_bio_page, get_bio_pages, get_public_bio_page, update_bio_page};
+use crate::services::shorten_url_write::{delete_url, get_original_url_service, get_url_preview_service, get_urls, increase_view_count, store_new_url, update_shorten_url_name, verify_link_workspace};
+use crate::services::workspaces::{authorize, MembershipLookup, Role};
 // the message payloads are converted to structs, this is why gRPC is any language supporter
 use aws_sdk_dynamodb::Client as DynamoClient;
+use tokio_stream::wrappers::ReceiverStream;
 
 #[derive(Debug)]
 pub struct UrlShortnerServerServices {
     db: Arc<Pool<Postgres>>,
-    client: Arc<DynamoClient>
+    client: Arc<DynamoClient>,
+    fetcher: Arc<dyn PageFetcher>,
+    resolver: Arc<dyn TxtResolver>,
+    memberships: Arc<dyn MembershipLookup>,
+    threat_lists: SharedThreatLists,
+    blocklist: SharedBlocklist
 }
 
 impl UrlShortnerServerServices {
-    pub fn new(db: Arc<Pool<Postgres>>, client: Arc<DynamoClient>) -> Self {
-        Self { db, client }
+    pub fn new(db: Arc<Pool<Postgres>>, client: Arc<DynamoClient>, fetcher: Arc<dyn PageFetcher>, resolver: Arc<dyn TxtResolver>, memberships: Arc<dyn MembershipLookup>, threat_lists: SharedThreatLists, blocklist: SharedBlocklist) -> Self {
+        Self { db, client, fetcher, resolver, memberships, threat_lists, blocklist }
+    }
+
+    // the calls on the links of a workspace are made by its members, the reads need a viewer and the changes an editor
+    async fn authorize(&self, workspace_id: i32, user_id: i32, needed: Role) -> Result<(), Status> {
+        authorize(workspace_id, user_id, needed, self.memberships.as_ref()).await.map(|_| ()).map_err(Status::from)
     }
 }
 
@@ -28,9 +51,22 @@ impl UrlShortnerService for UrlShortnerServerServices {
         let payload = request.into_inner();
 
         tracing::info!("Received request: {:?}", payload);
-        match store_new_url(payload, &self.db).await {
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        let fetch_social_preview = payload.fetch_social_preview ;
+        let original_url = payload.original_url.clone() ;
+        // cloning the current lists out, the lock can't be held across the await
+        let threat_lists = self.threat_lists.read().unwrap().clone() ;
+        let blocklist = self.blocklist.read().unwrap().clone() ;
+        match store_new_url(payload, &threat_lists, &blocklist, self.memberships.as_ref(), &self.db).await {
             Ok(result) => {
                 tracing::info!("result: {:?}", result);
+                if fetch_social_preview {
+                    // the destination page is fetched in the background, creation doesn't wait for it
+                    let (id, db, fetcher) = (result.1, self.db.clone(), self.fetcher.clone()) ;
+                    tokio::spawn(async move {
+                        fetch_and_store_social_preview(id, original_url, fetcher.as_ref(), &db).await ;
+                    });
+                }
                 Ok(
                     Response::new(
                         Shorten {
@@ -52,22 +88,13 @@ impl UrlShortnerService for UrlShortnerServerServices {
         tracing::info!("Deleting shorten url was going to execute") ;
         let details = request.into_inner() ;
         tracing::info!("Received request: {:?}", details);
-        let result = delete_url(details.id, details.user_id,&self.db).await ;
+        self.authorize(details.workspace_id, details.user_id, Role::Editor).await?;
+        let result = delete_url(details.id, details.workspace_id, &self.db).await ;
 
         match result {
             Ok(shorten_url) => {
-                tracing::info!("Deleted successfully");
-                // here we are going to delete the delete Operations in the dynamo db
-
-                match delete_insights(shorten_url).await {
-                    Ok(message_id) => {
-                        tracing::info!("Deleted successfully from Dynamo Db as well here is message id {}", message_id);
-                    },
-                    Err(err) => {
-                        tracing::error!("Error while deleting insights: {:?}", err);
-                    }
-                }
-
+                // the DELETE_INSIGHT event was written to the outbox along with the deletion
+                tracing::info!("Deleted successfully, the insights of {} will be deleted by the relay", shorten_url);
                 Ok(Response::new(
                     SuccessMessage {
                         cause: "None".to_string(),
@@ -86,7 +113,8 @@ impl UrlShortnerService for UrlShortnerServerServices {
         tracing::info!("Getting shorten urls list was going to execute") ;
         let user = request.into_inner();
         tracing::info!("Received request: {:?}", user);
-        let urls = get_urls(user.user_id as i32,user.page_number, user.page_size, &self.db).await ;
+        self.authorize(user.workspace_id, user.user_id, Role::Viewer).await?;
+        let urls = get_urls(user.workspace_id,user.page_number, user.page_size, &user.tag, user.folder_id, &self.db).await ;
         match urls {
             Ok(urls) => {
                 Ok(Response::new(
@@ -105,7 +133,7 @@ impl UrlShortnerService for UrlShortnerServerServices {
         tracing::info!("Incrementing count was going to execute") ;
         let url = request.into_inner();
         tracing::info!("Received request: {:?}", url);
-        let result = increase_view_count(&url.url, &self.db).await ;
+        let result = increase_view_count(&url.url, &url.domain, &self.db).await ;
         match result {
             Ok(res) => {
                 tracing::info!("Count incremented successfully");
@@ -127,19 +155,599 @@ impl UrlShortnerService for UrlShortnerServerServices {
         tracing::info!("get_original_url was going to execute") ;
         let url = request.into_inner();
         tracing::info!("Received request: {:?}", url);
-        let result = get_original_url_service(&url.url, &self.db).await ;
+        let result = get_original_url_service(&url.url, &url.domain, &self.db).await ;
 
         match result {
-            Ok(res) => {
+            Ok(original_url) => {
                 tracing::info!("Successfully got original url");
+                Ok(Response::new(original_url))
+            },
+            Err(err) => {
+                tracing::error!("Error while getting original url: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_url_preview(&self, request: Request<Url>) -> Result<Response<Urls>, Status> {
+        tracing::info!("get_url_preview was going to execute") ;
+        let url = request.into_inner();
+        tracing::info!("Received request: {:?}", url);
+        match get_url_preview_service(&url.url, &url.domain, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the url preview");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error while getting url preview: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn update_social_preview(&self, request: Request<SocialPreview>) -> Result<Response<SuccessMessage>, Status> {
+        tracing::info!("update_social_preview was going to execute") ;
+        let preview = request.into_inner();
+        tracing::info!("Received request: {:?}", preview);
+        self.authorize(preview.workspace_id, preview.user_id, Role::Editor).await?;
+        match update_social_preview(preview, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Social preview updated successfully");
                 Ok(Response::new(
-                    Url {
-                        url: res
+                    SuccessMessage {
+                        cause: "None".to_string(),
+                        operation: res
                     }
                 ))
             },
             Err(err) => {
-                tracing::error!("Error while getting original url: {:?}", err);
+                tracing::error!("Error while updating social preview: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_social_preview(&self, request: Request<Url>) -> Result<Response<SocialPreview>, Status> {
+        tracing::info!("get_social_preview was going to execute") ;
+        let url = request.into_inner();
+        tracing::info!("Received request: {:?}", url);
+        match get_social_preview(&url.url, &url.domain, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the social preview");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error while getting social preview: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn add_custom_domain(&self, request: Request<CustomDomain>) -> Result<Response<CustomDomain>, Status> {
+        tracing::info!("add_custom_domain was going to execute") ;
+        let domain = request.into_inner();
+        tracing::info!("Received request: {:?}", domain);
+        self.authorize(domain.workspace_id, domain.user_id, Role::Editor).await?;
+        match add_custom_domain(domain.workspace_id, domain.user_id, &domain.domain, self.memberships.as_ref(), &self.db).await {
+            Ok(res) => {
+                tracing::info!("Custom domain added successfully");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error while adding custom domain: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn verify_custom_domain(&self, request: Request<CustomDomain>) -> Result<Response<CustomDomain>, Status> {
+        tracing::info!("verify_custom_domain was going to execute") ;
+        let domain = request.into_inner();
+        tracing::info!("Received request: {:?}", domain);
+        self.authorize(domain.workspace_id, domain.user_id, Role::Editor).await?;
+        match verify_custom_domain(domain.id, domain.workspace_id, self.memberships.as_ref(), &self.db, self.resolver.as_ref()).await {
+            Ok(res) => {
+                tracing::info!("Custom domain verified successfully");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error while verifying custom domain: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_custom_domains(&self, request: Request<User>) -> Result<Response<CustomDomainsList>, Status> {
+        tracing::info!("get_custom_domains was going to execute") ;
+        let user = request.into_inner();
+        self.authorize(user.workspace_id, user.user_id, Role::Viewer).await?;
+        match get_custom_domains(user.workspace_id, &self.db).await {
+            Ok(list) => Ok(Response::new(CustomDomainsList { list })),
+            Err(err) => {
+                tracing::error!("Error while getting custom domains: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_verified_domains(&self, _request: Request<VerifiedDomainsRequest>) -> Result<Response<CustomDomainsList>, Status> {
+        tracing::info!("get_verified_domains was going to execute") ;
+        match get_verified_domains(&self.db).await {
+            Ok(list) => Ok(Response::new(CustomDomainsList { list })),
+            Err(err) => {
+                tracing::error!("Error while getting verified domains: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_blocked_names(&self, _request: Request<BlockedNamesRequest>) -> Result<Response<BlockedNamesList>, Status> {
+        tracing::info!("get_blocked_names was going to execute") ;
+        match get_blocked_names(&self.db).await {
+            Ok(list) => Ok(Response::new(BlockedNamesList { list })),
+            Err(err) => {
+                tracing::error!("Error while getting blocked names: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_link_health(&self, request: Request<UrlId>) -> Result<Response<LinkHealth>, Status> {
+        tracing::info!("get_link_health was going to execute") ;
+        let url_id = request.into_inner();
+        tracing::info!("Received request: {:?}", url_id);
+        self.authorize(url_id.workspace_id, url_id.user_id, Role::Viewer).await?;
+        match get_link_health(url_id.id, url_id.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the link health");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error while getting link health: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn update_activation_window(&self, request: Request<ActivationWindow>) -> Result<Response<SuccessMessage>, Status> {
+        tracing::info!("update_activation_window was going to execute") ;
+        let window = request.into_inner();
+        tracing::info!("Received request: {:?}", window);
+        self.authorize(window.workspace_id, window.user_id, Role::Editor).await?;
+        let threat_lists = self.threat_lists.read().unwrap().clone() ;
+        match update_activation_window(window, &threat_lists, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Activation window updated successfully");
+                Ok(Response::new(
+                    SuccessMessage {
+                        cause: "None".to_string(),
+                        operation: res
+                    }
+                ))
+            },
+            Err(err) => {
+                tracing::error!("Error while updating activation window: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_tags(&self, request: Request<User>) -> Result<Response<TagsList>, Status> {
+        tracing::info!("get_tags was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Viewer).await?;
+        match get_tags(payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the tags");
+                Ok(Response::new(TagsList { list: res }))
+            },
+            Err(err) => {
+                tracing::error!("Error in get_tags: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn delete_tag(&self, request: Request<Tag>) -> Result<Response<SuccessMessage>, Status> {
+        tracing::info!("delete_tag was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        match delete_tag(payload.id, payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Tag deleted successfully");
+                Ok(Response::new(
+                    SuccessMessage {
+                        cause: "None".to_string(),
+                        operation: res
+                    }
+                ))
+            },
+            Err(err) => {
+                tracing::error!("Error in delete_tag: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn set_url_tags(&self, request: Request<UrlTags>) -> Result<Response<SuccessMessage>, Status> {
+        tracing::info!("set_url_tags was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        match set_url_tags(payload, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Url tags updated successfully");
+                Ok(Response::new(
+                    SuccessMessage {
+                        cause: "None".to_string(),
+                        operation: res
+                    }
+                ))
+            },
+            Err(err) => {
+                tracing::error!("Error in set_url_tags: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn create_folder(&self, request: Request<Folder>) -> Result<Response<Folder>, Status> {
+        tracing::info!("create_folder was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        match create_folder(payload, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Folder created successfully");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error in create_folder: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_folders(&self, request: Request<User>) -> Result<Response<FoldersList>, Status> {
+        tracing::info!("get_folders was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Viewer).await?;
+        match get_folders(payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the folders");
+                Ok(Response::new(FoldersList { list: res }))
+            },
+            Err(err) => {
+                tracing::error!("Error in get_folders: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn delete_folder(&self, request: Request<Folder>) -> Result<Response<SuccessMessage>, Status> {
+        tracing::info!("delete_folder was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        match delete_folder(payload.id, payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Folder deleted successfully");
+                Ok(Response::new(
+                    SuccessMessage {
+                        cause: "None".to_string(),
+                        operation: res
+                    }
+                ))
+            },
+            Err(err) => {
+                tracing::error!("Error in delete_folder: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn set_url_folder(&self, request: Request<UrlFolder>) -> Result<Response<SuccessMessage>, Status> {
+        tracing::info!("set_url_folder was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        match set_url_folder(payload, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Url folder updated successfully");
+                Ok(Response::new(
+                    SuccessMessage {
+                        cause: "None".to_string(),
+                        operation: res
+                    }
+                ))
+            },
+            Err(err) => {
+                tracing::error!("Error in set_url_folder: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_analytics_summary(&self, request: Request<AnalyticsRange>) -> Result<Response<AnalyticsSummary>, Status> {
+        tracing::info!("get_analytics_summary was going to execute") ;
+        let range = request.into_inner();
+        tracing::info!("Received request: {:?}", range);
+        self.authorize(range.workspace_id, range.user_id, Role::Viewer).await?;
+        match get_analytics_summary(range, &self.client, &self.db).await {
+            Ok(res) => Ok(Response::new(res)),
+            Err(err) => {
+                tracing::error!("Error while getting the analytics summary: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn compare_analytics(&self, request: Request<AnalyticsComparisonRequest>) -> Result<Response<AnalyticsComparison>, Status> {
+        tracing::info!("compare_analytics was going to execute") ;
+        let comparison = request.into_inner();
+        tracing::info!("Received request: {:?}", comparison);
+        self.authorize(comparison.workspace_id, comparison.user_id, Role::Viewer).await?;
+        match compare_analytics(comparison, &self.client, &self.db).await {
+            Ok(res) => Ok(Response::new(res)),
+            Err(err) => {
+                tracing::error!("Error while comparing the analytics: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_campaign_insights(&self, request: Request<Folder>) -> Result<Response<CampaignInsights>, Status> {
+        tracing::info!("get_campaign_insights was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Viewer).await?;
+        match get_campaign_insights(payload.id, payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the campaign insights");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error in get_campaign_insights: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    type exportInsightsStream = ReceiverStream<Result<InsightExportBatch, Status>>;
+
+    async fn export_insights(&self, request: Request<InsightExportRequest>) -> Result<Response<Self::exportInsightsStream>, Status> {
+        tracing::info!("export_insights was going to execute") ;
+        let export_request = request.into_inner();
+        tracing::info!("Received request: {:?}", export_request);
+        self.authorize(export_request.workspace_id, export_request.user_id, Role::Viewer).await?;
+        match export_insights(export_request, self.client.clone(), &self.db).await {
+            Ok(receiver) => Ok(Response::new(ReceiverStream::new(receiver))),
+            Err(err) => {
+                tracing::error!("Error while exporting the insights: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn create_webhook_endpoint(&self, request: Request<WebhookEndpoint>) -> Result<Response<WebhookEndpoint>, Status> {
+        tracing::info!("create_webhook_endpoint was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        let threat_lists = self.threat_lists.read().unwrap().clone() ;
+        match create_webhook_endpoint(payload, &threat_lists, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Webhook endpoint created successfully");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error in create_webhook_endpoint: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_webhook_endpoints(&self, request: Request<User>) -> Result<Response<WebhookEndpointsList>, Status> {
+        tracing::info!("get_webhook_endpoints was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Viewer).await?;
+        match get_webhook_endpoints(payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the webhook endpoints");
+                Ok(Response::new(WebhookEndpointsList { list: res }))
+            },
+            Err(err) => {
+                tracing::error!("Error in get_webhook_endpoints: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn delete_webhook_endpoint(&self, request: Request<WebhookEndpoint>) -> Result<Response<SuccessMessage>, Status> {
+        tracing::info!("delete_webhook_endpoint was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        match delete_webhook_endpoint(payload.id, payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Webhook endpoint deleted successfully");
+                Ok(Response::new(
+                    SuccessMessage {
+                        cause: "None".to_string(),
+                        operation: res
+                    }
+                ))
+            },
+            Err(err) => {
+                tracing::error!("Error in delete_webhook_endpoint: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn create_webhook_rule(&self, request: Request<WebhookRule>) -> Result<Response<WebhookRule>, Status> {
+        tracing::info!("create_webhook_rule was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        match create_webhook_rule(payload, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Webhook rule created successfully");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error in create_webhook_rule: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_webhook_rules(&self, request: Request<User>) -> Result<Response<WebhookRulesList>, Status> {
+        tracing::info!("get_webhook_rules was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Viewer).await?;
+        match get_webhook_rules(payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the webhook rules");
+                Ok(Response::new(WebhookRulesList { list: res }))
+            },
+            Err(err) => {
+                tracing::error!("Error in get_webhook_rules: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn delete_webhook_rule(&self, request: Request<WebhookRule>) -> Result<Response<SuccessMessage>, Status> {
+        tracing::info!("delete_webhook_rule was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        match delete_webhook_rule(payload.id, payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Webhook rule deleted successfully");
+                Ok(Response::new(
+                    SuccessMessage {
+                        cause: "None".to_string(),
+                        operation: res
+                    }
+                ))
+            },
+            Err(err) => {
+                tracing::error!("Error in delete_webhook_rule: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_webhook_deliveries(&self, request: Request<User>) -> Result<Response<WebhookDeliveriesList>, Status> {
+        tracing::info!("get_webhook_deliveries was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Viewer).await?;
+        match get_webhook_deliveries(payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the webhook deliveries");
+                Ok(Response::new(WebhookDeliveriesList { list: res }))
+            },
+            Err(err) => {
+                tracing::error!("Error in get_webhook_deliveries: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn create_bio_page(&self, request: Request<BioPage>) -> Result<Response<BioPage>, Status> {
+        tracing::info!("create_bio_page was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        let blocklist = self.blocklist.read().unwrap().clone() ;
+        match create_bio_page(payload, &blocklist, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Bio page created successfully");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error in create_bio_page: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn update_bio_page(&self, request: Request<BioPage>) -> Result<Response<BioPage>, Status> {
+        tracing::info!("update_bio_page was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        let blocklist = self.blocklist.read().unwrap().clone() ;
+        match update_bio_page(payload, &blocklist, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Bio page updated successfully");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error in update_bio_page: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_bio_pages(&self, request: Request<User>) -> Result<Response<BioPagesList>, Status> {
+        tracing::info!("get_bio_pages was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Viewer).await?;
+        match get_bio_pages(payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the bio pages");
+                Ok(Response::new(BioPagesList { list: res }))
+            },
+            Err(err) => {
+                tracing::error!("Error in get_bio_pages: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn delete_bio_page(&self, request: Request<BioPage>) -> Result<Response<SuccessMessage>, Status> {
+        tracing::info!("delete_bio_page was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        self.authorize(payload.workspace_id, payload.user_id, Role::Editor).await?;
+        match delete_bio_page(payload.id, payload.workspace_id, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Bio page deleted successfully");
+                Ok(Response::new(
+                    SuccessMessage {
+                        cause: "None".to_string(),
+                        operation: res
+                    }
+                ))
+            },
+            Err(err) => {
+                tracing::error!("Error in delete_bio_page: {:?}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_public_bio_page(&self, request: Request<BioPage>) -> Result<Response<PublicBioPage>, Status> {
+        tracing::info!("get_public_bio_page was going to execute") ;
+        let payload = request.into_inner();
+        tracing::info!("Received request: {:?}", payload);
+        match get_public_bio_page(&payload.slug, &self.db).await {
+            Ok(res) => {
+                tracing::info!("Successfully got the public bio page");
+                Ok(Response::new(res))
+            },
+            Err(err) => {
+                tracing::error!("Error in get_public_bio_page: {:?}", err);
                 Err(err.into())
             }
         }
@@ -149,6 +757,8 @@ impl UrlShortnerService for UrlShortnerServerServices {
         // we are going to get the data
         tracing::info!("get_key_insights was going to execute") ;
         let insights_request = request.into_inner() ;
+        self.authorize(insights_request.workspace_id, insights_request.user_id, Role::Viewer).await?;
+        verify_link_workspace(&insights_request.shorten_url, &insights_request.domain, insights_request.workspace_id, &self.db).await?;
         match get_insights(insights_request, &self.client).await {
             Ok(result) => {
                 tracing::info!("Got the  Insights for the shorten url {}", result.shorten_url);
@@ -156,7 +766,7 @@ impl UrlShortnerService for UrlShortnerServerServices {
             },
             Err(err) => {
                 tracing::error!("Error while getting insights: {:?}", err);
-                Err(Status::internal(err))
+                Err(err.into())
             }
         }
     }
@@ -0,0 +1,147 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use proto_definations_snip_sight::generated::url_shortner::{ActivationWindow, Url};
+use sqlx::{Pool, Postgres};
+use crate::models::ErrorMessage;
+use crate::services::url_safety::{screen_new_url, ThreatLists};
+
+// the coming soon message column is a VARCHAR(500)
+const MAX_MESSAGE_LENGTH: usize = 500;
+
+pub const UPCOMING: &str = "upcoming";
+pub const ENDED: &str = "ended";
+
+// the window as it was stored, the timestamps are kept in UTC
+#[derive(Debug, Default, PartialEq)]
+pub struct Window {
+    pub active_from: Option<NaiveDateTime>,
+    pub active_until: Option<NaiveDateTime>,
+    pub fallback_url: Option<String>,
+    pub coming_soon_message: Option<String>,
+}
+
+fn parse_timestamp(value: &str, field: &str) -> Result<Option<NaiveDateTime>, ErrorMessage> {
+    if value.trim().is_empty() {
+        return Ok(None)
+    }
+    match DateTime::parse_from_rfc3339(value.trim()) {
+        Ok(timestamp) => Ok(Some(timestamp.naive_utc())),
+        Err(_) => Err(ErrorMessage::new(format!("{} was not a valid RFC 3339 timestamp", field), 400))
+    }
+}
+
+fn optional(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() { None } else { Some(value.to_string()) }
+}
+
+// validates the window coming in the requests, the fallback url goes through the same screening as the destinations
+pub async fn parse_window(active_from: &str, active_until: &str, fallback_url: &str, coming_soon_message: &str, lists: &ThreatLists) -> Result<Window, ErrorMessage> {
+    let window = Window {
+        active_from: parse_timestamp(active_from, "active_from")?,
+        active_until: parse_timestamp(active_until, "active_until")?,
+        fallback_url: optional(fallback_url),
+        coming_soon_message: optional(coming_soon_message),
+    };
+    if let (Some(from), Some(until)) = (window.active_from, window.active_until) && from >= until {
+        return Err(ErrorMessage::new("active_until must be after active_from".to_string(), 400))
+    }
+    if window.coming_soon_message.as_ref().is_some_and(|message| message.chars().count() > MAX_MESSAGE_LENGTH) {
+        return Err(ErrorMessage::new(format!("coming soon message can't be longer than {} characters", MAX_MESSAGE_LENGTH), 400))
+    }
+    if let Some(fallback_url) = &window.fallback_url {
+        screen_new_url(fallback_url, lists).await?;
+    }
+    Ok(window)
+}
+
+// empty when the link is live, otherwise the side of the window it was on
+pub fn activation_state(active_from: Option<NaiveDateTime>, active_until: Option<NaiveDateTime>, now: NaiveDateTime) -> &'static str {
+    if active_from.is_some_and(|from| now < from) {
+        UPCOMING
+    } else if active_until.is_some_and(|until| now >= until) {
+        ENDED
+    } else {
+        ""
+    }
+}
+
+// the side of the window the visitors are on, the fallback and the message are only for the ones who came early
+pub fn visitor_window(active_from: Option<NaiveDateTime>, active_until: Option<NaiveDateTime>, fallback_url: Option<String>, coming_soon_message: Option<String>) -> Url {
+    let activation_state = activation_state(active_from, active_until, now());
+    let upcoming = activation_state == UPCOMING;
+    Url {
+        activation_state: activation_state.to_string(),
+        active_from: format_timestamp(active_from),
+        fallback_url: fallback_url.filter(|_| upcoming).unwrap_or_default(),
+        coming_soon_message: coming_soon_message.filter(|_| upcoming).unwrap_or_default(),
+        ..Default::default()
+    }
+}
+
+pub fn format_timestamp(timestamp: Option<NaiveDateTime>) -> String {
+    timestamp.map(|timestamp| timestamp.and_utc().to_rfc3339()).unwrap_or_default()
+}
+
+pub fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
+
+pub async fn update_activation_window(request: ActivationWindow, lists: &ThreatLists, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+    tracing::info!("update activation window was called with the id {}", request.id) ;
+    let window = parse_window(&request.active_from, &request.active_until, &request.fallback_url, &request.coming_soon_message, lists).await? ;
+
+    let result = sqlx::query("update website_urls SET active_from=$1, active_until=$2, fallback_url=$3, coming_soon_message=$4 where id=$5 AND workspace_id=$6")
+        .bind(window.active_from).bind(window.active_until).bind(window.fallback_url).bind(window.coming_soon_message)
+        .bind(request.id).bind(request.workspace_id).execute(db).await ;
+    match result {
+        Ok(result) if result.rows_affected() > 0 => Ok(true),
+        Ok(_) => {
+            tracing::warn!("no url with the id {} in the workspace {}", request.id, request.workspace_id) ;
+            Err(ErrorMessage::new("Shorten url doesn't exists".to_string(), 404))
+        },
+        Err(err) => {
+            tracing::error!("error while updating the activation window was {}", err) ;
+            Err(ErrorMessage::new(err.to_string(), 500))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> Option<NaiveDateTime> {
+        parse_timestamp(value, "at").unwrap()
+    }
+
+    #[test]
+    fn state_follows_the_window() {
+        let (from, until) = (at("2026-03-01T10:00:00+05:30"), at("2026-03-02T00:00:00Z"));
+        assert_eq!(activation_state(from, until, at("2026-03-01T04:29:59Z").unwrap()), UPCOMING);
+        assert_eq!(activation_state(from, until, at("2026-03-01T04:30:00Z").unwrap()), "");
+        assert_eq!(activation_state(from, until, at("2026-03-02T00:00:00Z").unwrap()), ENDED);
+        assert_eq!(activation_state(None, None, now()), "");
+    }
+
+    #[test]
+    fn only_the_early_visitors_get_the_fallback() {
+        let (fallback, message) = (Some("https://example.com/waitlist".to_string()), Some("Soon".to_string()));
+        let upcoming = visitor_window(Some(now() + chrono::Duration::days(1)), None, fallback.clone(), message.clone());
+        assert_eq!((upcoming.activation_state.as_str(), upcoming.fallback_url.as_str(), upcoming.coming_soon_message.as_str()), (UPCOMING, "https://example.com/waitlist", "Soon"));
+
+        let ended = visitor_window(None, Some(now() - chrono::Duration::days(1)), fallback, message);
+        assert_eq!((ended.activation_state.as_str(), ended.fallback_url.as_str(), ended.coming_soon_message.as_str()), (ENDED, "", ""));
+    }
+
+    #[tokio::test]
+    async fn windows_are_validated() {
+        let lists = ThreatLists::default();
+        let window = parse_window("2026-03-01T10:00:00Z", "", " ", "Launching soon", &lists).await.unwrap();
+        assert_eq!(format_timestamp(window.active_from), "2026-03-01T10:00:00+00:00");
+        assert_eq!((window.active_until, window.fallback_url), (None, None));
+
+        assert!(parse_window("tomorrow", "", "", "", &lists).await.is_err());
+        assert!(parse_window("2026-03-02T00:00:00Z", "2026-03-01T00:00:00Z", "", "", &lists).await.is_err());
+        assert!(parse_window("", "", "http://127.0.0.1/admin", "", &lists).await.is_err());
+    }
+}
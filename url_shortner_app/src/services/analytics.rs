@@ -1,8 +1,15 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::sync::Arc;
 use aws_sdk_dynamodb::Client;
-use proto_definations_snip_sight::generated::url_shortner::{Insight};
+use chrono::{DateTime, Duration, NaiveDate};
+use proto_definations_snip_sight::generated::url_shortner::{AnalyticsComparison, AnalyticsComparisonRequest, AnalyticsRange, AnalyticsSummary, DailyClicks, DimensionCount, DimensionShare, Insight, MetricDelta};
+use prost_types::Timestamp;
+use sqlx::{Pool, Postgres};
+use crate::models::{ErrorMessage, InsightRollupModel};
+use crate::services::activation_windows::now;
+use snipsight_events::insights::insight_key;
+use crate::services::dynamo_db_operations::insights_between;
 use tokio::sync::RwLock;
-use crate::models::TopInsights;
 
 #[derive(Clone)]
 pub struct Analytics{
@@ -13,7 +20,6 @@ pub struct Analytics{
     device_pie: Arc<Vec<(String, f32)>>,
     refferrers_pie: Arc<Vec<(String, f32)>>,
     os_pie: Arc<Vec<(String, f32)>>,
-    top_insights: Option<Arc<TopInsights>>,
     past_six_hours: Arc<Vec<u32>>, // each index is the hour, the size will be 6 for 6 hours each hour views
     list_of_location_points: Arc<Vec<(String, i32)>> // each location and their count
     // remaining was views over time line
@@ -31,7 +37,6 @@ impl Analytics{
             list_of_location_points: Arc::new(Vec::new()),
             os_pie: Arc::new(Vec::new()),
             past_six_hours: Arc::new(Vec::new()),
-            top_insights: None,
         }
     }
     // implementing the concurrency logic inside each method
@@ -87,4 +92,434 @@ impl Analytics{
         }
     }
 
-}
\ No newline at end of file
+}
+
+// the names of a dimension kept in a rollup, the rest are counted under OTHER
+const TOP_NAMES: usize = 10;
+pub const OTHER: &str = "Other";
+const UNKNOWN: &str = "Unknown";
+// the default range of the summary and the longest one that can be asked for
+const DEFAULT_RANGE_DAYS: i64 = 30;
+const MAX_RANGE_DAYS: i64 = 366;
+
+// clicks of a day or of a range of days, the daily rollups are stored as these. for a range the unique visitors
+// are the daily ones added up, the distinct ip addresses of the whole range are not known once the days are rolled up
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Aggregate {
+    pub clicks: i64,
+    pub unique_visitors: i64,
+    pub browsers: BTreeMap<String, i64>,
+    pub os: BTreeMap<String, i64>,
+    pub devices: BTreeMap<String, i64>,
+    pub referrers: BTreeMap<String, i64>,
+    pub locations: BTreeMap<String, i64>,
+    pub channels: BTreeMap<String, i64>,
+}
+
+fn count(counts: &mut BTreeMap<String, i64>, name: &str) {
+    let name = if name.is_empty() { UNKNOWN } else { name };
+    *counts.entry(name.to_string()).or_default() += 1;
+}
+
+fn add(counts: &mut BTreeMap<String, i64>, other: &BTreeMap<String, i64>) {
+    for (name, clicks) in other {
+        *counts.entry(name.clone()).or_default() += clicks;
+    }
+}
+
+// most clicks first, the ties by name so the order doesn't change between calls
+pub fn ranked(counts: &BTreeMap<String, i64>) -> Vec<DimensionCount> {
+    let mut ranked = counts.iter().map(|(name, count)| DimensionCount { name: name.clone(), count: *count }).collect::<Vec<_>>();
+    ranked.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    ranked
+}
+
+fn top(counts: BTreeMap<String, i64>, keep: usize) -> BTreeMap<String, i64> {
+    let mut top = BTreeMap::new();
+    for (index, DimensionCount { name, count }) in ranked(&counts).into_iter().enumerate() {
+        let name = if index < keep && name != OTHER { name } else { OTHER.to_string() };
+        *top.entry(name).or_default() += count;
+    }
+    top
+}
+
+impl Aggregate {
+    // the raw insights of one day, the unique visitors are the distinct ip addresses
+    pub fn of_insights(insights: &[Insight]) -> Self {
+        let mut aggregate = Aggregate::default();
+        let mut visitors = HashSet::new();
+        for insight in insights {
+            aggregate.clicks += 1;
+            visitors.insert(insight.ip_address.as_str());
+            count(&mut aggregate.browsers, &insight.browser);
+            count(&mut aggregate.os, &insight.os);
+            count(&mut aggregate.devices, &insight.device_type);
+            // the normalised source, the insights from before it only have the raw referrer
+            count(&mut aggregate.referrers, if insight.source.is_empty() { &insight.refferal_source } else { &insight.source });
+            count(&mut aggregate.locations, &insight.location);
+            count(&mut aggregate.channels, &insight.channel);
+        }
+        aggregate.unique_visitors = visitors.len() as i64;
+        aggregate
+    }
+
+    // the unique visitors of two days are added up, a visitor of both days is counted twice
+    pub fn merge(&mut self, other: &Aggregate) {
+        self.clicks += other.clicks;
+        self.unique_visitors += other.unique_visitors;
+        add(&mut self.browsers, &other.browsers);
+        add(&mut self.os, &other.os);
+        add(&mut self.devices, &other.devices);
+        add(&mut self.referrers, &other.referrers);
+        add(&mut self.locations, &other.locations);
+        add(&mut self.channels, &other.channels);
+    }
+
+    // keeps the top names of every dimension, so a rollup row stays small however many referrers a link had
+    pub fn truncated(self) -> Self {
+        Aggregate {
+            browsers: top(self.browsers, TOP_NAMES),
+            os: top(self.os, TOP_NAMES),
+            devices: top(self.devices, TOP_NAMES),
+            referrers: top(self.referrers, TOP_NAMES),
+            locations: top(self.locations, TOP_NAMES),
+            channels: top(self.channels, TOP_NAMES),
+            ..self
+        }
+    }
+}
+
+impl From<InsightRollupModel> for (NaiveDate, Aggregate) {
+    fn from(rollup: InsightRollupModel) -> Self {
+        (rollup.day, Aggregate {
+            clicks: rollup.clicks as i64,
+            unique_visitors: rollup.unique_visitors as i64,
+            browsers: rollup.browsers.0,
+            os: rollup.os.0,
+            devices: rollup.devices.0,
+            referrers: rollup.referrers.0,
+            locations: rollup.locations.0,
+            channels: rollup.channels.0,
+        })
+    }
+}
+
+// the day of the click in UTC
+pub fn insight_day(insight_time: &Timestamp) -> Option<NaiveDate> {
+    DateTime::from_timestamp(insight_time.seconds, 0).map(|at| at.date_naive())
+}
+
+pub fn by_day(insights: Vec<Insight>) -> BTreeMap<NaiveDate, Aggregate> {
+    let mut days: BTreeMap<NaiveDate, Vec<Insight>> = BTreeMap::new();
+    for insight in insights {
+        if let Some(day) = insight.insight_time.as_ref().and_then(insight_day) {
+            days.entry(day).or_default().push(insight);
+        }
+    }
+    days.into_iter().map(|(day, insights)| (day, Aggregate::of_insights(&insights))).collect()
+}
+
+fn parse_day(value: &str, field: &str) -> Result<Option<NaiveDate>, ErrorMessage> {
+    if value.trim().is_empty() {
+        return Ok(None)
+    }
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map(Some)
+        .map_err(|_| ErrorMessage::new(format!("{} must be a YYYY-MM-DD date", field), 400))
+}
+
+// the inclusive range, `to` falls back to `default_to` and `from` to the `default_days` ending on `to`
+fn checked_range(from: (&str, &str), to: (&str, &str), default_to: NaiveDate, default_days: i64) -> Result<(NaiveDate, NaiveDate), ErrorMessage> {
+    let to_day = parse_day(to.0, to.1)?.unwrap_or(default_to);
+    let from_day = parse_day(from.0, from.1)?.unwrap_or(to_day - Duration::days(default_days - 1));
+    if from_day > to_day {
+        return Err(ErrorMessage::new(format!("{} must not be after {}", from.1, to.1), 400))
+    }
+    if (to_day - from_day).num_days() >= MAX_RANGE_DAYS {
+        return Err(ErrorMessage::new(format!("the range can be at most {} days", MAX_RANGE_DAYS), 400))
+    }
+    Ok((from_day, to_day))
+}
+
+// the inclusive range of the request, today and the 29 days before it when nothing was given
+pub fn parse_range(from: &str, to: &str, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), ErrorMessage> {
+    checked_range((from, "from"), (to, "to"), today, DEFAULT_RANGE_DAYS)
+}
+
+fn total(days: &BTreeMap<NaiveDate, Aggregate>) -> Aggregate {
+    let mut total = Aggregate::default();
+    for aggregate in days.values() {
+        total.merge(aggregate);
+    }
+    total
+}
+
+pub fn summary(id: i32, from: NaiveDate, to: NaiveDate, days: &BTreeMap<NaiveDate, Aggregate>) -> AnalyticsSummary {
+    let total = total(days);
+    AnalyticsSummary {
+        id,
+        from: from.to_string(),
+        to: to.to_string(),
+        clicks: total.clicks,
+        daily_unique_visitors: total.unique_visitors,
+        daily: days.iter().map(|(day, aggregate)| DailyClicks { day: day.to_string(), clicks: aggregate.clicks, unique_visitors: aggregate.unique_visitors }).collect(),
+        browsers: ranked(&total.browsers),
+        os: ranked(&total.os),
+        devices: ranked(&total.devices),
+        referrers: ranked(&total.referrers),
+        locations: ranked(&total.locations),
+        channels: ranked(&total.channels),
+    }
+}
+
+fn database_error(err: sqlx::Error) -> ErrorMessage {
+    tracing::error!("error while reading the rollups was {}", err) ;
+    ErrorMessage::new(String::from("An unexpected database error occurred"), 500)
+}
+
+// a link along with how far its insights were rolled up
+pub type RolledUpLink = (i32, String, Option<String>, Option<NaiveDate>);
+
+// the days of the range in the rollups, then the days after the last rollup (today at least) from the raw insights
+pub async fn link_days(link: &RolledUpLink, from: NaiveDate, to: NaiveDate, client: &Client, db: &Pool<Postgres>) -> Result<BTreeMap<NaiveDate, Aggregate>, ErrorMessage> {
+    let (id, shorten_url, domain, rolled_up_until) = link;
+    let rollups = sqlx::query_as::<_, InsightRollupModel>("select day, clicks, unique_visitors, browsers, os, devices, referrers, locations, channels \
+        from insight_rollups where url_id=$1 AND day BETWEEN $2 AND $3")
+        .bind(id).bind(from).bind(to).fetch_all(db).await.map_err(database_error)?;
+    let mut days = rollups.into_iter().map(<(NaiveDate, Aggregate)>::from).collect::<BTreeMap<_, _>>();
+
+    let raw_from = rolled_up_until.map(|day| day + Duration::days(1)).unwrap_or(from).max(from);
+    if raw_from <= to {
+        let key = insight_key(shorten_url, &domain.clone().unwrap_or_default());
+        let insights = insights_between(&key, raw_from, to + Duration::days(1), client).await
+            .map_err(|err| ErrorMessage::new(err, 500))?;
+        days.extend(by_day(insights));
+    }
+    Ok(days)
+}
+
+// the links behind the analytics, the url or all the links in the campaign's folder, they have to belong to the workspace
+async fn analytics_links(id: i32, workspace_id: i32, campaign: bool, db: &Pool<Postgres>) -> Result<Vec<RolledUpLink>, ErrorMessage> {
+    if !campaign {
+        let link = sqlx::query_as::<_, RolledUpLink>("select w.id, w.shorten_url, d.domain, w.rolled_up_until from website_urls w \
+            LEFT JOIN custom_domains d ON d.id = w.domain_id where w.id=$1 AND w.workspace_id=$2")
+            .bind(id).bind(workspace_id).fetch_optional(db).await.map_err(database_error)?;
+        return link.map(|link| vec![link]).ok_or(ErrorMessage::new("Shorten url doesn't exists".to_string(), 404))
+    }
+    let folder = sqlx::query_scalar::<_, i32>("select id from folders where id=$1 AND workspace_id=$2")
+        .bind(id).bind(workspace_id).fetch_optional(db).await.map_err(database_error)?;
+    if folder.is_none() {
+        return Err(ErrorMessage::new("Folder doesn't exists".to_string(), 404))
+    }
+    sqlx::query_as::<_, RolledUpLink>("select w.id, w.shorten_url, d.domain, w.rolled_up_until from website_urls w \
+        LEFT JOIN custom_domains d ON d.id = w.domain_id where w.folder_id=$1 AND w.workspace_id=$2 ORDER BY w.id")
+        .bind(id).bind(workspace_id).fetch_all(db).await.map_err(database_error)
+}
+
+pub async fn get_days(id: i32, workspace_id: i32, from: NaiveDate, to: NaiveDate, client: &Client, db: &Pool<Postgres>) -> Result<BTreeMap<NaiveDate, Aggregate>, ErrorMessage> {
+    let links = analytics_links(id, workspace_id, false, db).await?;
+    link_days(&links[0], from, to, client, db).await
+}
+
+// the clicks of all the links added up, by day
+async fn days_of_links(links: &[RolledUpLink], from: NaiveDate, to: NaiveDate, client: &Client, db: &Pool<Postgres>) -> Result<BTreeMap<NaiveDate, Aggregate>, ErrorMessage> {
+    let mut days: BTreeMap<NaiveDate, Aggregate> = BTreeMap::new();
+    for link in links {
+        for (day, aggregate) in link_days(link, from, to, client, db).await? {
+            days.entry(day).or_default().merge(&aggregate);
+        }
+    }
+    Ok(days)
+}
+
+pub async fn get_analytics_summary(request: AnalyticsRange, client: &Client, db: &Pool<Postgres>) -> Result<AnalyticsSummary, ErrorMessage> {
+    tracing::info!("get analytics summary was called with the id {}", request.id) ;
+    let (from, to) = parse_range(&request.from, &request.to, now().date())?;
+    let days = get_days(request.id, request.workspace_id, from, to, client, db).await?;
+    Ok(summary(request.id, from, to, &days))
+}
+
+// the default range of a comparison, this week against the last one
+const DEFAULT_COMPARISON_DAYS: i64 = 7;
+
+fn rounded(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+// None when there was nothing to compare against
+fn change_percent(current: i64, previous: i64) -> Option<f64> {
+    (previous != 0).then(|| rounded((current - previous) as f64 * 100.0 / previous as f64))
+}
+
+fn share(clicks: i64, total: i64) -> f64 {
+    if total == 0 { 0.0 } else { rounded(clicks as f64 * 100.0 / total as f64) }
+}
+
+fn delta(current: i64, previous: i64) -> MetricDelta {
+    MetricDelta { current, previous, change: current - previous, change_percent: change_percent(current, previous) }
+}
+
+// every name seen in either range, most current clicks first, then most previous clicks
+fn shares(current: &BTreeMap<String, i64>, previous: &BTreeMap<String, i64>, current_total: i64, previous_total: i64) -> Vec<DimensionShare> {
+    let names = current.keys().chain(previous.keys()).collect::<BTreeSet<_>>();
+    let mut shares = names.into_iter().map(|name| {
+        let (current_clicks, previous_clicks) = (current.get(name).copied().unwrap_or_default(), previous.get(name).copied().unwrap_or_default());
+        let (current_share, previous_share) = (share(current_clicks, current_total), share(previous_clicks, previous_total));
+        DimensionShare {
+            name: name.clone(),
+            current_clicks,
+            previous_clicks,
+            current_share,
+            previous_share,
+            share_change: rounded(current_share - previous_share),
+            clicks_change_percent: change_percent(current_clicks, previous_clicks),
+        }
+    }).collect::<Vec<_>>();
+    shares.sort_by(|a, b| b.current_clicks.cmp(&a.current_clicks).then(b.previous_clicks.cmp(&a.previous_clicks)).then_with(|| a.name.cmp(&b.name)));
+    shares
+}
+
+pub fn comparison(request: &AnalyticsComparisonRequest, ranges: [(NaiveDate, NaiveDate); 2], current: &Aggregate, previous: &Aggregate) -> AnalyticsComparison {
+    let [(from, to), (previous_from, previous_to)] = ranges;
+    AnalyticsComparison {
+        id: request.id,
+        campaign: request.campaign,
+        from: from.to_string(),
+        to: to.to_string(),
+        previous_from: previous_from.to_string(),
+        previous_to: previous_to.to_string(),
+        clicks: Some(delta(current.clicks, previous.clicks)),
+        daily_unique_visitors: Some(delta(current.unique_visitors, previous.unique_visitors)),
+        browsers: shares(&current.browsers, &previous.browsers, current.clicks, previous.clicks),
+        os: shares(&current.os, &previous.os, current.clicks, previous.clicks),
+        devices: shares(&current.devices, &previous.devices, current.clicks, previous.clicks),
+        referrers: shares(&current.referrers, &previous.referrers, current.clicks, previous.clicks),
+        channels: shares(&current.channels, &previous.channels, current.clicks, previous.clicks),
+    }
+}
+
+// the current range is the last 7 days by default, the previous one is as long as it and ends the day before it
+pub fn comparison_ranges(request: &AnalyticsComparisonRequest, today: NaiveDate) -> Result<[(NaiveDate, NaiveDate); 2], ErrorMessage> {
+    let (from, to) = checked_range((&request.from, "from"), (&request.to, "to"), today, DEFAULT_COMPARISON_DAYS)?;
+    let previous = checked_range((&request.previous_from, "previous_from"), (&request.previous_to, "previous_to"),
+        from - Duration::days(1), (to - from).num_days() + 1)?;
+    Ok([(from, to), previous])
+}
+
+pub async fn compare_analytics(request: AnalyticsComparisonRequest, client: &Client, db: &Pool<Postgres>) -> Result<AnalyticsComparison, ErrorMessage> {
+    tracing::info!("compare analytics was called with the id {} (campaign {})", request.id, request.campaign) ;
+    let ranges = comparison_ranges(&request, now().date())?;
+    let links = analytics_links(request.id, request.workspace_id, request.campaign, db).await?;
+    let [(from, to), (previous_from, previous_to)] = ranges;
+    let current = total(&days_of_links(&links, from, to, client, db).await?);
+    let previous = total(&days_of_links(&links, previous_from, previous_to, client, db).await?);
+    Ok(comparison(&request, ranges, &current, &previous))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(deprecated)]
+    fn insight(ip_address: &str, insight_time: &str, browser: &str) -> Insight {
+        Insight {
+            ip_address: ip_address.to_string(),
+            insight_time: insight_time.parse().ok(),
+            legacy_insight_time: insight_time.to_string(),
+            browser: browser.to_string(),
+            os: "Linux".to_string(),
+            device_type: "Other".to_string(),
+            refferal_source: "Direct".to_string(),
+            location: String::new(),
+            source: String::new(),
+            channel: "direct".to_string(),
+        }
+    }
+
+    #[test]
+    fn insights_are_rolled_up_per_day() {
+        let days = by_day(vec![
+            insight("1.1.1.1", "2026-03-01T10:00:00.000001Z", "Chrome"),
+            insight("1.1.1.1", "2026-03-01T23:59:59.999999Z", "Firefox"),
+            insight("2.2.2.2", "2026-03-02T00:00:00.000000Z", "Chrome"),
+            insight("3.3.3.3", "not a time", "Chrome"),
+        ]);
+        let first = &days[&NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()];
+        assert_eq!((first.clicks, first.unique_visitors), (2, 1));
+        assert_eq!(first.locations, BTreeMap::from([(UNKNOWN.to_string(), 2)]));
+        assert_eq!(days.len(), 2);
+
+        let summary = summary(7, NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(), &days);
+        assert_eq!((summary.clicks, summary.daily_unique_visitors, summary.daily.len()), (3, 2, 2));
+        assert_eq!(summary.browsers[0], DimensionCount { name: "Chrome".to_string(), count: 2 });
+    }
+
+    #[test]
+    fn referrers_are_counted_by_their_normalised_source() {
+        let social = Insight { refferal_source: "https://news.ycombinator.com/item?id=1".to_string(), source: "ycombinator.com".to_string(), channel: "social".to_string(), ..insight("1.1.1.1", "2026-03-01T10:00:00Z", "Chrome") };
+        let older = Insight { refferal_source: "https://old.example.com/".to_string(), channel: String::new(), ..insight("2.2.2.2", "2026-03-01T11:00:00Z", "Chrome") };
+        let aggregate = Aggregate::of_insights(&[social, older]);
+        assert_eq!(aggregate.referrers, BTreeMap::from([("ycombinator.com".to_string(), 1), ("https://old.example.com/".to_string(), 1)]));
+        assert_eq!(aggregate.channels, BTreeMap::from([("social".to_string(), 1), (UNKNOWN.to_string(), 1)]));
+    }
+
+    #[test]
+    fn rollups_keep_only_the_top_names() {
+        let mut aggregate = Aggregate::default();
+        for index in 0..15 {
+            aggregate.referrers.insert(format!("site{:02}", index), 100 - index);
+        }
+        aggregate.referrers.insert(OTHER.to_string(), 1);
+        let referrers = aggregate.truncated().referrers;
+        assert_eq!(referrers.len(), TOP_NAMES + 1);
+        assert_eq!(referrers[OTHER], (90 - 4..=90).sum::<i64>() + 1);
+        assert_eq!(referrers["site00"], 100);
+    }
+
+    #[test]
+    fn ranges_are_validated() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+        assert_eq!(parse_range("", "", today).unwrap(), (NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(), today));
+        assert!(parse_range("2026-03-02", "2026-03-01", today).is_err());
+        assert!(parse_range("2025-01-01", "2026-03-01", today).is_err());
+        assert!(parse_range("yesterday", "", today).is_err());
+    }
+
+    #[test]
+    fn previous_range_defaults_to_the_days_before() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+        let day = |day| NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+        let request = AnalyticsComparisonRequest::default();
+        assert_eq!(comparison_ranges(&request, today).unwrap(), [(day(25), day(31)), (day(18), day(24))]);
+
+        let request = AnalyticsComparisonRequest { from: "2026-03-10".to_string(), to: "2026-03-12".to_string(), ..Default::default() };
+        assert_eq!(comparison_ranges(&request, today).unwrap(), [(day(10), day(12)), (day(7), day(9))]);
+        let request = AnalyticsComparisonRequest { previous_from: "2026-02-01".to_string(), previous_to: "2026-02-07".to_string(), ..Default::default() };
+        assert_eq!(comparison_ranges(&request, today).unwrap()[1], (NaiveDate::from_ymd_opt(2026, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2026, 2, 7).unwrap()));
+        let request = AnalyticsComparisonRequest { previous_from: "2026-03-20".to_string(), previous_to: "2026-03-19".to_string(), ..Default::default() };
+        assert_eq!(comparison_ranges(&request, today).unwrap_err().message, "previous_from must not be after previous_to");
+    }
+
+    #[test]
+    fn deltas_and_shares_are_compared() {
+        let aggregate = |clicks, browsers: &[(&str, i64)]| Aggregate {
+            clicks,
+            unique_visitors: clicks / 2,
+            browsers: browsers.iter().map(|(name, count)| (name.to_string(), *count)).collect(),
+            ..Default::default()
+        };
+        let current = aggregate(150, &[("Chrome", 120), ("Safari", 30)]);
+        let previous = aggregate(100, &[("Chrome", 50), ("Firefox", 50)]);
+        let day = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let comparison = comparison(&AnalyticsComparisonRequest::default(), [(day, day), (day, day)], &current, &previous);
+
+        assert_eq!(comparison.clicks.unwrap(), MetricDelta { current: 150, previous: 100, change: 50, change_percent: Some(50.0) });
+        let names = comparison.browsers.iter().map(|share| share.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["Chrome", "Safari", "Firefox"]);
+        assert_eq!((comparison.browsers[0].current_share, comparison.browsers[0].previous_share, comparison.browsers[0].share_change), (80.0, 50.0, 30.0));
+        assert_eq!(comparison.browsers[0].clicks_change_percent, Some(140.0));
+        // new in the current range, nothing to take a percentage of
+        assert_eq!(comparison.browsers[1].clicks_change_percent, None);
+        assert_eq!(comparison.browsers[2].clicks_change_percent, Some(-100.0));
+        assert_eq!(delta(0, 0).change_percent, None);
+    }
+}
@@ -0,0 +1,203 @@
+use proto_definations_snip_sight::generated::url_shortner::{BioPage, BioPageLink, PublicBioPage};
+use proto_definations_snip_sight::name_blocklist::NameBlocklist;
+use sqlx::{Error, Pool, Postgres, Transaction};
+use crate::models::{BioPageLinkModel, BioPageModel, ErrorMessage};
+use crate::services::name_blocklist::check_custom_name;
+
+pub const THEMES: [&str; 2] = ["light", "dark"];
+const MIN_SLUG_LENGTH: usize = 3;
+const MAX_SLUG_LENGTH: usize = 50;
+const MAX_TITLE_LENGTH: usize = 100;
+const MAX_AVATAR_URL_LENGTH: usize = 2048;
+const MAX_PAGE_LINKS: usize = 50;
+
+// the pages along with their links in the order they are shown
+const PAGE_COLUMNS: &str = "p.id, p.user_id, p.workspace_id, p.slug, p.title, p.avatar_url, p.theme, \
+    ARRAY(select l.url_id from bio_page_links l where l.page_id = p.id ORDER BY l.position) AS url_ids, p.created_at";
+
+// slugs are lowercased, they are a part of the public url so only letters, numbers and inner '-' are allowed
+pub fn normalise_slug(slug: &str) -> Result<String, ErrorMessage> {
+    let slug = slug.trim().to_lowercase();
+    if slug.chars().count() < MIN_SLUG_LENGTH || slug.chars().count() > MAX_SLUG_LENGTH {
+        return Err(ErrorMessage::new(format!("slug must be {} to {} characters long", MIN_SLUG_LENGTH, MAX_SLUG_LENGTH), 400))
+    }
+    if !slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') || slug.starts_with('-') || slug.ends_with('-') {
+        return Err(ErrorMessage::new("slug can only have letters, numbers and - in between".to_string(), 400))
+    }
+    Ok(slug)
+}
+
+// the url ids without the repeated ones, in the order they came
+fn page_links(url_ids: &[i32]) -> Result<Vec<i32>, ErrorMessage> {
+    let mut links = Vec::new();
+    for id in url_ids {
+        if !links.contains(id) {
+            links.push(*id);
+        }
+    }
+    if links.len() > MAX_PAGE_LINKS {
+        return Err(ErrorMessage::new(format!("a page can have at most {} links", MAX_PAGE_LINKS), 400))
+    }
+    Ok(links)
+}
+
+// the page with its fields normalised, the theme defaults to light
+pub fn validate_page(page: BioPage) -> Result<BioPage, ErrorMessage> {
+    let slug = normalise_slug(&page.slug)?;
+    let title = page.title.trim().to_string();
+    if title.is_empty() || title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(ErrorMessage::new(format!("title must be 1 to {} characters long", MAX_TITLE_LENGTH), 400))
+    }
+    let avatar_url = page.avatar_url.trim().to_string();
+    if !avatar_url.is_empty() {
+        let valid = reqwest::Url::parse(&avatar_url).is_ok_and(|url| url.scheme() == "https" || url.scheme() == "http");
+        if !valid || avatar_url.len() > MAX_AVATAR_URL_LENGTH {
+            return Err(ErrorMessage::new("avatar must be an http or https url".to_string(), 400))
+        }
+    }
+    let theme = if page.theme.trim().is_empty() { THEMES[0].to_string() } else { page.theme.trim().to_lowercase() };
+    if !THEMES.contains(&theme.as_str()) {
+        return Err(ErrorMessage::new(format!("theme must be one of {}", THEMES.join(", ")), 400))
+    }
+    let url_ids = page_links(&page.url_ids)?;
+    Ok(BioPage { slug, title, avatar_url, theme, url_ids, ..page })
+}
+
+fn database_error(err: Error) -> ErrorMessage {
+    tracing::error!("database error was {}", err) ;
+    ErrorMessage::new(String::from("An unexpected database error occurred"), 500)
+}
+
+fn page_error(err: Error) -> ErrorMessage {
+    match err {
+        Error::Database(error) if error.constraint() == Some("unique_bio_page_slug") => {
+            tracing::warn!("The slug was already taken") ;
+            ErrorMessage::new("Slug already taken".to_string(), 409)
+        },
+        err => database_error(err)
+    }
+}
+
+// replaces the links of the page, every one of them has to belong to the workspace
+async fn set_page_links(page_id: i32, workspace_id: i32, url_ids: &[i32], transaction: &mut Transaction<'_, Postgres>) -> Result<(), ErrorMessage> {
+    let (owned,) = sqlx::query_as::<_, (i64,)>("select COUNT(*) from website_urls where workspace_id=$1 AND id = ANY($2)")
+        .bind(workspace_id).bind(url_ids).fetch_one(&mut **transaction).await.map_err(database_error)?;
+    if owned != url_ids.len() as i64 {
+        return Err(ErrorMessage::new("Shorten url doesn't exists".to_string(), 404))
+    }
+    sqlx::query("DELETE FROM bio_page_links where page_id=$1")
+        .bind(page_id).execute(&mut **transaction).await.map_err(database_error)?;
+    sqlx::query("insert into bio_page_links (page_id, url_id, position) select $1, url_id, position::INT from UNNEST($2::INT[]) WITH ORDINALITY AS links(url_id, position)")
+        .bind(page_id).bind(url_ids).execute(&mut **transaction).await.map_err(database_error)?;
+    Ok(())
+}
+
+async fn page_of(id: i32, transaction: &mut Transaction<'_, Postgres>) -> Result<BioPage, ErrorMessage> {
+    let page = sqlx::query_as::<_, BioPageModel>(&format!("select {} from bio_pages p where p.id=$1", PAGE_COLUMNS))
+        .bind(id).fetch_one(&mut **transaction).await.map_err(database_error)?;
+    Ok(BioPage::from(page))
+}
+
+pub async fn create_bio_page(request: BioPage, blocklist: &NameBlocklist, db: &Pool<Postgres>) -> Result<BioPage, ErrorMessage> {
+    let page = validate_page(request)?;
+    tracing::info!("create bio page was called with the slug {} for the workspace {}", page.slug, page.workspace_id) ;
+    check_custom_name(&page.slug, blocklist)?;
+
+    let mut transaction = db.begin().await.map_err(database_error)?;
+    let (id,) = sqlx::query_as::<_, (i32,)>("insert into bio_pages (user_id, workspace_id, slug, title, avatar_url, theme) values ($1, $2, $3, $4, NULLIF($5, ''), $6) RETURNING id")
+        .bind(page.user_id).bind(page.workspace_id).bind(&page.slug).bind(&page.title).bind(&page.avatar_url).bind(&page.theme)
+        .fetch_one(&mut *transaction).await.map_err(page_error)?;
+    set_page_links(id, page.workspace_id, &page.url_ids, &mut transaction).await?;
+    let page = page_of(id, &mut transaction).await?;
+    transaction.commit().await.map_err(database_error)?;
+    Ok(page)
+}
+
+// replaces everything of the page, the slug can be changed as long as it is free
+pub async fn update_bio_page(request: BioPage, blocklist: &NameBlocklist, db: &Pool<Postgres>) -> Result<BioPage, ErrorMessage> {
+    let page = validate_page(request)?;
+    tracing::info!("update bio page was called with the id {}", page.id) ;
+    check_custom_name(&page.slug, blocklist)?;
+
+    let mut transaction = db.begin().await.map_err(database_error)?;
+    let result = sqlx::query("update bio_pages SET slug=$1, title=$2, avatar_url=NULLIF($3, ''), theme=$4 where id=$5 AND workspace_id=$6")
+        .bind(&page.slug).bind(&page.title).bind(&page.avatar_url).bind(&page.theme).bind(page.id).bind(page.workspace_id)
+        .execute(&mut *transaction).await.map_err(page_error)?;
+    if result.rows_affected() == 0 {
+        return Err(ErrorMessage::new("Page doesn't exists".to_string(), 404))
+    }
+    set_page_links(page.id, page.workspace_id, &page.url_ids, &mut transaction).await?;
+    let page = page_of(page.id, &mut transaction).await?;
+    transaction.commit().await.map_err(database_error)?;
+    Ok(page)
+}
+
+pub async fn get_bio_pages(workspace_id: i32, db: &Pool<Postgres>) -> Result<Vec<BioPage>, ErrorMessage> {
+    tracing::info!("get bio pages was called for the workspace {}", workspace_id) ;
+    let pages = sqlx::query_as::<_, BioPageModel>(&format!("select {} from bio_pages p where p.workspace_id=$1 ORDER BY p.slug", PAGE_COLUMNS))
+        .bind(workspace_id).fetch_all(db).await.map_err(database_error)?;
+    Ok(pages.into_iter().map(BioPage::from).collect())
+}
+
+pub async fn delete_bio_page(id: i32, workspace_id: i32, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+    tracing::info!("delete bio page was called with the id {}", id) ;
+    // only the page goes, its links stay as they are
+    let result = sqlx::query("DELETE FROM bio_pages where id=$1 AND workspace_id=$2")
+        .bind(id).bind(workspace_id).execute(db).await.map_err(database_error)?;
+    if result.rows_affected() == 0 {
+        return Err(ErrorMessage::new("Page doesn't exists".to_string(), 404))
+    }
+    Ok(true)
+}
+
+// the page as the visitors see it, the disabled links and the ones whose window ended are not shown
+pub async fn get_public_bio_page(slug: &str, db: &Pool<Postgres>) -> Result<PublicBioPage, ErrorMessage> {
+    let slug = normalise_slug(slug).map_err(|_| ErrorMessage::new("Page doesn't exists".to_string(), 404))?;
+    let page = sqlx::query_as::<_, (i32, String, Option<String>, String)>("select id, title, avatar_url, theme from bio_pages where slug=$1")
+        .bind(&slug).fetch_optional(db).await.map_err(database_error)?;
+    let Some((id, title, avatar_url, theme)) = page else {
+        return Err(ErrorMessage::new("Page doesn't exists".to_string(), 404))
+    };
+    let links = sqlx::query_as::<_, BioPageLinkModel>("select w.shorten_url, d.domain, w.og_title, w.original_url from bio_page_links l \
+        JOIN website_urls w ON w.id = l.url_id LEFT JOIN custom_domains d ON d.id = w.domain_id \
+        where l.page_id=$1 AND w.disabled_reason IS NULL AND (w.active_until IS NULL OR w.active_until > (NOW() AT TIME ZONE 'UTC')) \
+        ORDER BY l.position")
+        .bind(id).fetch_all(db).await.map_err(database_error)?;
+    Ok(PublicBioPage {
+        slug,
+        title,
+        avatar_url: avatar_url.unwrap_or_default(),
+        theme,
+        links: links.into_iter().map(BioPageLink::from).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(slug: &str, url_ids: Vec<i32>) -> BioPage {
+        BioPage { slug: slug.to_string(), title: " Ada's links ".to_string(), url_ids, ..Default::default() }
+    }
+
+    #[test]
+    fn slugs_are_normalised() {
+        assert_eq!(normalise_slug(" Ada-Lovelace ").unwrap(), "ada-lovelace");
+        assert!(normalise_slug("ab").is_err());
+        assert!(normalise_slug("-ada").is_err());
+        assert!(normalise_slug("ada_l").is_err());
+        assert!(normalise_slug("adä").is_err());
+    }
+
+    #[test]
+    fn pages_are_validated() {
+        let valid = validate_page(page("ada", vec![3, 1, 3, 2])).unwrap();
+        assert_eq!((valid.title.as_str(), valid.theme.as_str(), valid.url_ids), ("Ada's links", "light", vec![3, 1, 2]));
+
+        assert_eq!(validate_page(BioPage { theme: "Dark".to_string(), ..page("ada", vec![]) }).unwrap().theme, "dark");
+        assert!(validate_page(BioPage { theme: "neon".to_string(), ..page("ada", vec![]) }).is_err());
+        assert!(validate_page(BioPage { avatar_url: "javascript:alert(1)".to_string(), ..page("ada", vec![]) }).is_err());
+        assert!(validate_page(BioPage { title: "  ".to_string(), ..page("ada", vec![]) }).is_err());
+        assert!(validate_page(page("ada", (0..=MAX_PAGE_LINKS as i32).collect())).is_err());
+    }
+}
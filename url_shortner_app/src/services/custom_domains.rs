@@ -0,0 +1,221 @@
+use hickory_resolver::TokioAsyncResolver;
+use hickory_resolver::config::{ResolverConfig, ResolverOpts};
+use proto_definations_snip_sight::generated::url_shortner::CustomDomain;
+use sqlx::{Error, Pool, Postgres};
+use crate::models::{CustomDomainModel, ErrorMessage};
+use crate::services::workspaces::MembershipLookup;
+
+const VERIFICATION_PREFIX: &str = "snipsight-verification=";
+const FREE_PLAN: &str = "free";
+
+// the resolver is injected in to the service, so tests can stub the DNS answers
+#[tonic::async_trait]
+pub trait TxtResolver: Send + Sync + std::fmt::Debug {
+    async fn txt_records(&self, name: &str) -> Result<Vec<String>, String>;
+}
+
+#[derive(Debug)]
+pub struct DnsTxtResolver {
+    resolver: TokioAsyncResolver,
+}
+
+impl DnsTxtResolver {
+    pub fn new() -> Self {
+        Self { resolver: TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default()) }
+    }
+}
+
+#[tonic::async_trait]
+impl TxtResolver for DnsTxtResolver {
+    async fn txt_records(&self, name: &str) -> Result<Vec<String>, String> {
+        let lookup = self.resolver.txt_lookup(name).await.map_err(|e| e.to_string())?;
+        Ok(lookup.iter().map(|txt| txt.to_string()).collect())
+    }
+}
+
+// hosts are compared in lowercase, without the port and the trailing dot
+pub fn normalise_domain(domain: &str) -> String {
+    let domain = domain.trim().to_lowercase();
+    let domain = domain.split(':').next().unwrap_or_default();
+    domain.trim_end_matches('.').to_string()
+}
+
+pub fn verification_record_name(domain: &str) -> String {
+    format!("_snipsight.{}", domain)
+}
+
+// condition used while resolving a shorten url on the requested host, the hosts which are not a
+// verified custom domain resolve to our own domain (domain_id NULL)
+pub fn domain_condition(param: usize) -> String {
+    format!("COALESCE(domain_id, 0) = COALESCE((select id from custom_domains where domain=${} AND verified_at IS NOT NULL), 0)", param)
+}
+
+// the custom domains are a premium feature, every plan other than the free one has them
+pub fn plan_has_custom_domains(plan: &str) -> bool {
+    !plan.trim().eq_ignore_ascii_case(FREE_PLAN)
+}
+
+// the plan of the user who made the workspace, whoever of its members is asking. the users without a row in
+// user_plans are on the free plan
+pub async fn check_custom_domain_plan(workspace_id: i32, memberships: &dyn MembershipLookup, db: &Pool<Postgres>) -> Result<(), ErrorMessage> {
+    let owner = memberships.owner(workspace_id).await.map_err(|err| {
+        tracing::error!("unable to get the owner of the workspace {} : {}", workspace_id, err) ;
+        ErrorMessage::new("Unable to check the plan of the workspace".to_string(), 500)
+    })?;
+    let result = sqlx::query_as::<_, (String,)>("select plan from user_plans where user_id=$1")
+        .bind(owner).fetch_optional(db).await ;
+    let plan = match result {
+        Ok(plan) => plan.map(|(plan,)| plan).unwrap_or(FREE_PLAN.to_string()),
+        Err(err) => {
+            tracing::error!("error while getting the plan of the user was {}", err) ;
+            return Err(ErrorMessage::new(String::from("An unexpected database error occurred"), 500))
+        }
+    };
+    if plan_has_custom_domains(&plan) {
+        Ok(())
+    } else {
+        tracing::warn!("the workspace {} of the user {} on the {} plan tried to use a custom domain", workspace_id, owner, plan) ;
+        Err(ErrorMessage::new("Custom domains need a premium plan".to_string(), 403))
+    }
+}
+
+pub async fn txt_record_matches(domain: &str, token: &str, resolver: &dyn TxtResolver) -> Result<bool, ErrorMessage> {
+    let expected = format!("{}{}", VERIFICATION_PREFIX, token);
+    match resolver.txt_records(&verification_record_name(domain)).await {
+        Ok(records) => Ok(records.iter().any(|record| record.trim() == expected)),
+        Err(err) => {
+            tracing::warn!("TXT lookup failed for {} : {}", domain, err) ;
+            Ok(false)
+        }
+    }
+}
+
+pub async fn add_custom_domain(workspace_id: i32, user_id: i32, domain: &str, memberships: &dyn MembershipLookup, db: &Pool<Postgres>) -> Result<CustomDomain, ErrorMessage> {
+    let domain = normalise_domain(domain) ;
+    tracing::info!("add custom domain was called with the domain {} for the workspace {}", domain, workspace_id) ;
+    check_custom_domain_plan(workspace_id, memberships, db).await? ;
+    let token = uuid::Uuid::new_v4().simple().to_string() ;
+    let result = sqlx::query_as::<_, CustomDomainModel>("insert into custom_domains (user_id, workspace_id, domain, verification_token) values ($1, $2, $3, $4) RETURNING *")
+        .bind(user_id).bind(workspace_id).bind(&domain).bind(token)
+        .fetch_one(db).await ;
+    match result {
+        Ok(result) => Ok(CustomDomain::from(result)),
+        Err(Error::Database(error)) if error.constraint() == Some("unique_workspace_domain") => {
+            tracing::warn!("The domain was already added to the workspace") ;
+            Err(ErrorMessage::new("Domain already added".to_string(), 409))
+        },
+        Err(err) => {
+            tracing::error!("error while inserting into custom_domains was {}", err) ;
+            Err(ErrorMessage::new(String::from("An unexpected database error occurred"), 500))
+        }
+    }
+}
+
+pub async fn verify_custom_domain(id: i32, workspace_id: i32, memberships: &dyn MembershipLookup, db: &Pool<Postgres>, resolver: &dyn TxtResolver) -> Result<CustomDomain, ErrorMessage> {
+    tracing::info!("verify custom domain was called with the id {}", id) ;
+    // checked again, the plan could have been downgraded after the domain was added
+    check_custom_domain_plan(workspace_id, memberships, db).await? ;
+    let domain = sqlx::query_as::<_, CustomDomainModel>("select * from custom_domains where id=$1 AND workspace_id=$2")
+        .bind(id).bind(workspace_id).fetch_one(db).await ;
+    let domain = match domain {
+        Ok(domain) => domain,
+        Err(Error::RowNotFound) => {
+            tracing::warn!("NO Row Found") ;
+            return Err(ErrorMessage::new("Domain doesn't exists".to_string(), 404))
+        },
+        Err(err) => {
+            tracing::error!("error while getting the custom domain was {}", err) ;
+            return Err(ErrorMessage::new(String::from("An unexpected database error occurred"), 500))
+        }
+    };
+    if domain.verified_at.is_some() {
+        return Ok(CustomDomain::from(domain))
+    }
+
+    if !txt_record_matches(&domain.domain, &domain.verification_token, resolver).await? {
+        return Err(ErrorMessage::new(format!("TXT record {}{} was not found on {}", VERIFICATION_PREFIX, domain.verification_token, verification_record_name(&domain.domain)), 400))
+    }
+
+    let result = sqlx::query_as::<_, CustomDomainModel>("update custom_domains SET verified_at=NOW() where id=$1 RETURNING *")
+        .bind(id).fetch_one(db).await ;
+    match result {
+        Ok(result) => {
+            tracing::info!("domain {} verified", result.domain) ;
+            Ok(CustomDomain::from(result))
+        },
+        Err(Error::Database(error)) if error.constraint() == Some("custom_domains_verified_domain_key") => {
+            tracing::warn!("The domain was already verified by an other user") ;
+            Err(ErrorMessage::new("Domain already verified by an other account".to_string(), 409))
+        },
+        Err(err) => {
+            tracing::error!("error while verifying the custom domain was {}", err) ;
+            Err(ErrorMessage::new(String::from("An unexpected database error occurred"), 500))
+        }
+    }
+}
+
+pub async fn get_custom_domains(workspace_id: i32, db: &Pool<Postgres>) -> Result<Vec<CustomDomain>, ErrorMessage> {
+    tracing::info!("get custom domains was called with the workspace_id {}", workspace_id) ;
+    let result = sqlx::query_as::<_, CustomDomainModel>("select * from custom_domains where workspace_id=$1 ORDER BY id")
+        .bind(workspace_id).fetch_all(db).await ;
+    match result {
+        Ok(domains) => Ok(domains.into_iter().map(CustomDomain::from).collect()),
+        Err(err) => {
+            tracing::error!("error while getting the custom domains was {}", err) ;
+            Err(ErrorMessage::new("Internal Server Error".to_string(), 500))
+        }
+    }
+}
+
+// used by the gateway to allow these domains through CORS, so the tokens are not shared
+pub async fn get_verified_domains(db: &Pool<Postgres>) -> Result<Vec<CustomDomain>, ErrorMessage> {
+    let result = sqlx::query_as::<_, CustomDomainModel>("select * from custom_domains where verified_at IS NOT NULL")
+        .fetch_all(db).await ;
+    match result {
+        Ok(domains) => Ok(domains.into_iter().map(|domain| CustomDomain {
+            verification_token: String::new(),
+            ..CustomDomain::from(domain)
+        }).collect()),
+        Err(err) => {
+            tracing::error!("error while getting the verified domains was {}", err) ;
+            Err(ErrorMessage::new("Internal Server Error".to_string(), 500))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct StubResolver(Vec<String>);
+
+    #[tonic::async_trait]
+    impl TxtResolver for StubResolver {
+        async fn txt_records(&self, name: &str) -> Result<Vec<String>, String> {
+            assert_eq!(name, "_snipsight.go.example.com");
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn domains_are_normalised() {
+        assert_eq!(normalise_domain(" Go.Example.COM:8080 "), "go.example.com");
+        assert_eq!(normalise_domain("go.example.com."), "go.example.com");
+    }
+
+    #[test]
+    fn custom_domains_need_a_paid_plan() {
+        assert!(!plan_has_custom_domains("free"));
+        assert!(!plan_has_custom_domains(" Free "));
+        assert!(plan_has_custom_domains("pro"));
+        assert!(plan_has_custom_domains("business"));
+    }
+
+    #[tokio::test]
+    async fn txt_record_needs_the_exact_token() {
+        let resolver = StubResolver(vec!["v=spf1 -all".to_string(), "snipsight-verification=abc123".to_string()]);
+        assert!(txt_record_matches("go.example.com", "abc123", &resolver).await.unwrap());
+        assert!(!txt_record_matches("go.example.com", "abc", &resolver).await.unwrap());
+    }
+}
@@ -0,0 +1,459 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use aws_sdk_dynamodb::Client as DynamoClient;
+use chrono::NaiveDate;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Deserialize;
+use sqlx::{Pool, Postgres};
+use crate::models::ErrorMessage;
+use crate::services::activation_windows::now;
+use crate::services::analytics::{link_days, Aggregate, RolledUpLink};
+
+// the links listed as the top ones, and the most changed ones
+const TOP_LINKS: usize = 5;
+// a change is notable when it is at least these many clicks and half of what it was
+const MIN_NOTABLE_CHANGE: i64 = 10;
+const MIN_NOTABLE_PERCENT: f64 = 50.0;
+const DASHBOARD_URL: &str = "https://web.snipsight.phani.services";
+
+#[derive(Debug, Clone)]
+pub struct DigestConfig {
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    // STARTTLS, turned off for a local SMTP sink
+    pub smtp_tls: bool,
+    pub smtp_credentials: Option<(String, String)>,
+    pub from: Mailbox,
+    // the authentication service keeps the digest settings and the mail ids of the users
+    pub authentication_url: String,
+}
+
+impl DigestConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let smtp_tls = var("SMTP_TLS").map(|tls| tls != "false").unwrap_or(true);
+        Self {
+            smtp_host: var("SMTP_HOST").unwrap_or("localhost".to_string()),
+            smtp_port: var("SMTP_PORT").and_then(|port| port.parse().ok()).unwrap_or(if smtp_tls { 587 } else { 25 }),
+            smtp_tls,
+            smtp_credentials: var("SMTP_USERNAME").zip(var("SMTP_PASSWORD")),
+            from: var("DIGEST_FROM").and_then(|from| from.parse().ok())
+                .unwrap_or("SnipSight <digest@snipsight.phani.services>".parse().unwrap()),
+            authentication_url: var("AUTHENTICATION_URL").unwrap_or("http://authentication-container:9090".to_string()),
+        }
+    }
+
+    pub fn mailer(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, String> {
+        let builder = if self.smtp_tls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.smtp_host).map_err(|err| err.to_string())?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.smtp_host)
+        };
+        let builder = builder.port(self.smtp_port).timeout(Some(Duration::from_secs(30)));
+        Ok(match &self.smtp_credentials {
+            Some((username, password)) => builder.credentials(Credentials::new(username.clone(), password.clone())).build(),
+            None => builder.build()
+        })
+    }
+}
+
+// a user whose digest is due, from the authentication service
+#[derive(Deserialize, Debug, Clone)]
+pub struct DigestRecipient {
+    pub user_id: i32,
+    pub username: String,
+    pub mail_id: String,
+    pub frequency: String,
+}
+
+#[derive(Deserialize)]
+struct DigestRecipients {
+    recipients: Vec<DigestRecipient>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkDigest {
+    // the code, along with the custom domain when it has one
+    pub name: String,
+    pub clicks: i64,
+    pub previous_clicks: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Digest {
+    pub username: String,
+    pub frequency: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub clicks: i64,
+    pub previous_clicks: i64,
+    pub daily_unique_visitors: i64, // the unique visitors of each day added up
+    // every link of the user, most clicks first
+    pub links: Vec<LinkDigest>,
+}
+
+// the complete days covered by the digest, yesterday or the 7 days ending yesterday, and the same length before them
+pub fn digest_ranges(frequency: &str, today: NaiveDate) -> [(NaiveDate, NaiveDate); 2] {
+    let days = if frequency == "daily" { 1 } else { 7 };
+    let to = today - chrono::Duration::days(1);
+    let from = to - chrono::Duration::days(days - 1);
+    [(from, to), (from - chrono::Duration::days(days), from - chrono::Duration::days(1))]
+}
+
+fn clicks_between(days: &BTreeMap<NaiveDate, Aggregate>, (from, to): (NaiveDate, NaiveDate)) -> Aggregate {
+    let mut total = Aggregate::default();
+    for (_, aggregate) in days.range(from..=to) {
+        total.merge(aggregate);
+    }
+    total
+}
+
+// the days of each link span both the ranges, they are split here
+pub fn digest(recipient: &DigestRecipient, ranges: [(NaiveDate, NaiveDate); 2], links: Vec<(String, BTreeMap<NaiveDate, Aggregate>)>) -> Digest {
+    let [current, previous] = ranges;
+    let mut digest = Digest {
+        username: recipient.username.clone(),
+        frequency: recipient.frequency.clone(),
+        from: current.0,
+        to: current.1,
+        clicks: 0,
+        previous_clicks: 0,
+        daily_unique_visitors: 0,
+        links: Vec::new(),
+    };
+    for (name, days) in links {
+        let (current, previous) = (clicks_between(&days, current), clicks_between(&days, previous));
+        digest.clicks += current.clicks;
+        digest.previous_clicks += previous.clicks;
+        digest.daily_unique_visitors += current.unique_visitors;
+        digest.links.push(LinkDigest { name, clicks: current.clicks, previous_clicks: previous.clicks });
+    }
+    digest.links.sort_by(|a, b| b.clicks.cmp(&a.clicks).then_with(|| a.name.cmp(&b.name)));
+    digest
+}
+
+fn change_percent(current: i64, previous: i64) -> Option<f64> {
+    (previous != 0).then(|| ((current - previous) as f64 * 1000.0 / previous as f64).round() / 10.0)
+}
+
+pub fn top_links(digest: &Digest) -> Vec<&LinkDigest> {
+    digest.links.iter().filter(|link| link.clicks > 0).take(TOP_LINKS).collect()
+}
+
+// the links which gained or lost the most, a new link getting its first clicks counts as well
+pub fn notable_changes(digest: &Digest) -> Vec<&LinkDigest> {
+    let mut changes = digest.links.iter().filter(|link| {
+        let change = link.clicks - link.previous_clicks;
+        change.abs() >= MIN_NOTABLE_CHANGE && change_percent(link.clicks, link.previous_clicks).is_none_or(|percent| percent.abs() >= MIN_NOTABLE_PERCENT)
+    }).collect::<Vec<_>>();
+    changes.sort_by_key(|link| std::cmp::Reverse((link.clicks - link.previous_clicks).abs()));
+    changes.truncate(TOP_LINKS);
+    changes
+}
+
+fn change_text(current: i64, previous: i64) -> String {
+    match change_percent(current, previous) {
+        Some(percent) => format!("{:+}%", percent),
+        None if current == 0 => "no change".to_string(),
+        None => "new".to_string(),
+    }
+}
+
+fn period(digest: &Digest) -> String {
+    if digest.from == digest.to { digest.from.format("%b %-d, %Y").to_string() } else { format!("{} - {}", digest.from.format("%b %-d"), digest.to.format("%b %-d, %Y")) }
+}
+
+pub fn subject(digest: &Digest) -> String {
+    let frequency = if digest.frequency == "daily" { "daily" } else { "weekly" };
+    format!("Your {} SnipSight digest: {} clicks ({})", frequency, digest.clicks, change_text(digest.clicks, digest.previous_clicks))
+}
+
+pub fn render_text(digest: &Digest) -> String {
+    let mut text = format!("Hi {},\n\nHere is how your links did for {}.\n\nTotal clicks: {} ({} from {})\nUnique visitors (counted once a day): {}\n",
+        digest.username, period(digest), digest.clicks, change_text(digest.clicks, digest.previous_clicks), digest.previous_clicks, digest.daily_unique_visitors);
+    let top = top_links(digest);
+    if !top.is_empty() {
+        text.push_str("\nTop links\n");
+        for link in top {
+            text.push_str(&format!("- {}: {} clicks\n", link.name, link.clicks));
+        }
+    }
+    let changes = notable_changes(digest);
+    if !changes.is_empty() {
+        text.push_str("\nNotable changes\n");
+        for link in changes {
+            text.push_str(&format!("- {}: {} -> {} clicks ({})\n", link.name, link.previous_clicks, link.clicks, change_text(link.clicks, link.previous_clicks)));
+        }
+    }
+    text.push_str(&format!("\nSee the full analytics at {}\nYou can turn these emails off or change how often they come in your settings.\n", DASHBOARD_URL));
+    text
+}
+
+fn escape_html(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&#39;")
+}
+
+fn html_rows(links: &[&LinkDigest], changes: bool) -> String {
+    links.iter().map(|link| {
+        let value = if changes {
+            format!("{} &rarr; {} ({})", link.previous_clicks, link.clicks, change_text(link.clicks, link.previous_clicks))
+        } else {
+            link.clicks.to_string()
+        };
+        format!("<tr><td style=\"padding:4px 12px 4px 0\">{}</td><td style=\"padding:4px 0;text-align:right\">{}</td></tr>", escape_html(&link.name), value)
+    }).collect()
+}
+
+pub fn render_html(digest: &Digest) -> String {
+    let (top, changes) = (top_links(digest), notable_changes(digest));
+    let mut sections = String::new();
+    if !top.is_empty() {
+        sections.push_str(&format!("<h3>Top links</h3><table>{}</table>", html_rows(&top, false)));
+    }
+    if !changes.is_empty() {
+        sections.push_str(&format!("<h3>Notable changes</h3><table>{}</table>", html_rows(&changes, true)));
+    }
+    format!(r#"<!DOCTYPE html>
+<html lang="en">
+<head><meta charset="utf-8"><title>{subject}</title></head>
+<body style="font-family:sans-serif;color:#222;max-width:560px;margin:0 auto;padding:24px">
+<p>Hi {username},</p>
+<p>Here is how your links did for {period}.</p>
+<p style="font-size:28px;margin:8px 0"><strong>{clicks}</strong> clicks <span style="font-size:16px;color:#666">{change} from {previous}</span></p>
+<p>{unique_visitors} unique visitors, counted once a day</p>
+{sections}
+<p><a href="{dashboard}">See the full analytics</a></p>
+<p style="font-size:12px;color:#888">You can turn these emails off or change how often they come in your settings.</p>
+</body>
+</html>"#,
+        subject = escape_html(&subject(digest)),
+        username = escape_html(&digest.username),
+        period = period(digest),
+        clicks = digest.clicks,
+        change = change_text(digest.clicks, digest.previous_clicks),
+        previous = digest.previous_clicks,
+        unique_visitors = digest.daily_unique_visitors,
+        dashboard = DASHBOARD_URL,
+    )
+}
+
+pub fn digest_message(from: &Mailbox, recipient: &DigestRecipient, digest: &Digest) -> Result<Message, String> {
+    let to = recipient.mail_id.parse::<Mailbox>().map_err(|err| format!("invalid mail id {} : {}", recipient.mail_id, err))?;
+    Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(subject(digest))
+        .multipart(MultiPart::alternative_plain_html(render_text(digest), render_html(digest)))
+        .map_err(|err| err.to_string())
+}
+
+// the user's links and their days over both the ranges, None when the user has no links to report on
+async fn collect_digest(recipient: &DigestRecipient, today: NaiveDate, client: &DynamoClient, db: &Pool<Postgres>) -> Result<Option<Digest>, ErrorMessage> {
+    let links = sqlx::query_as::<_, RolledUpLink>("select w.id, w.shorten_url, d.domain, w.rolled_up_until from website_urls w \
+        LEFT JOIN custom_domains d ON d.id = w.domain_id where w.user_id=$1 ORDER BY w.id")
+        .bind(recipient.user_id).fetch_all(db).await.map_err(|err| {
+            tracing::error!("unable to get the links of the digest : {}", err) ;
+            ErrorMessage::new("An unexpected database error occurred".to_string(), 500)
+        })?;
+    if links.is_empty() {
+        return Ok(None)
+    }
+    let ranges = digest_ranges(&recipient.frequency, today);
+    let mut days = Vec::with_capacity(links.len());
+    for link in &links {
+        let name = match &link.2 {
+            Some(domain) => format!("{}/{}", domain, link.1),
+            None => link.1.clone(),
+        };
+        days.push((name, link_days(link, ranges[1].0, ranges[0].1, client, db).await?));
+    }
+    Ok(Some(digest(recipient, ranges, days)))
+}
+
+pub struct DigestSender {
+    config: DigestConfig,
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+    http: reqwest::Client,
+}
+
+impl DigestSender {
+    pub fn new(config: DigestConfig) -> Result<Self, String> {
+        let mailer = config.mailer()?;
+        let http = reqwest::Client::builder().timeout(Duration::from_secs(10)).build().map_err(|err| err.to_string())?;
+        Ok(Self { config, mailer, http })
+    }
+
+    async fn recipients(&self) -> Result<Vec<DigestRecipient>, String> {
+        let response = self.http.get(format!("{}/digest-recipients", self.config.authentication_url)).send().await
+            .and_then(|response| response.error_for_status()).map_err(|err| err.to_string())?;
+        let body = response.text().await.map_err(|err| err.to_string())?;
+        Ok(serde_json::from_str::<DigestRecipients>(&body).map_err(|err| err.to_string())?.recipients)
+    }
+
+    async fn mark_sent(&self, user_id: i32) -> Result<(), String> {
+        self.http.post(format!("{}/digest-sent/{}", self.config.authentication_url, user_id)).send().await
+            .and_then(|response| response.error_for_status()).map(|_| ()).map_err(|err| err.to_string())
+    }
+
+    async fn send(&self, recipient: &DigestRecipient, today: NaiveDate, client: &DynamoClient, db: &Pool<Postgres>) -> Result<bool, String> {
+        let digest = collect_digest(recipient, today, client, db).await.map_err(|err| err.message)?;
+        if let Some(digest) = &digest {
+            let message = digest_message(&self.config.from, recipient, digest)?;
+            self.mailer.send(message).await.map_err(|err| err.to_string())?;
+        }
+        // marked even without links, otherwise the user would come back on every run
+        self.mark_sent(recipient.user_id).await?;
+        Ok(digest.is_some())
+    }
+
+    // sends the digests which are due, returns how many were sent. a failed one isn't marked, so it's retried on the next run
+    pub async fn send_due(&self, client: &DynamoClient, db: &Pool<Postgres>) -> Result<u64, String> {
+        let today = now().date();
+        let mut sent = 0;
+        for recipient in self.recipients().await? {
+            match self.send(&recipient, today, client, db).await {
+                Ok(true) => sent += 1,
+                Ok(false) => {},
+                Err(err) => tracing::error!("unable to send the digest of the user {} : {}", recipient.user_id, err)
+            }
+        }
+        Ok(sent)
+    }
+}
+
+pub async fn run_digests(sender: DigestSender, client: Arc<DynamoClient>, db: Arc<Pool<Postgres>>, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        match sender.send_due(&client, &db).await {
+            Ok(0) => {},
+            Ok(sent) => tracing::info!("{} digests were sent", sent),
+            Err(err) => tracing::error!("unable to send the digests {}", err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn clicks(days: &[(u32, i64)]) -> BTreeMap<NaiveDate, Aggregate> {
+        days.iter().map(|(on, clicks)| (day(*on), Aggregate { clicks: *clicks, unique_visitors: clicks / 2, ..Default::default() })).collect()
+    }
+
+    fn recipient(frequency: &str) -> DigestRecipient {
+        DigestRecipient { user_id: 7, username: "phani <admin>".to_string(), mail_id: "phani@example.com".to_string(), frequency: frequency.to_string() }
+    }
+
+    fn weekly() -> Digest {
+        let ranges = digest_ranges("weekly", day(15));
+        digest(&recipient("weekly"), ranges, vec![
+            ("launch".to_string(), clicks(&[(2, 40), (9, 100), (14, 20)])),
+            ("go.acme.com/sale".to_string(), clicks(&[(8, 5), (10, 60)])),
+            ("old".to_string(), clicks(&[(7, 30)])),
+            ("quiet".to_string(), clicks(&[(12, 3)])),
+        ])
+    }
+
+    #[test]
+    fn digests_cover_the_complete_days() {
+        assert_eq!(digest_ranges("daily", day(15)), [(day(14), day(14)), (day(13), day(13))]);
+        assert_eq!(digest_ranges("weekly", day(15)), [(day(8), day(14)), (day(1), day(7))]);
+    }
+
+    #[test]
+    fn links_are_split_into_the_ranges() {
+        let digest = weekly();
+        assert_eq!((digest.clicks, digest.previous_clicks, digest.daily_unique_visitors), (188, 70, 93));
+        assert_eq!(top_links(&digest).iter().map(|link| link.name.as_str()).collect::<Vec<_>>(), ["launch", "go.acme.com/sale", "quiet"]);
+        // the new link, the link which went quiet and the grown one, "quiet" is too small a change
+        let changes = notable_changes(&digest).iter().map(|link| (link.name.as_str(), link.previous_clicks, link.clicks)).collect::<Vec<_>>();
+        assert_eq!(changes, [("launch", 40, 120), ("go.acme.com/sale", 0, 65), ("old", 30, 0)]);
+    }
+
+    #[test]
+    fn the_html_and_text_say_the_same() {
+        let digest = weekly();
+        assert_eq!(subject(&digest), "Your weekly SnipSight digest: 188 clicks (+168.6%)");
+        let text = render_text(&digest);
+        assert!(text.contains("Here is how your links did for Mar 8 - Mar 14, 2026."));
+        assert!(text.contains("- launch: 40 -> 120 clicks (+200%)"));
+        assert!(text.contains("- go.acme.com/sale: 0 -> 65 clicks (new)"));
+        let html = render_html(&digest);
+        assert!(html.contains("Hi phani &lt;admin&gt;,"));
+        assert!(html.contains("30 &rarr; 0 (-100%)"));
+    }
+
+    // a local SMTP sink, it accepts every message and keeps what came after DATA
+    async fn smtp_sink(messages: Arc<Mutex<Vec<String>>>) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            loop {
+                let (socket, _) = listener.accept().await.unwrap();
+                let messages = messages.clone();
+                tokio::spawn(async move {
+                    let (reader, mut writer) = socket.into_split();
+                    let mut lines = BufReader::new(reader).lines();
+                    writer.write_all(b"220 sink ESMTP\r\n").await.unwrap();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let command = line.to_uppercase();
+                        let reply: &[u8] = if command.starts_with("EHLO") {
+                            b"250-sink\r\n250 8BITMIME\r\n"
+                        } else if command.starts_with("DATA") {
+                            writer.write_all(b"354 go ahead\r\n").await.unwrap();
+                            let mut message = String::new();
+                            while let Ok(Some(line)) = lines.next_line().await {
+                                if line == "." { break }
+                                message.push_str(&line);
+                                message.push('\n');
+                            }
+                            messages.lock().unwrap().push(message);
+                            b"250 queued\r\n"
+                        } else if command.starts_with("QUIT") {
+                            writer.write_all(b"221 bye\r\n").await.unwrap();
+                            break
+                        } else {
+                            b"250 ok\r\n"
+                        };
+                        writer.write_all(reply).await.unwrap();
+                    }
+                });
+            }
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn digests_are_sent_as_html_and_text() {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let config = DigestConfig {
+            smtp_host: "127.0.0.1".to_string(),
+            smtp_port: smtp_sink(messages.clone()).await,
+            smtp_tls: false,
+            smtp_credentials: None,
+            from: "SnipSight <digest@example.com>".parse().unwrap(),
+            authentication_url: String::new(),
+        };
+        let message = digest_message(&config.from, &recipient("weekly"), &weekly()).unwrap();
+        config.mailer().unwrap().send(message).await.unwrap();
+
+        let message = messages.lock().unwrap()[0].clone();
+        assert!(message.contains("To: phani@example.com"));
+        assert!(message.contains("Subject: Your weekly SnipSight digest: 188 clicks (+168.6%)"));
+        assert!(message.contains("multipart/alternative"));
+        assert!(message.contains("Content-Type: text/plain; charset=utf-8"));
+        assert!(message.contains("Content-Type: text/html; charset=utf-8"));
+        assert!(digest_message(&config.from, &DigestRecipient { mail_id: "not a mail".to_string(), ..recipient("daily") }, &weekly()).is_err());
+    }
+}
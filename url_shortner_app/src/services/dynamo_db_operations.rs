@@ -1,99 +1,271 @@
 use std::collections::HashMap;
-use aws_config::BehaviorVersion;
-use aws_sdk_sqs::Client;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, NaiveDate, Utc};
 use proto_definations_snip_sight::generated::url_shortner::{GetInsights, Insight, KeyInsights};
-use crate::models::{DeleteInsight, ErrorMessage};
-use serde_json::to_string;
+use crate::models::ErrorMessage;
+use crate::services::custom_domains::normalise_domain;
 use aws_sdk_dynamodb::Client as DynamoClient;
 use aws_sdk_dynamodb::types::AttributeValue;
+use snipsight_events::insights::{delete_insights, insight_key};
 
-pub async fn get_insights(request : GetInsights, client: &DynamoClient) -> Result<KeyInsights, String>{
-    // here we need to get the insights from the dynamo db
-    tracing::info!("Getting insights from Dynamo DB");
+const INSIGHTS_TABLE: &str = "ShortenURLInsights";
 
-    let mut last_key = HashMap::new();
-    last_key.insert("shorten_url".to_string(), AttributeValue::S(request.shorten_url.clone()));
-    last_key.insert("insight_time".to_string(), AttributeValue::S(request.last_evaluated_key.clone())) ;// here last_evaluated_key was the insight time
-
-    let query = client.query()
-        .table_name("ShortenURLInsights")
-        .key_condition_expression("shorten_url = :s")
-        .expression_attribute_values(":s", AttributeValue::S(request.shorten_url.clone()))
-        .scan_index_forward(false)
-        .limit(request.page_size as i32)
-        .set_exclusive_start_key(Some(last_key));
-
-    let result = query.send().await.map_err(|e| {
-        Err(e.to_string())
-    });
-    match result {
-        Ok(result) => {
-            let items = result.items();
-
-            let insights = items
-                .into_iter()
-                .filter_map(|item| {
-                    Some(Insight {
-                        ip_address: item.get("ip_address")?.as_s().ok()?.to_string(),
-                        refferal_source: item.get("refferal_source")?.as_s().ok()?.to_string(),
-                        device_type: item.get("device_type")?.as_s().ok()?.to_string(),
-                        browser: item.get("browser")?.as_s().ok()?.to_string(),
-                        os: item.get("os")?.as_s().ok()?.to_string(),
-                        location: item.get("location")?.as_s().unwrap_or(&"".into()).to_string(),
-                        insight_time: item.get("insight_time")?.as_s().ok()?.to_string(),
-                    })
-                })
-                .collect::<Vec<Insight>>();
-
-            let (shorten_url, insight_time) = match result.last_evaluated_key() {
-                Some(key_map) => (
-                    key_map.get("shorten_url").and_then(|v| v.as_s().ok()).unwrap_or(&request.shorten_url).to_string(),
-                    key_map.get("insight_time").and_then(|v| v.as_s().ok()).unwrap_or(&String::from("")).to_string(),
-                ),
-                None => (request.shorten_url.clone(), "".to_string()), // fallback
-                // this executes, when there are no more records down the line
-            };
-
-            Ok(KeyInsights {
-                list: insights,
-                shorten_url,
-                insight_time,
-            })
-        },
-        Err(err) => {
-            match err {
-                Ok(err) => {
-                    tracing::error!("Error getting insights from Dynamo DB inside Error OK was {}", err);
-                    Err(err)
-                },
-                Err(err) => {
-                    tracing::error!("Error getting insights from Dynamo DB inside Error Err was {}", err);
-                    Err(err.to_string())
-                }
+// the insight_time the next page starts after, kept base64 so the clients don't build it themselves
+fn page_token(insight_time: &str) -> String {
+    URL_SAFE_NO_PAD.encode(insight_time)
+}
+
+fn parse_page_token(token: &str) -> Result<String, ErrorMessage> {
+    URL_SAFE_NO_PAD.decode(token).ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .filter(|insight_time| !insight_time.is_empty())
+        .ok_or(ErrorMessage::new("invalid page token".to_string(), 400))
+}
+
+// the bound in the format of insight_time, the stored ones have microseconds so they compare as strings
+fn time_bound(value: &str, name: &str) -> Result<Option<String>, ErrorMessage> {
+    if value.is_empty() {
+        return Ok(None)
+    }
+    let time = DateTime::parse_from_rfc3339(value)
+        .map_err(|_| ErrorMessage::new(format!("{} has to be an RFC 3339 timestamp", name), 400))?;
+    Ok(Some(time.with_timezone(&Utc).format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string()))
+}
+
+// the key condition and filter of the request, along with the attribute names and the values they use
+type InsightsQuery = (String, Option<String>, Option<HashMap<String, String>>, HashMap<String, AttributeValue>);
+
+fn insights_query(key: &str, request: &GetInsights) -> Result<InsightsQuery, ErrorMessage> {
+    let mut values = HashMap::from([(":s".to_string(), AttributeValue::S(key.to_string()))]);
+    let (from, to) = (time_bound(&request.from, "from")?, time_bound(&request.to, "to")?);
+    let condition = match (from, to) {
+        (Some(from), Some(to)) => {
+            if from > to {
+                return Err(ErrorMessage::new("from must not be after to".to_string(), 400))
             }
+            values.insert(":from".to_string(), AttributeValue::S(from));
+            values.insert(":to".to_string(), AttributeValue::S(to));
+            "shorten_url = :s AND insight_time BETWEEN :from AND :to"
+        },
+        (Some(from), None) => {
+            values.insert(":from".to_string(), AttributeValue::S(from));
+            "shorten_url = :s AND insight_time >= :from"
+        },
+        (None, Some(to)) => {
+            values.insert(":to".to_string(), AttributeValue::S(to));
+            "shorten_url = :s AND insight_time <= :to"
+        },
+        (None, None) => "shorten_url = :s",
+    };
+
+    let filters = [
+        ("browser", &request.browser),
+        ("os", &request.os),
+        ("device_type", &request.device_type),
+        ("refferal_source", &request.referrer),
+        ("location", &request.location),
+    ];
+    // the attributes always go through names, location (and who knows what next) is a reserved word of dynamo db
+    let mut names = HashMap::new();
+    let filter = filters.iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(attribute, value)| {
+            names.insert(format!("#{}", attribute), attribute.to_string());
+            values.insert(format!(":{}", attribute), AttributeValue::S(value.to_string()));
+            format!("#{} = :{}", attribute, attribute)
+        })
+        .collect::<Vec<_>>();
+    // dynamo db rejects the names which are not used in the expressions
+    let (filter, names) = if filter.is_empty() { (None, None) } else { (Some(filter.join(" AND ")), Some(names)) };
+    Ok((condition.to_string(), filter, names, values))
+}
+
+// the latest insights first. a filtered query can read a whole page without a match, so it keeps reading
+// until the page is full or there is nothing left
+pub async fn get_insights(request : GetInsights, client: &DynamoClient) -> Result<KeyInsights, ErrorMessage>{
+    tracing::info!("Getting insights from Dynamo DB");
+    if request.page_size == 0 {
+        return Err(ErrorMessage::new("page size has to be at least 1".to_string(), 400))
+    }
+    let key = insight_key(&request.shorten_url, &normalise_domain(&request.domain));
+    let (condition, filter, names, values) = insights_query(&key, &request)?;
+
+    let mut start_key = match request.page_token.as_str() {
+        "" => None,
+        token => Some(HashMap::from([
+            ("shorten_url".to_string(), AttributeValue::S(key.clone())),
+            ("insight_time".to_string(), AttributeValue::S(parse_page_token(token)?)),
+        ])),
+    };
+    let mut insights = Vec::new();
+    loop {
+        let output = client.query()
+            .table_name(INSIGHTS_TABLE)
+            .key_condition_expression(&condition)
+            .set_filter_expression(filter.clone())
+            .set_expression_attribute_names(names.clone())
+            .set_expression_attribute_values(Some(values.clone()))
+            .scan_index_forward(false)
+            .limit((request.page_size as usize - insights.len()) as i32)
+            .set_exclusive_start_key(start_key)
+            .send().await
+            .map_err(|e| {
+                tracing::error!("Error getting insights from Dynamo DB was {:?}", e);
+                ErrorMessage::new("unable to get the insights".to_string(), 500)
+            })?;
+        insights.extend(output.items().iter().filter_map(to_insight));
+        // no last key when there are no more records down the line
+        start_key = output.last_evaluated_key().cloned();
+        if start_key.is_none() || insights.len() >= request.page_size as usize {
+            break
         }
     }
+
+    let next_page_token = start_key
+        .and_then(|last_key| last_key.get("insight_time").and_then(|v| v.as_s().ok()).map(|insight_time| page_token(insight_time)))
+        .unwrap_or_default();
+    Ok(KeyInsights {
+        list: insights,
+        shorten_url: request.shorten_url,
+        next_page_token,
+    })
 }
 
-pub async fn delete_insights(shorten_url : String) -> Result<String, ErrorMessage>{
-    // here we need to add the delete operation to the SQS
-    let config = aws_config::load_defaults(BehaviorVersion::v2025_01_17()).await;
-    let client = Client::new(&config);
-    tracing::info!("Sending message to SQS for DELETE_INSIGHT");
-    // Your FIFO queue URL (get this from AWS Console)
-    let queue_url = "https://sqs.ap-south-1.amazonaws.com/637423550786/snipsightmessages.fifo";
-    let delete_insight = DeleteInsight {message_type: "DELETE_INSIGHT".to_string(), shorten_url} ;
-    let message_body = to_string(&delete_insight).unwrap();
-    let resp = client.send_message()
-        .queue_url(queue_url).message_body(message_body).message_group_id("delete-event")
-        .message_deduplication_id(uuid::Uuid::new_v4().to_string()).send().await;
-    match resp {
-        Ok(resp) => {
-            Ok(resp.message_id.unwrap())
-        },
-        Err(err) => {
-            tracing::error!("Error sending message to SQS for Delete INSIGHT was {}", err);
-            Err(ErrorMessage::new(format!("Error sending message to SQS for Delete INSIGHT was {}", err),500))
+// the insight_time sort key is an RFC 3339 string, the response carries it as a Timestamp
+#[allow(deprecated)]
+fn to_insight(item: &HashMap<String, AttributeValue>) -> Option<Insight> {
+    let insight_time = item.get("insight_time")?.as_s().ok()?;
+    Some(Insight {
+        ip_address: item.get("ip_address")?.as_s().ok()?.to_string(),
+        refferal_source: item.get("refferal_source")?.as_s().ok()?.to_string(),
+        source: item.get("source").and_then(|source| source.as_s().ok()).cloned().unwrap_or_default(),
+        channel: item.get("channel").and_then(|channel| channel.as_s().ok()).cloned().unwrap_or_default(),
+        device_type: item.get("device_type")?.as_s().ok()?.to_string(),
+        browser: item.get("browser")?.as_s().ok()?.to_string(),
+        os: item.get("os")?.as_s().ok()?.to_string(),
+        location: item.get("location").and_then(|location| location.as_s().ok()).cloned().unwrap_or_default(),
+        insight_time: insight_time.parse().ok(),
+        legacy_insight_time: insight_time.to_string(),
+    })
+}
+
+// "2026-03-01T00:00:00" sorts before every insight_time of that day and after every one of the day before,
+// so the day starts work as the bounds of the sort key
+fn day_start(day: NaiveDate) -> AttributeValue {
+    AttributeValue::S(day.format("%Y-%m-%dT00:00:00").to_string())
+}
+
+// a page of the insights of the days from `from` upto (not including) `to`, oldest first, along with the key
+// the next page starts after (None on the last page)
+pub async fn insights_page(key: &str, from: NaiveDate, to: NaiveDate, start_key: Option<HashMap<String, AttributeValue>>, client: &DynamoClient)
+    -> Result<(Vec<Insight>, Option<HashMap<String, AttributeValue>>), String> {
+    let output = client.query()
+        .table_name(INSIGHTS_TABLE)
+        .key_condition_expression("shorten_url = :s AND insight_time BETWEEN :from AND :to")
+        .expression_attribute_values(":s", AttributeValue::S(key.to_string()))
+        .expression_attribute_values(":from", day_start(from))
+        .expression_attribute_values(":to", day_start(to))
+        .set_exclusive_start_key(start_key)
+        .send().await
+        .map_err(|e| format!("unable to query the insights of {}: {:?}", key, e))?;
+    Ok((output.items().iter().filter_map(to_insight).collect(), output.last_evaluated_key().cloned()))
+}
+
+// all the insights of the days from `from` upto (not including) `to`, oldest first
+pub async fn insights_between(key: &str, from: NaiveDate, to: NaiveDate, client: &DynamoClient) -> Result<Vec<Insight>, String> {
+    let mut insights = Vec::new();
+    let mut start_key = None;
+    loop {
+        let (page, last_key) = insights_page(key, from, to, start_key, client).await?;
+        insights.extend(page);
+        match last_key {
+            Some(last_key) => start_key = Some(last_key),
+            None => return Ok(insights)
         }
     }
-}
\ No newline at end of file
+}
+
+// deletes the raw insights of the days before `before`, returns how many were deleted
+pub async fn expire_insights_before(key: &str, before: NaiveDate, client: &DynamoClient) -> Result<usize, String> {
+    let mut expired = 0;
+    loop {
+        // a page at a time, so the keys of years of clicks are never held together
+        let output = client.query()
+            .table_name(INSIGHTS_TABLE)
+            .key_condition_expression("shorten_url = :s AND insight_time < :before")
+            .expression_attribute_values(":s", AttributeValue::S(key.to_string()))
+            .expression_attribute_values(":before", day_start(before))
+            .projection_expression("shorten_url, insight_time")
+            .send().await
+            .map_err(|e| format!("unable to query the expired insights of {}: {:?}", key, e))?;
+        if output.items().is_empty() {
+            return Ok(expired)
+        }
+        delete_insights(client, INSIGHTS_TABLE, output.items()).await?;
+        expired += output.items().len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> GetInsights {
+        GetInsights { page_size: 10, shorten_url: "launch".to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn page_tokens_round_trip() {
+        let token = page_token("2026-03-01T10:00:00.123456Z");
+        assert!(!token.contains("2026"));
+        assert_eq!(parse_page_token(&token).unwrap(), "2026-03-01T10:00:00.123456Z");
+        assert!(parse_page_token("not a token!").is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn insight_times_become_timestamps() {
+        let item = |insight_time: &str| HashMap::from([
+            ("insight_time".to_string(), AttributeValue::S(insight_time.to_string())),
+            ("ip_address".to_string(), AttributeValue::S("1.1.1.1".to_string())),
+            ("refferal_source".to_string(), AttributeValue::S("Direct".to_string())),
+            ("device_type".to_string(), AttributeValue::S("Other".to_string())),
+            ("browser".to_string(), AttributeValue::S("Firefox".to_string())),
+            ("os".to_string(), AttributeValue::S("Linux".to_string())),
+        ]);
+        let insight = to_insight(&item("2026-03-01T10:00:00.123456Z")).unwrap();
+        assert_eq!(insight.insight_time.map(|at| (at.seconds, at.nanos)), Some((1_772_359_200, 123_456_000)));
+        assert_eq!(insight.legacy_insight_time, "2026-03-01T10:00:00.123456Z");
+
+        let broken = to_insight(&item("yesterday")).unwrap();
+        assert_eq!((broken.insight_time, broken.legacy_insight_time.as_str()), (None, "yesterday"));
+    }
+
+    #[test]
+    fn range_and_filters_go_into_the_query() {
+        let (condition, filter, names, values) = insights_query("launch", &request()).unwrap();
+        assert_eq!((condition.as_str(), filter, names, values.len()), ("shorten_url = :s", None, None, 1));
+
+        let (condition, filter, names, values) = insights_query("launch", &GetInsights {
+            from: "2026-03-01T00:00:00Z".to_string(),
+            to: "2026-03-01T12:00:00+02:00".to_string(),
+            browser: "Firefox".to_string(),
+            referrer: "https://news.ycombinator.com/".to_string(),
+            location: "IN".to_string(),
+            ..request()
+        }).unwrap();
+        assert_eq!(condition, "shorten_url = :s AND insight_time BETWEEN :from AND :to");
+        assert_eq!(filter.unwrap(), "#browser = :browser AND #refferal_source = :refferal_source AND #location = :location");
+        assert_eq!(names.unwrap(), HashMap::from([
+            ("#browser".to_string(), "browser".to_string()),
+            ("#refferal_source".to_string(), "refferal_source".to_string()),
+            ("#location".to_string(), "location".to_string()),
+        ]));
+        assert_eq!(values[":to"], AttributeValue::S("2026-03-01T10:00:00.000000Z".to_string()));
+        assert_eq!(values[":location"], AttributeValue::S("IN".to_string()));
+
+        assert!(insights_query("launch", &GetInsights { from: "yesterday".to_string(), ..request() }).is_err());
+        assert!(insights_query("launch", &GetInsights { from: "2026-03-02T00:00:00Z".to_string(), to: "2026-03-01T00:00:00Z".to_string(), ..request() }).is_err());
+    }
+}
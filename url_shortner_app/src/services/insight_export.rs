@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use aws_sdk_dynamodb::Client as DynamoClient;
+use chrono::{Duration, NaiveDate};
+use proto_definations_snip_sight::generated::url_shortner::{ExportedInsight, Insight, InsightExportBatch, InsightExportRequest};
+use sqlx::{Pool, Postgres};
+use tokio::sync::mpsc;
+use tonic::Status;
+use crate::models::ErrorMessage;
+use crate::services::activation_windows::now;
+use crate::services::analytics::parse_range;
+use snipsight_events::insights::insight_key;
+use crate::services::dynamo_db_operations::insights_page;
+
+// the pages waiting for the client, a slow download holds the export instead of piling the pages up in memory
+const EXPORT_BUFFER_PAGES: usize = 4;
+
+pub type ExportSender = mpsc::Sender<Result<InsightExportBatch, Status>>;
+
+#[allow(deprecated)]
+fn exported(key: &str, insight: Insight) -> ExportedInsight {
+    ExportedInsight {
+        shorten_url: key.to_string(),
+        insight_time: insight.insight_time,
+        legacy_insight_time: insight.legacy_insight_time,
+        ip_address: insight.ip_address,
+        browser: insight.browser,
+        os: insight.os,
+        device_type: insight.device_type,
+        referrer: insight.refferal_source,
+        location: insight.location,
+    }
+}
+
+// the insight keys of the links which are exported, the link has to belong to the workspace
+async fn export_keys(request: &InsightExportRequest, db: &Pool<Postgres>) -> Result<Vec<String>, ErrorMessage> {
+    let links = if request.url_id == 0 {
+        sqlx::query_as::<_, (String, Option<String>)>("select w.shorten_url, d.domain from website_urls w \
+            LEFT JOIN custom_domains d ON d.id = w.domain_id where w.workspace_id=$1 ORDER BY w.id")
+            .bind(request.workspace_id).fetch_all(db).await
+    } else {
+        sqlx::query_as::<_, (String, Option<String>)>("select w.shorten_url, d.domain from website_urls w \
+            LEFT JOIN custom_domains d ON d.id = w.domain_id where w.id=$1 AND w.workspace_id=$2")
+            .bind(request.url_id).bind(request.workspace_id).fetch_all(db).await
+    }.map_err(|err| {
+        tracing::error!("unable to get the links to export : {}", err) ;
+        ErrorMessage::new(err.to_string(), 500)
+    })?;
+    if request.url_id != 0 && links.is_empty() {
+        return Err(ErrorMessage::new("Url not found".to_string(), 404))
+    }
+    Ok(links.into_iter().map(|(shorten_url, domain)| insight_key(&shorten_url, &domain.unwrap_or_default())).collect())
+}
+
+// sends the insights a DynamoDB page at a time, stops early when the client went away
+async fn send_pages(keys: Vec<String>, from: NaiveDate, to: NaiveDate, client: Arc<DynamoClient>, sender: ExportSender) {
+    for key in keys {
+        let mut start_key = None;
+        loop {
+            let (page, last_key) = match insights_page(&key, from, to, start_key, &client).await {
+                Ok(page) => page,
+                Err(err) => {
+                    tracing::error!("unable to export the insights of {} : {}", key, err) ;
+                    let _ = sender.send(Err(Status::internal("unable to read the insights"))).await;
+                    return
+                }
+            };
+            if !page.is_empty() {
+                let rows = page.into_iter().map(|insight| exported(&key, insight)).collect();
+                if sender.send(Ok(InsightExportBatch { rows })).await.is_err() {
+                    tracing::info!("the export was cancelled by the client") ;
+                    return
+                }
+            }
+            match last_key {
+                Some(last_key) => start_key = Some(last_key),
+                None => break
+            }
+        }
+    }
+}
+
+// the range and the links are checked before the stream starts, so those errors come back as the status of the rpc
+pub async fn export_insights(request: InsightExportRequest, client: Arc<DynamoClient>, db: &Pool<Postgres>) -> Result<mpsc::Receiver<Result<InsightExportBatch, Status>>, ErrorMessage> {
+    tracing::info!("export insights was called by the user {} of the workspace {} for the url {}", request.user_id, request.workspace_id, request.url_id) ;
+    let (from, to) = parse_range(&request.from, &request.to, now().date())?;
+    let keys = export_keys(&request, db).await?;
+    let (sender, receiver) = mpsc::channel(EXPORT_BUFFER_PAGES);
+    // `to` is inclusive, the query's upper bound is the start of the day after it
+    tokio::spawn(send_pages(keys, from, to + Duration::days(1), client, sender));
+    Ok(receiver)
+}
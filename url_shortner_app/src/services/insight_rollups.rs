@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use aws_sdk_dynamodb::Client as DynamoClient;
+use chrono::{Duration, NaiveDate};
+use sqlx::{Pool, Postgres};
+use sqlx::types::Json;
+use crate::models::RollupTarget;
+use crate::services::activation_windows::now;
+use crate::services::analytics::by_day;
+use snipsight_events::insights::insight_key;
+use crate::services::dynamo_db_operations::{expire_insights_before, insights_between};
+
+// the days of raw insights read in one go, so the first rollup of an old link doesn't hold all its clicks
+const ROLLUP_WINDOW_DAYS: i64 = 31;
+const ROLLUP_BATCH_SIZE: i64 = 200;
+// the insights come through the queue, one can land on a day after it was rolled up. the last days are rolled up
+// again on every run and their raw insights are kept at least this long, so the late ones are still counted
+const LATE_INSIGHT_DAYS: i64 = 3;
+const FREE_PLAN: &str = "free";
+const DEFAULT_RETENTION: &str = "free=30,pro=365,business=1095";
+
+// how many days the raw insights are kept for each plan, the rollups are kept as long as the link
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetentionPolicy {
+    days_by_plan: HashMap<String, i64>,
+}
+
+impl RetentionPolicy {
+    // "free=30,pro=365", the plans which are not listed get the free plan's days
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut days_by_plan = HashMap::new();
+        for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (plan, days) = entry.split_once('=').ok_or(format!("{} is not plan=days", entry))?;
+            let days = days.trim().parse::<i64>().ok().filter(|days| *days > 0)
+                .ok_or(format!("the retention of {} must be a positive number of days", plan))?;
+            days_by_plan.insert(plan.trim().to_lowercase(), days);
+        }
+        if !days_by_plan.contains_key(FREE_PLAN) {
+            return Err("the retention of the free plan is needed".to_string())
+        }
+        Ok(Self { days_by_plan })
+    }
+
+    pub fn from_env() -> Self {
+        let value = std::env::var("INSIGHT_RETENTION_DAYS").unwrap_or(DEFAULT_RETENTION.to_string());
+        Self::parse(&value).unwrap_or_else(|err| {
+            tracing::error!("INSIGHT_RETENTION_DAYS was invalid, using {} : {}", DEFAULT_RETENTION, err) ;
+            Self::parse(DEFAULT_RETENTION).unwrap()
+        })
+    }
+
+    pub fn days(&self, plan: &str) -> i64 {
+        self.days_by_plan.get(&plan.to_lowercase()).or(self.days_by_plan.get(FREE_PLAN)).copied().unwrap()
+    }
+}
+
+// the first day rolled up by this run, the days not rolled up yet and the last LATE_INSIGHT_DAYS complete days
+fn rollup_start(target: &RollupTarget, today: NaiveDate) -> NaiveDate {
+    let created = target.created_at.date();
+    match target.rolled_up_until {
+        Some(day) => (day + Duration::days(1)).min(today - Duration::days(LATE_INSIGHT_DAYS)).max(created),
+        None => created
+    }
+}
+
+// rolls up the complete days which were not rolled up yet along with the last few ones, then expires the raw insights older
+// than the plan's retention. a day is only expired after it was rolled up, the rollup of a window and rolled_up_until are
+// written together. a day rolled up again is replaced as a whole, so the late insights are added without counting twice
+pub async fn rollup_link(target: &RollupTarget, today: NaiveDate, policy: &RetentionPolicy, client: &DynamoClient, db: &Pool<Postgres>) -> Result<usize, String> {
+    let key = insight_key(&target.shorten_url, &target.domain.clone().unwrap_or_default());
+    let mut from = rollup_start(target, today);
+    while from < today {
+        let to = (from + Duration::days(ROLLUP_WINDOW_DAYS)).min(today);
+        let days = by_day(insights_between(&key, from, to, client).await?);
+
+        let mut transaction = db.begin().await.map_err(|err| err.to_string())?;
+        for (day, aggregate) in days {
+            let aggregate = aggregate.truncated();
+            sqlx::query("insert into insight_rollups (url_id, day, clicks, unique_visitors, browsers, os, devices, referrers, locations, channels) \
+                values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) ON CONFLICT (url_id, day) DO UPDATE SET clicks=$3, unique_visitors=$4, \
+                browsers=$5, os=$6, devices=$7, referrers=$8, locations=$9, channels=$10")
+                .bind(target.id).bind(day).bind(aggregate.clicks as i32).bind(aggregate.unique_visitors as i32)
+                .bind(Json(aggregate.browsers)).bind(Json(aggregate.os)).bind(Json(aggregate.devices))
+                .bind(Json(aggregate.referrers)).bind(Json(aggregate.locations)).bind(Json(aggregate.channels))
+                .execute(&mut *transaction).await.map_err(|err| err.to_string())?;
+        }
+        sqlx::query("update website_urls SET rolled_up_until=$1 where id=$2")
+            .bind(to - Duration::days(1)).bind(target.id).execute(&mut *transaction).await.map_err(|err| err.to_string())?;
+        transaction.commit().await.map_err(|err| err.to_string())?;
+        from = to;
+    }
+
+    // nothing can be older than the link itself, the days which are rolled up again keep their raw insights
+    let cutoff = (today - Duration::days(policy.days(&target.plan).max(LATE_INSIGHT_DAYS))).min(from);
+    if cutoff <= target.created_at.date() {
+        return Ok(0)
+    }
+    expire_insights_before(&key, cutoff, client).await
+}
+
+// goes through every link once, returns how many links were rolled up and how many raw insights were expired
+pub async fn rollup_all_links(policy: &RetentionPolicy, client: &DynamoClient, db: &Pool<Postgres>) -> Result<(u64, usize), String> {
+    let today = now().date();
+    let (mut links, mut expired) = (0, 0);
+    let mut last_id = 0;
+    loop {
+        let targets = sqlx::query_as::<_, RollupTarget>("select w.id, w.shorten_url, d.domain, w.created_at, w.rolled_up_until, \
+            COALESCE(p.plan, 'free') AS plan from website_urls w LEFT JOIN custom_domains d ON d.id = w.domain_id \
+            LEFT JOIN user_plans p ON p.user_id = w.user_id where w.id > $1 ORDER BY w.id LIMIT $2")
+            .bind(last_id).bind(ROLLUP_BATCH_SIZE).fetch_all(db).await
+            .map_err(|err| err.to_string())?;
+        let Some(last) = targets.last() else { break };
+        last_id = last.id;
+
+        for target in targets {
+            match rollup_link(&target, today, policy, client, db).await {
+                Ok(count) => {
+                    links += 1;
+                    expired += count;
+                },
+                // the next run starts from the last window which was written
+                Err(err) => tracing::error!("unable to roll up the insights of {} : {}", target.shorten_url, err)
+            }
+        }
+    }
+    Ok((links, expired))
+}
+
+pub async fn run_insight_rollups(policy: RetentionPolicy, client: Arc<DynamoClient>, db: Arc<Pool<Postgres>>, every: std::time::Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        match rollup_all_links(&policy, &client, &db).await {
+            Ok((links, expired)) => tracing::info!("rolled up the insights of {} links, {} raw insights were expired", links, expired),
+            Err(err) => tracing::error!("unable to run the insight rollups {}", err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retention_comes_from_the_plan() {
+        let policy = RetentionPolicy::parse("free=30, Pro=365").unwrap();
+        assert_eq!((policy.days("free"), policy.days("pro"), policy.days("enterprise")), (30, 365, 30));
+        assert_eq!(RetentionPolicy::parse(DEFAULT_RETENTION).unwrap().days("business"), 1095);
+        assert!(RetentionPolicy::parse("pro=365").is_err());
+        assert!(RetentionPolicy::parse("free=0").is_err());
+        assert!(RetentionPolicy::parse("free").is_err());
+    }
+
+    #[test]
+    fn the_last_days_are_rolled_up_again() {
+        let day = |value: &str| value.parse::<NaiveDate>().unwrap();
+        let target = |created: &str, rolled_up_until: Option<&str>| RollupTarget {
+            created_at: day(created).and_hms_opt(10, 0, 0).unwrap(),
+            rolled_up_until: rolled_up_until.map(day),
+            ..Default::default()
+        };
+        let today = day("2026-03-10");
+        assert_eq!(rollup_start(&target("2026-03-01", None), today), day("2026-03-01"));
+        // rolled up till yesterday, the 3 days before today are read again
+        assert_eq!(rollup_start(&target("2026-03-01", Some("2026-03-09")), today), day("2026-03-07"));
+        assert_eq!(rollup_start(&target("2026-03-08", Some("2026-03-09")), today), day("2026-03-08"));
+        // a link which wasn't rolled up for a while starts from where it was left
+        assert_eq!(rollup_start(&target("2026-01-01", Some("2026-02-01")), today), day("2026-02-02"));
+    }
+}
@@ -0,0 +1,172 @@
+use proto_definations_snip_sight::generated::url_shortner::{CampaignInsights, Folder, Tag, UrlFolder, UrlTags, Urls};
+use sqlx::{Error, Pool, Postgres};
+use crate::models::{ErrorMessage, FolderModel, TagModel, UrlModel};
+use crate::services::shorten_url_write::URL_COLUMNS;
+
+const MAX_TAG_LENGTH: usize = 50;
+const MAX_FOLDER_NAME_LENGTH: usize = 100;
+const MAX_TAGS_PER_LINK: usize = 20;
+// links shown with the campaign insights
+const TOP_CAMPAIGN_LINKS: i64 = 10;
+
+// the folders along with the number of links in them and the clicks of those links
+const FOLDER_COLUMNS: &str = "f.id, f.user_id, f.workspace_id, f.name, COUNT(w.id)::INT AS link_count, COALESCE(SUM(w.view_count), 0)::BIGINT AS total_clicks, f.created_at";
+
+// tags are lowercased and the inner spaces are collapsed, so "Summer  Sale" and "summer sale" are the same tag
+pub fn normalise_tag(name: &str) -> Result<String, ErrorMessage> {
+    let tag = name.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    if tag.is_empty() || tag.chars().count() > MAX_TAG_LENGTH {
+        return Err(ErrorMessage::new(format!("tag must be 1 to {} characters long", MAX_TAG_LENGTH), 400))
+    }
+    if !tag.chars().all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_') {
+        return Err(ErrorMessage::new("tag can only have letters, numbers, spaces, - and _".to_string(), 400))
+    }
+    Ok(tag)
+}
+
+fn normalise_tags(names: &[String]) -> Result<Vec<String>, ErrorMessage> {
+    let mut tags = Vec::new();
+    for name in names {
+        let tag = normalise_tag(name)?;
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    if tags.len() > MAX_TAGS_PER_LINK {
+        return Err(ErrorMessage::new(format!("a link can have at most {} tags", MAX_TAGS_PER_LINK), 400))
+    }
+    Ok(tags)
+}
+
+fn normalise_folder_name(name: &str) -> Result<String, ErrorMessage> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_FOLDER_NAME_LENGTH {
+        return Err(ErrorMessage::new(format!("folder name must be 1 to {} characters long", MAX_FOLDER_NAME_LENGTH), 400))
+    }
+    Ok(name.to_string())
+}
+
+fn database_error(err: Error) -> ErrorMessage {
+    tracing::error!("database error was {}", err) ;
+    ErrorMessage::new(String::from("An unexpected database error occurred"), 500)
+}
+
+pub async fn get_tags(workspace_id: i32, db: &Pool<Postgres>) -> Result<Vec<Tag>, ErrorMessage> {
+    tracing::info!("get tags was called for the workspace {}", workspace_id) ;
+    let tags = sqlx::query_as::<_, TagModel>("select t.id, t.user_id, t.workspace_id, t.name, COUNT(ut.url_id)::INT AS link_count from tags t \
+        LEFT JOIN url_tags ut ON ut.tag_id = t.id where t.workspace_id = $1 GROUP BY t.id ORDER BY t.name")
+        .bind(workspace_id).fetch_all(db).await.map_err(database_error)?;
+    Ok(tags.into_iter().map(Tag::from).collect())
+}
+
+pub async fn delete_tag(id: i32, workspace_id: i32, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+    tracing::info!("delete tag was called with the id {}", id) ;
+    // url_tags rows go along with it through the cascade
+    let result = sqlx::query("DELETE FROM tags where id=$1 AND workspace_id=$2")
+        .bind(id).bind(workspace_id).execute(db).await.map_err(database_error)?;
+    if result.rows_affected() == 0 {
+        return Err(ErrorMessage::new("Tag doesn't exists".to_string(), 404))
+    }
+    Ok(true)
+}
+
+// replaces all the tags of the link, the tags the workspace didn't have yet are created
+pub async fn set_url_tags(request: UrlTags, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+    tracing::info!("set url tags was called with the id {}", request.id) ;
+    let tags = normalise_tags(&request.tags)?;
+
+    let mut transaction = db.begin().await.map_err(database_error)?;
+    let url = sqlx::query_as::<_, (i32,)>("select id from website_urls where id=$1 AND workspace_id=$2 FOR UPDATE")
+        .bind(request.id).bind(request.workspace_id).fetch_optional(&mut *transaction).await.map_err(database_error)?;
+    if url.is_none() {
+        return Err(ErrorMessage::new("Shorten url doesn't exists".to_string(), 404))
+    }
+    sqlx::query("insert into tags (user_id, workspace_id, name) select $1, $2, UNNEST($3::TEXT[]) ON CONFLICT (workspace_id, name) DO NOTHING")
+        .bind(request.user_id).bind(request.workspace_id).bind(&tags).execute(&mut *transaction).await.map_err(database_error)?;
+    sqlx::query("DELETE FROM url_tags where url_id=$1")
+        .bind(request.id).execute(&mut *transaction).await.map_err(database_error)?;
+    sqlx::query("insert into url_tags (url_id, tag_id) select $1, id from tags where workspace_id=$2 AND name = ANY($3)")
+        .bind(request.id).bind(request.workspace_id).bind(&tags).execute(&mut *transaction).await.map_err(database_error)?;
+    transaction.commit().await.map_err(database_error)?;
+    Ok(true)
+}
+
+pub async fn create_folder(request: Folder, db: &Pool<Postgres>) -> Result<Folder, ErrorMessage> {
+    let name = normalise_folder_name(&request.name)?;
+    tracing::info!("create folder was called with the name {} for the workspace {}", name, request.workspace_id) ;
+    let result = sqlx::query_as::<_, FolderModel>("insert into folders (user_id, workspace_id, name) values ($1, $2, $3) \
+        RETURNING id, user_id, workspace_id, name, 0 AS link_count, 0::BIGINT AS total_clicks, created_at")
+        .bind(request.user_id).bind(request.workspace_id).bind(name).fetch_one(db).await ;
+    match result {
+        Ok(result) => Ok(Folder::from(result)),
+        Err(Error::Database(error)) if error.constraint() == Some("unique_workspace_folder") => {
+            tracing::warn!("The folder name was already used in the workspace") ;
+            Err(ErrorMessage::new("Folder already exists".to_string(), 409))
+        },
+        Err(err) => Err(database_error(err))
+    }
+}
+
+pub async fn get_folders(workspace_id: i32, db: &Pool<Postgres>) -> Result<Vec<Folder>, ErrorMessage> {
+    tracing::info!("get folders was called for the workspace {}", workspace_id) ;
+    let folders = sqlx::query_as::<_, FolderModel>(&format!("select {} from folders f LEFT JOIN website_urls w ON w.folder_id = f.id \
+        where f.workspace_id = $1 GROUP BY f.id ORDER BY f.name", FOLDER_COLUMNS))
+        .bind(workspace_id).fetch_all(db).await.map_err(database_error)?;
+    Ok(folders.into_iter().map(Folder::from).collect())
+}
+
+pub async fn delete_folder(id: i32, workspace_id: i32, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+    tracing::info!("delete folder was called with the id {}", id) ;
+    // the links stay, only their folder_id is cleared
+    let result = sqlx::query("DELETE FROM folders where id=$1 AND workspace_id=$2")
+        .bind(id).bind(workspace_id).execute(db).await.map_err(database_error)?;
+    if result.rows_affected() == 0 {
+        return Err(ErrorMessage::new("Folder doesn't exists".to_string(), 404))
+    }
+    Ok(true)
+}
+
+pub async fn set_url_folder(request: UrlFolder, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+    tracing::info!("set url folder was called with the id {} and folder {}", request.id, request.folder_id) ;
+    let folder_id = if request.folder_id == 0 { None } else { Some(request.folder_id) };
+    // both the link and the folder have to belong to the workspace
+    let result = sqlx::query("update website_urls SET folder_id=$1 where id=$2 AND workspace_id=$3 \
+        AND ($1::INT IS NULL OR EXISTS (select 1 from folders where id=$1 AND workspace_id=$3))")
+        .bind(folder_id).bind(request.id).bind(request.workspace_id).execute(db).await.map_err(database_error)?;
+    if result.rows_affected() == 0 {
+        return Err(ErrorMessage::new("Shorten url or folder doesn't exists".to_string(), 404))
+    }
+    Ok(true)
+}
+
+// clicks of the whole campaign along with its best performing links
+pub async fn get_campaign_insights(id: i32, workspace_id: i32, db: &Pool<Postgres>) -> Result<CampaignInsights, ErrorMessage> {
+    tracing::info!("get campaign insights was called with the folder {}", id) ;
+    let folder = sqlx::query_as::<_, FolderModel>(&format!("select {} from folders f LEFT JOIN website_urls w ON w.folder_id = f.id \
+        where f.id = $1 AND f.workspace_id = $2 GROUP BY f.id", FOLDER_COLUMNS))
+        .bind(id).bind(workspace_id).fetch_optional(db).await.map_err(database_error)?;
+    let Some(folder) = folder else {
+        return Err(ErrorMessage::new("Folder doesn't exists".to_string(), 404))
+    };
+    let top_links = sqlx::query_as::<_, UrlModel>(&format!("select {} from website_urls w LEFT JOIN custom_domains d ON d.id = w.domain_id \
+        where w.folder_id = $1 ORDER BY w.view_count DESC LIMIT $2", URL_COLUMNS))
+        .bind(id).bind(TOP_CAMPAIGN_LINKS).fetch_all(db).await.map_err(database_error)?;
+    Ok(CampaignInsights {
+        folder: Some(Folder::from(folder)),
+        top_links: top_links.into_iter().map(Urls::from).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_normalised_and_deduplicated() {
+        let tags = normalise_tags(&["Summer  Sale".to_string(), " summer sale".to_string(), "launch_2026".to_string()]).unwrap();
+        assert_eq!(tags, vec!["summer sale".to_string(), "launch_2026".to_string()]);
+        assert!(normalise_tag("   ").is_err());
+        assert!(normalise_tag("drop;table").is_err());
+        assert!(normalise_tags(&(0..21).map(|i| format!("tag{}", i)).collect::<Vec<_>>()).is_err());
+    }
+}
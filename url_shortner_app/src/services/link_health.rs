@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use proto_definations_snip_sight::generated::url_shortner::LinkHealth;
+use reqwest::{Method, StatusCode, Url};
+use sqlx::{Error, Pool, Postgres};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+use crate::models::{ErrorMessage, HealthCheckTarget, LinkHealthModel};
+use crate::services::outbox::enqueue;
+use crate::services::url_safety::{screened_redirects, PublicResolver};
+use snipsight_events::events::{BrokenLink, SnipSightEvent};
+
+// a link is reported once it fails these many checks in a row
+const ALERT_AFTER_FAILURES: i32 = 3;
+// links are picked up in batches of these many rows
+const CHECK_BATCH_SIZE: i64 = 200;
+// different hosts are checked in parallel, upto these many at a time
+const MAX_CONCURRENT_CHECKS: usize = 16;
+// the error column is a VARCHAR(200)
+const MAX_ERROR_LENGTH: usize = 200;
+// the hosts the limiter remembers before it drops the ones whose slot has passed
+const MAX_TRACKED_HOSTS: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeResult {
+    pub status: Option<u16>, // None when the destination didn't respond
+    pub latency_ms: i32,
+    pub error: Option<String>,
+}
+
+impl ProbeResult {
+    pub fn is_broken(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+// the http client is injected, so tests can point it to a local mock server
+#[tonic::async_trait]
+pub trait HealthProbe: Send + Sync + std::fmt::Debug {
+    async fn probe(&self, url: &str) -> ProbeResult;
+}
+
+#[derive(Debug)]
+pub struct HttpHealthProbe {
+    client: reqwest::Client,
+}
+
+impl HttpHealthProbe {
+    // the destinations are the users' and the result goes back to them, so every redirect is screened and the names
+    // are resolved to public addresses only, otherwise a link could be used to look around our network
+    pub fn new(timeout: Duration, max_redirects: usize) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(screened_redirects(max_redirects))
+            .dns_resolver(Arc::new(PublicResolver))
+            .user_agent("SnipSightBot/1.0 (+https://web.snipsight.phani.services)")
+            .build()
+            .expect("unable to build the http client");
+        Self { client }
+    }
+
+    async fn send(&self, method: Method, url: &str) -> Result<StatusCode, String> {
+        // only the status is needed, the body is never read
+        match self.client.request(method, url).send().await {
+            Ok(response) => Ok(response.status()),
+            Err(err) if err.is_timeout() => Err("destination timed out".to_string()),
+            // too many redirects, or a redirect to a destination which wasn't allowed
+            Err(err) if err.is_redirect() => Err(std::error::Error::source(&err).map_or("too many redirects".to_string(), |reason| reason.to_string())),
+            Err(err) if err.is_connect() => Err(format!("unable to connect: {}", err)),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl HealthProbe for HttpHealthProbe {
+    async fn probe(&self, url: &str) -> ProbeResult {
+        let started = Instant::now();
+        // HEAD is cheaper, but plenty of servers don't allow it so falling back to GET
+        let result = match self.send(Method::HEAD, url).await {
+            Ok(status) if status == StatusCode::METHOD_NOT_ALLOWED || status == StatusCode::NOT_IMPLEMENTED => self.send(Method::GET, url).await,
+            other => other,
+        };
+        let latency_ms = started.elapsed().as_millis().min(i32::MAX as u128) as i32;
+        match result {
+            Ok(status) if status.is_client_error() || status.is_server_error() => ProbeResult {
+                status: Some(status.as_u16()),
+                latency_ms,
+                error: Some(format!("destination responded with {}", status)),
+            },
+            Ok(status) => ProbeResult { status: Some(status.as_u16()), latency_ms, error: None },
+            Err(error) => ProbeResult { status: None, latency_ms, error: Some(error) },
+        }
+    }
+}
+
+// keeps a gap between two requests to the same host, so we never hammer a destination which has many links
+#[derive(Debug)]
+pub struct HostRateLimiter {
+    gap: Duration,
+    next_slots: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostRateLimiter {
+    pub fn new(gap: Duration) -> Self {
+        Self { gap, next_slots: Mutex::new(HashMap::new()) }
+    }
+
+    pub async fn wait(&self, host: &str) {
+        let slot = {
+            let mut next_slots = self.next_slots.lock().unwrap();
+            let now = Instant::now();
+            // a host whose slot has passed is the same as one which was never seen, so only those are dropped
+            if next_slots.len() >= MAX_TRACKED_HOSTS {
+                next_slots.retain(|_, next| *next > now);
+            }
+            let slot = next_slots.get(host).map_or(now, |next| (*next).max(now));
+            next_slots.insert(host.to_string(), slot + self.gap);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+fn truncate_error(error: String) -> String {
+    match error.char_indices().nth(MAX_ERROR_LENGTH) {
+        Some((index, _)) => error[..index].to_string(),
+        None => error,
+    }
+}
+
+fn host_of(original_url: &str) -> String {
+    Url::parse(original_url).ok()
+        .and_then(|url| url.host_str().map(|host| host.to_lowercase()))
+        .unwrap_or_default()
+}
+
+async fn check_link(target: HealthCheckTarget, probe: Arc<dyn HealthProbe>, db: Arc<Pool<Postgres>>) -> Result<bool, ErrorMessage> {
+    let result = probe.probe(&target.original_url).await;
+    let failure_streak = if result.is_broken() { target.health_failure_streak + 1 } else { 0 };
+    let error = result.error.clone().map(truncate_error);
+
+    // the health and the alert are written together, the alert goes out through the outbox relay
+    let mut transaction = db.begin().await.map_err(|err| ErrorMessage::new(err.to_string(), 500))?;
+    sqlx::query("update website_urls SET health_status=$1, health_latency_ms=$2, health_failure_streak=$3, health_error=$4, health_checked_at=NOW() where id=$5")
+        .bind(result.status.map(i32::from)).bind(result.latency_ms).bind(failure_streak).bind(&error).bind(target.id)
+        .execute(&mut *transaction).await
+        .map_err(|err| ErrorMessage::new(err.to_string(), 500))?;
+
+    // only reported when it crosses the threshold, not on every failed check after that
+    if failure_streak == ALERT_AFTER_FAILURES {
+        tracing::warn!("the destination of {} was broken for {} checks : {:?}", target.shorten_url, failure_streak, error) ;
+        let event = BrokenLink {
+            url_id: target.id,
+            user_id: target.user_id,
+            shorten_url: target.shorten_url,
+            original_url: target.original_url,
+            status: result.status.map(i32::from).unwrap_or_default(),
+            error: error.unwrap_or_default(),
+            failure_streak,
+        };
+        enqueue(SnipSightEvent::BrokenLink(event), &mut transaction).await?;
+    }
+    transaction.commit().await.map_err(|err| ErrorMessage::new(err.to_string(), 500))?;
+    Ok(result.is_broken())
+}
+
+// checks every active link once, returns how many were checked and how many of them were broken
+pub async fn check_all_links(probe: Arc<dyn HealthProbe>, limiter: Arc<HostRateLimiter>, db: Arc<Pool<Postgres>>) -> Result<(u64, u64), ErrorMessage> {
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_CHECKS));
+    let (mut checked, mut broken) = (0, 0);
+    let mut last_id = 0;
+    loop {
+        let targets = sqlx::query_as::<_, HealthCheckTarget>("select id, user_id, original_url, shorten_url, health_failure_streak from website_urls \
+            where id > $1 AND disabled_reason IS NULL ORDER BY id LIMIT $2")
+            .bind(last_id).bind(CHECK_BATCH_SIZE).fetch_all(db.as_ref()).await
+            .map_err(|err| ErrorMessage::new(err.to_string(), 500))?;
+        let Some(last) = targets.last() else { break };
+        last_id = last.id;
+
+        let mut checks = JoinSet::new();
+        for target in targets {
+            let (probe, limiter, db, permits) = (probe.clone(), limiter.clone(), db.clone(), permits.clone());
+            checks.spawn(async move {
+                // waiting for the host's slot first, so a slow host doesn't hold the permits of the others
+                limiter.wait(&host_of(&target.original_url)).await;
+                let _permit = permits.acquire_owned().await.unwrap();
+                check_link(target, probe, db).await
+            });
+        }
+        while let Some(result) = checks.join_next().await {
+            match result {
+                Ok(Ok(is_broken)) => {
+                    checked += 1;
+                    if is_broken {
+                        broken += 1;
+                    }
+                },
+                Ok(Err(err)) => tracing::error!("unable to store the link health {:?}", err),
+                Err(err) => tracing::error!("link health check panicked {}", err),
+            }
+        }
+    }
+    Ok((checked, broken))
+}
+
+pub async fn run_link_health_checker(probe: Arc<dyn HealthProbe>, limiter: Arc<HostRateLimiter>, db: Arc<Pool<Postgres>>, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        match check_all_links(probe.clone(), limiter.clone(), db.clone()).await {
+            Ok((checked, broken)) => tracing::info!("health checked {} links, {} of them were broken", checked, broken),
+            Err(err) => tracing::error!("unable to run the link health checks {:?}", err)
+        }
+    }
+}
+
+pub async fn get_link_health(id: i32, workspace_id: i32, db: &Pool<Postgres>) -> Result<LinkHealth, ErrorMessage> {
+    tracing::info!("get link health was called with the id {}", id) ;
+    let result = sqlx::query_as::<_, LinkHealthModel>("select id, health_status, health_latency_ms, health_failure_streak, health_checked_at, health_error from website_urls where id=$1 AND workspace_id=$2")
+        .bind(id).bind(workspace_id).fetch_one(db).await ;
+    match result {
+        Ok(result) => Ok(LinkHealth::from(result)),
+        Err(Error::RowNotFound) => {
+            tracing::warn!("NO Row Found for the link health") ;
+            Err(ErrorMessage::new("Shorten url doesn't exists".to_string(), 404))
+        },
+        Err(err) => {
+            tracing::error!("error while getting the link health was {}", err) ;
+            Err(ErrorMessage::new(err.to_string(), 500))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    // a tiny mock destination, HEAD is not allowed on /no-head, /missing is a 404 and /internal redirects to itself,
+    // which is a private address
+    async fn mock_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut buffer = [0u8; 1024];
+                    let read = socket.read(&mut buffer).await.unwrap();
+                    let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                    let status = match request.split_whitespace().take(2).collect::<Vec<_>>()[..] {
+                        ["HEAD", "/no-head"] => "405 Method Not Allowed\r\nallow: GET",
+                        [_, "/missing"] => "404 Not Found",
+                        [_, "/internal"] => "302 Found\r\nlocation: /internal",
+                        _ => "200 OK",
+                    };
+                    let response = format!("HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn probe_reports_status_and_falls_back_to_get() {
+        let server = mock_server().await;
+        let probe = HttpHealthProbe::new(Duration::from_secs(2), 3);
+
+        let healthy = probe.probe(&format!("{}/no-head", server)).await;
+        assert_eq!((healthy.status, healthy.is_broken()), (Some(200), false));
+
+        let missing = probe.probe(&format!("{}/missing", server)).await;
+        assert_eq!((missing.status, missing.is_broken()), (Some(404), true));
+
+    }
+
+    #[tokio::test]
+    async fn probe_doesnt_follow_redirects_in_to_private_addresses() {
+        let server = mock_server().await;
+        let internal = HttpHealthProbe::new(Duration::from_secs(2), 3).probe(&format!("{}/internal", server)).await;
+        assert_eq!((internal.status, internal.error.as_deref()), (None, Some("private destinations are not allowed")));
+    }
+
+    #[tokio::test]
+    async fn probe_reports_unreachable_destinations() {
+        // binding and dropping, so nothing listens on the port anymore
+        let address = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+        let result = HttpHealthProbe::new(Duration::from_secs(2), 3).probe(&format!("http://{}/", address)).await;
+        assert_eq!(result.status, None);
+        assert!(result.is_broken());
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_spaces_requests_per_host() {
+        let limiter = HostRateLimiter::new(Duration::from_millis(100));
+        let started = Instant::now();
+        limiter.wait("example.com").await;
+        limiter.wait("other.example").await;
+        assert!(started.elapsed() < Duration::from_millis(100));
+        limiter.wait("example.com").await;
+        limiter.wait("example.com").await;
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_forgets_the_hosts_whose_slot_has_passed() {
+        let limiter = HostRateLimiter::new(Duration::from_millis(100));
+        limiter.wait("example.com").await;
+        {
+            // the other hosts were checked long ago
+            let mut next_slots = limiter.next_slots.lock().unwrap();
+            for index in 1..MAX_TRACKED_HOSTS {
+                next_slots.insert(format!("host-{}.example", index), Instant::now());
+            }
+        }
+        limiter.wait("other.example").await;
+        let next_slots = limiter.next_slots.lock().unwrap();
+        assert_eq!(next_slots.len(), 2);
+        assert!(next_slots.contains_key("example.com"));
+    }
+}
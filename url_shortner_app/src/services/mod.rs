@@ -1,3 +1,17 @@
 pub mod shorten_url_write;
 pub mod dynamo_db_operations;
-mod analytics;
\ No newline at end of file
+pub mod social_preview;
+pub mod custom_domains;
+pub mod name_blocklist;
+pub mod url_safety;
+pub mod link_health;
+pub mod activation_windows;
+pub mod link_groups;
+pub mod outbox;
+pub mod analytics;
+pub mod insight_rollups;
+pub mod insight_export;
+pub mod webhooks;
+pub mod digests;
+pub mod bio_pages;
+pub mod workspaces;
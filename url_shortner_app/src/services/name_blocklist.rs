@@ -0,0 +1,42 @@
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use proto_definations_snip_sight::generated::url_shortner::BlockedName;
+use proto_definations_snip_sight::name_blocklist::NameBlocklist;
+use sqlx::{Pool, Postgres};
+use crate::models::{BlockedNameModel, ErrorMessage};
+
+// the blocked_names table, read again every minute instead of on every custom name
+pub type SharedBlocklist = Arc<RwLock<Arc<NameBlocklist>>>;
+
+pub async fn get_blocked_names(db: &Pool<Postgres>) -> Result<Vec<BlockedName>, ErrorMessage> {
+    let result = sqlx::query_as::<_, BlockedNameModel>("select id, name, is_pattern, reason from blocked_names")
+        .fetch_all(db).await ;
+    match result {
+        Ok(names) => Ok(names.into_iter().map(BlockedName::from).collect()),
+        Err(err) => {
+            tracing::error!("error while getting the blocked names was {}", err) ;
+            Err(ErrorMessage::new("Internal Server Error".to_string(), 500))
+        }
+    }
+}
+
+// the rows added by the admins apply within a minute, a failed read keeps the last list
+pub async fn refresh_blocked_names(blocklist: SharedBlocklist, db: Arc<Pool<Postgres>>, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        if let Ok(names) = get_blocked_names(&db).await {
+            *blocklist.write().unwrap() = Arc::new(NameBlocklist::new(names));
+        }
+    }
+}
+
+pub fn check_custom_name(name: &str, blocklist: &NameBlocklist) -> Result<(), ErrorMessage> {
+    match blocklist.blocked_reason(name) {
+        Some(reason) => {
+            tracing::warn!("the custom name {} was blocked, reason {}", name, reason) ;
+            Err(ErrorMessage::new("custom name is not allowed".to_string(), 400))
+        },
+        None => Ok(())
+    }
+}
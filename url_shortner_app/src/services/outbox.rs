@@ -0,0 +1,160 @@
+use std::sync::Arc;
+use std::time::Duration;
+use sqlx::{PgConnection, Pool, Postgres};
+use snipsight_events::events::{Envelope, SnipSightEvent};
+use snipsight_events::publisher::{EventPublisher, OutgoingMessage};
+use crate::models::{ErrorMessage, OutboxMessage};
+
+// the rows picked up by the relay in one go
+const RELAY_BATCH_SIZE: i64 = 50;
+// the retries start at 5 seconds and double upto 15 minutes
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(5);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(15 * 60);
+// the claimed rows are picked up again after this, when the instance relaying them went down
+const RELAY_LEASE: Duration = Duration::from_secs(5 * 60);
+
+// the type, the FIFO group and the body of the event's row
+fn outbox_row(event: SnipSightEvent) -> (&'static str, &'static str, String) {
+    let (message_type, group_id) = (event.message_type(), event.group_id());
+    (message_type, group_id, Envelope::new(event).to_json())
+}
+
+// writes the event with the caller's transaction, it is only published when that transaction commits
+pub async fn enqueue(event: SnipSightEvent, connection: &mut PgConnection) -> Result<(), ErrorMessage> {
+    let (message_type, group_id, body) = outbox_row(event);
+    sqlx::query("insert into outbox (message_type, message_group_id, body) values ($1, $2, $3)")
+        .bind(message_type).bind(group_id).bind(body)
+        .execute(connection).await
+        .map_err(|err| {
+            tracing::error!("error while writing the {} event to the outbox was {}", message_type, err) ;
+            ErrorMessage::new(String::from("An unexpected database error occurred"), 500)
+        })?;
+    Ok(())
+}
+
+pub fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.clamp(0, 16) as u32;
+    FIRST_RETRY_DELAY.saturating_mul(2u32.pow(exponent)).min(MAX_RETRY_DELAY)
+}
+
+// what became of a claimed row
+#[derive(Debug, PartialEq)]
+enum Relayed {
+    Sent,
+    Retry { delay: Duration, error: String },
+}
+
+// publishes the rows together, the result of every row comes back in the same order
+async fn publish(publisher: &dyn EventPublisher, messages: &[OutboxMessage]) -> Vec<Relayed> {
+    // the outbox id is the deduplication id, so a retry after a lost response is not sent twice
+    let outgoing = messages.iter()
+        .map(|message| OutgoingMessage::with_deduplication_id(&message.message_group_id, format!("outbox-{}", message.id), message.body.clone()))
+        .collect::<Vec<_>>();
+    let results = publisher.publish_batch(&outgoing).await;
+    messages.iter().zip(results).map(|(message, result)| match result {
+        Ok(()) => Relayed::Sent,
+        Err(error) => Relayed::Retry { delay: retry_delay(message.attempts), error },
+    }).collect()
+}
+
+// publishes the pending rows, returns how many were sent and how many failed. the rows are claimed first by
+// moving their next attempt past the lease, so nothing is locked while the queue is called and more than
+// one instance of the service can run the relay
+pub async fn relay_pending(publisher: &dyn EventPublisher, db: &Pool<Postgres>) -> Result<(u64, u64), sqlx::Error> {
+    let mut messages = sqlx::query_as::<_, OutboxMessage>("WITH due AS (select id from outbox \
+            where sent_at IS NULL AND next_attempt_at <= NOW() ORDER BY id LIMIT $1 FOR UPDATE SKIP LOCKED) \
+        update outbox o SET next_attempt_at = NOW() + ($2 * INTERVAL '1 second') FROM due where o.id = due.id \
+        RETURNING o.id, o.message_type, o.message_group_id, o.body, o.attempts")
+        .bind(RELAY_BATCH_SIZE).bind(RELAY_LEASE.as_secs() as i32).fetch_all(db).await?;
+    // the order of RETURNING isn't guaranteed, the events of a group go out in the order they were written
+    messages.sort_by_key(|message| message.id);
+
+    let (mut sent, mut failed) = (0, 0);
+    for (message, relayed) in messages.iter().zip(publish(publisher, &messages).await) {
+        match relayed {
+            Relayed::Sent => {
+                sqlx::query("update outbox SET sent_at=NOW(), attempts=attempts+1, last_error=NULL where id=$1")
+                    .bind(message.id).execute(db).await?;
+                sent += 1;
+            },
+            Relayed::Retry { delay, error } => {
+                tracing::warn!("unable to publish the {} event {}, retrying in {:?} : {}", message.message_type, message.id, delay, error) ;
+                sqlx::query("update outbox SET attempts=attempts+1, last_error=$1, next_attempt_at=NOW() + ($2 * INTERVAL '1 second') where id=$3")
+                    .bind(error).bind(delay.as_secs() as i32).bind(message.id).execute(db).await?;
+                failed += 1;
+            }
+        }
+    }
+    Ok((sent, failed))
+}
+
+pub async fn run_outbox_relay(publisher: Arc<dyn EventPublisher>, db: Arc<Pool<Postgres>>, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        match relay_pending(publisher.as_ref(), &db).await {
+            Ok((0, 0)) => {},
+            Ok((sent, failed)) => tracing::info!("outbox relay sent {} events, {} failed", sent, failed),
+            Err(err) => tracing::error!("unable to relay the outbox {}", err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snipsight_events::events::DeleteInsight;
+    use snipsight_events::publisher::InMemoryPublisher;
+
+    // the queue is down, every message fails
+    #[derive(Debug)]
+    struct FailingPublisher;
+
+    #[tonic::async_trait]
+    impl EventPublisher for FailingPublisher {
+        async fn publish_batch(&self, messages: &[OutgoingMessage]) -> Vec<Result<(), String>> {
+            messages.iter().map(|_| Err("queue unreachable".to_string())).collect()
+        }
+    }
+
+    fn message(id: i64, attempts: i32) -> OutboxMessage {
+        let (message_type, group_id, body) = outbox_row(SnipSightEvent::DeleteInsight(DeleteInsight { shorten_url: "launch".to_string() }));
+        OutboxMessage { id, message_type: message_type.to_string(), message_group_id: group_id.to_string(), body, attempts }
+    }
+
+    #[test]
+    fn enqueued_rows_carry_the_envelope() {
+        let (message_type, group_id, body) = outbox_row(SnipSightEvent::DeleteInsight(DeleteInsight { shorten_url: "launch".to_string() }));
+        assert_eq!((message_type, group_id), ("DELETE_INSIGHT", "delete-event"));
+        let envelope = Envelope::parse(&body).unwrap();
+        assert!(matches!(envelope.event, SnipSightEvent::DeleteInsight(DeleteInsight { shorten_url }) if shorten_url == "launch"));
+    }
+
+    #[tokio::test]
+    async fn published_rows_are_deduplicated_by_their_id() {
+        let publisher = InMemoryPublisher::default();
+        let messages = [message(7, 0), message(8, 2)];
+        assert_eq!(publish(&publisher, &messages).await, vec![Relayed::Sent, Relayed::Sent]);
+        let sent = publisher.messages();
+        assert_eq!(sent.iter().map(|message| message.deduplication_id.as_str()).collect::<Vec<_>>(), vec!["outbox-7", "outbox-8"]);
+        assert_eq!((sent[0].group_id.as_str(), sent[0].body.as_str()), ("delete-event", messages[0].body.as_str()));
+    }
+
+    #[tokio::test]
+    async fn failed_rows_are_retried_with_backoff() {
+        let relayed = publish(&FailingPublisher, &[message(7, 0), message(8, 3)]).await;
+        assert_eq!(relayed, vec![
+            Relayed::Retry { delay: Duration::from_secs(5), error: "queue unreachable".to_string() },
+            Relayed::Retry { delay: Duration::from_secs(40), error: "queue unreachable".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn retries_back_off_exponentially_upto_the_cap() {
+        assert_eq!(retry_delay(0), Duration::from_secs(5));
+        assert_eq!(retry_delay(1), Duration::from_secs(10));
+        assert_eq!(retry_delay(4), Duration::from_secs(80));
+        assert_eq!(retry_delay(8), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(i32::MAX), MAX_RETRY_DELAY);
+    }
+}
@@ -1,13 +1,44 @@
-use proto_definations_snip_sight::generated::url_shortner::{CreateShortenUrlPayload, Urls};
+use proto_definations_snip_sight::generated::url_shortner::{CreateShortenUrlPayload, Url, Urls};
+use proto_definations_snip_sight::name_blocklist::NameBlocklist;
 use sqlx::{Error, FromRow, Pool, Postgres, Row};
 use sqlx::postgres::{PgDatabaseError, PgRow};
 use tonic::Status;
 use crate::models::{ShortenUrl, UrlModel, OriginalUrl, ErrorMessage};
+use crate::services::custom_domains::{check_custom_domain_plan, domain_condition, normalise_domain};
+use crate::services::workspaces::MembershipLookup;
+use snipsight_events::insights::insight_key;
+use crate::services::name_blocklist::check_custom_name;
+use crate::services::url_safety::{screen_new_url, ThreatLists};
+use crate::services::link_groups::normalise_tag;
+use crate::services::activation_windows::{parse_window, visitor_window};
+use crate::services::outbox::enqueue;
+use snipsight_events::events::{DeleteInsight, SnipSightEvent};
 
-pub async fn store_new_url(payload: CreateShortenUrlPayload, db: &Pool<Postgres>) -> Result<(String, i32), ErrorMessage> {
+// the custom domain columns joined with the website_urls rows, used while reading the urls
+pub const URL_COLUMNS: &str = "w.id, w.original_url, w.shorten_url, w.view_count, w.created_at, d.domain, w.disabled_reason, \
+    w.health_status, w.health_latency_ms, w.health_failure_streak, w.health_checked_at, \
+    w.active_from, w.active_until, w.fallback_url, w.coming_soon_message, \
+    ARRAY(select t.name::TEXT from url_tags ut JOIN tags t ON t.id = ut.tag_id where ut.url_id = w.id ORDER BY t.name) AS tags, \
+    w.folder_id, (select f.name from folders f where f.id = w.folder_id) AS folder";
 
-    let result = sqlx::query_as::<_, (String, i32)>("insert into website_urls (user_id, original_url, shorten_url) values ($1, $2, $3) RETURNING shorten_url,id")
-        .bind(payload.user_id).bind(payload.original_url).bind(payload.custom_url)
+// only the links which are inside their activation window are redirected and counted
+const INSIDE_WINDOW: &str = "(active_from IS NULL OR active_from <= (NOW() AT TIME ZONE 'UTC')) AND (active_until IS NULL OR active_until > (NOW() AT TIME ZONE 'UTC'))";
+
+pub async fn store_new_url(payload: CreateShortenUrlPayload, lists: &ThreatLists, blocklist: &NameBlocklist, memberships: &dyn MembershipLookup, db: &Pool<Postgres>) -> Result<(String, i32), ErrorMessage> {
+
+    check_custom_name(&payload.custom_url, blocklist)? ;
+    screen_new_url(&payload.original_url, lists).await? ;
+    let window = parse_window(&payload.active_from, &payload.active_until, &payload.fallback_url, &payload.coming_soon_message, lists).await? ;
+
+    let domain_id = if payload.domain.is_empty() {
+        None
+    } else {
+        Some(get_verified_domain_id(payload.workspace_id, &payload.domain, memberships, db).await?)
+    };
+
+    let result = sqlx::query_as::<_, (String, i32)>("insert into website_urls (user_id, workspace_id, original_url, shorten_url, domain_id, active_from, active_until, fallback_url, coming_soon_message) values ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING shorten_url,id")
+        .bind(payload.user_id).bind(payload.workspace_id).bind(payload.original_url).bind(payload.custom_url).bind(domain_id)
+        .bind(window.active_from).bind(window.active_until).bind(window.fallback_url).bind(window.coming_soon_message)
         .fetch_one(db).await ;
     match result {
         Ok(result) => {
@@ -17,11 +48,11 @@ pub async fn store_new_url(payload: CreateShortenUrlPayload, db: &Pool<Postgres>
         Err(sqlx::Error::Database(error)) => {
             tracing::error!("error while inserting into website_urls was {}",error) ;
                 match error.constraint() {
-                    Some("unique_user_original_url") => {
+                    Some("unique_workspace_original_url") => {
                         tracing::warn!("The error got for getting same original url using again") ;
                         Err(ErrorMessage::new("Original Url already exists".to_string(), 409))
                     },
-                    Some("website_urls_shorten_url_key") => {
+                    Some("website_urls_domain_shorten_url_key") => {
                         tracing::warn!("The chosen shorten URL is already in use.");
                         Err(ErrorMessage::new("custom name already exists".to_string(),409))
                     },
@@ -42,29 +73,42 @@ pub async fn store_new_url(payload: CreateShortenUrlPayload, db: &Pool<Postgres>
     }
 }
 
-pub async fn get_urls(user_id: i32,page_number: u32, page_size: u32, db: &Pool<Postgres>) -> Result<Vec<Urls>, ErrorMessage> {
+// links can only be created on the custom domains which the workspace has verified, as long as it is still on a premium plan
+async fn get_verified_domain_id(workspace_id: i32, domain: &str, memberships: &dyn MembershipLookup, db: &Pool<Postgres>) -> Result<i32, ErrorMessage> {
+    let result = sqlx::query_as::<_, (i32,)>("select id from custom_domains where workspace_id=$1 AND domain=$2 AND verified_at IS NOT NULL")
+        .bind(workspace_id).bind(normalise_domain(domain)).fetch_one(db).await ;
+    match result {
+        Ok(result) => {
+            check_custom_domain_plan(workspace_id, memberships, db).await? ;
+            Ok(result.0)
+        },
+        Err(Error::RowNotFound) => {
+            tracing::warn!("the domain {} was not verified for the workspace {}", domain, workspace_id) ;
+            Err(ErrorMessage::new("Custom domain was not verified for the workspace".to_string(), 403))
+        },
+        Err(err) => {
+            tracing::error!("error while getting the custom domain was {}", err) ;
+            Err(ErrorMessage::new(err.to_string(), 500))
+        }
+    }
+}
 
-    tracing::info!("get urls was called with the user_id {}", user_id) ;
+// the tag and folder filters are optional, an empty tag and a 0 folder_id return all the links
+pub async fn get_urls(workspace_id: i32,page_number: u32, page_size: u32, tag: &str, folder_id: i32, db: &Pool<Postgres>) -> Result<Vec<Urls>, ErrorMessage> {
+
+    tracing::info!("get urls was called with the workspace_id {}", workspace_id) ;
     tracing::info!("page size {} and page number {}", page_size, page_number) ;
+    let tag = if tag.trim().is_empty() { String::new() } else { normalise_tag(tag)? } ;
     let offset = (page_number - 1) * page_size ;
-    let urls = sqlx::query_as::<_, UrlModel>("select * from website_urls where user_id = $1 OFFSET $2 LIMIT $3")
-    .bind(user_id).bind(offset as i32).bind(page_size as i32).fetch_all(db).await ;
+    let urls = sqlx::query_as::<_, UrlModel>(&format!("select {} from website_urls w LEFT JOIN custom_domains d ON d.id = w.domain_id where w.workspace_id = $1 \
+        AND ($4 = '' OR EXISTS (select 1 from url_tags ut JOIN tags t ON t.id = ut.tag_id where ut.url_id = w.id AND t.name = $4)) \
+        AND ($5 = 0 OR w.folder_id = $5) OFFSET $2 LIMIT $3", URL_COLUMNS))
+    .bind(workspace_id).bind(offset as i32).bind(page_size as i32).bind(tag).bind(folder_id).fetch_all(db).await ;
 
     match urls {
         Ok(urls) => {
-            tracing::info!("got the urls for the user_id {}",user_id) ;
-            let mut urlss = vec![] ;
-            for url in urls.iter() {
-                urlss.push(Urls {
-                    id: url.id,
-                    original_url: url.original_url.to_string(),
-                    shorten_url: url.shorten_url.to_string(),
-                    view_count: url.view_count,
-                    created_at: url.created_at.to_string()
-                })
-            }
-
-            Ok(urlss)
+            tracing::info!("got the urls for the workspace_id {}",workspace_id) ;
+            Ok(urls.into_iter().map(Urls::from).collect())
         },
         Err(error) => {
             tracing::error!("Error Occured while getting urls {}",error) ;
@@ -75,12 +119,12 @@ pub async fn get_urls(user_id: i32,page_number: u32, page_size: u32, db: &Pool<P
 }
 
 //* This feature is Currently Not there
-pub async fn update_shorten_url_name(id: i32, name: &str, user_id: i32, db: &Pool<Postgres>) -> Result<bool, String> {
+pub async fn update_shorten_url_name(id: i32, name: &str, workspace_id: i32, db: &Pool<Postgres>) -> Result<bool, String> {
 
     tracing::info!("update shorten url name was called with the id {} and name {}", id, name) ;
 
-    let result = sqlx::query("update website_urls SET shorten_url=$1 where id=$2 AND user_id=$3")
-        .bind(name).bind(id).bind(user_id).execute(db).await ;
+    let result = sqlx::query("update website_urls SET shorten_url=$1 where id=$2 AND workspace_id=$3")
+        .bind(name).bind(id).bind(workspace_id).execute(db).await ;
 
     match result {
         Ok(result) => {
@@ -105,16 +149,27 @@ pub async fn update_shorten_url_name(id: i32, name: &str, user_id: i32, db: &Poo
     }
 }
 
-pub async fn delete_url(id: i32, user_id: i32, db: &Pool<Postgres>) -> Result<String, ErrorMessage> {
+pub async fn delete_url(id: i32, workspace_id: i32, db: &Pool<Postgres>) -> Result<String, ErrorMessage> {
 
     tracing::info!("delete url was called with the id {}", id) ;
-    let result = sqlx::query_as::<_, ShortenUrl>("DELETE FROM website_urls WHERE id=$1 AND user_id=$2 RETURNING shorten_url")
-        .bind(id).bind(user_id).fetch_one(db).await ;
+    let mut transaction = db.begin().await.map_err(|err| {
+        tracing::error!("unable to start the transaction {}", err) ;
+        ErrorMessage::new(String::from("An unexpected database error occurred"),500)
+    })?;
+    let result = sqlx::query_as::<_, ShortenUrl>("DELETE FROM website_urls w WHERE id=$1 AND workspace_id=$2 RETURNING shorten_url, (select domain from custom_domains d where d.id = w.domain_id) AS domain")
+        .bind(id).bind(workspace_id).fetch_one(&mut *transaction).await ;
 
     match result {
         Ok(result) => {
+            // the insights are deleted with the key they were stored with, the event is only sent if the delete commits
+            let key = insight_key(&result.shorten_url, &result.domain.unwrap_or_default());
+            enqueue(SnipSightEvent::DeleteInsight(DeleteInsight {shorten_url: key.clone()}), &mut transaction).await?;
+            transaction.commit().await.map_err(|err| {
+                tracing::error!("unable to commit the deletion {}", err) ;
+                ErrorMessage::new(String::from("An unexpected database error occurred"),500)
+            })?;
             tracing::info!("deletion happened") ;
-            Ok(result.shorten_url)
+            Ok(key)
         },
         Err(Error::RowNotFound) => {
             tracing::warn!("NO Row Found") ;
@@ -135,10 +190,10 @@ pub async fn delete_url(id: i32, user_id: i32, db: &Pool<Postgres>) -> Result<St
 }
 
 
-pub async fn increase_view_count(shorten_url: &str, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+pub async fn increase_view_count(shorten_url: &str, domain: &str, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
     tracing::info!("increase_view_count was called with the shorten_url {}", shorten_url) ;
-    let result = sqlx::query("update website_urls SET view_count=view_count+1 where shorten_url=$1")
-        .bind(shorten_url).execute(db).await ;
+    let result = sqlx::query(&format!("update website_urls SET view_count=view_count+1 where shorten_url=$1 AND disabled_reason IS NULL AND {} AND {}", INSIDE_WINDOW, domain_condition(2)))
+        .bind(shorten_url).bind(normalise_domain(domain)).execute(db).await ;
 
     match result {
         Ok(res) => {
@@ -156,19 +211,91 @@ pub async fn increase_view_count(shorten_url: &str, db: &Pool<Postgres>) -> Resu
     }
 }
 
-pub async fn get_original_url_service(shorten_url: &str, db: &Pool<Postgres>) -> Result<String, ErrorMessage> {
-    tracing::info!("get_original_url was called with the shorten_url {}", shorten_url) ;
+// returns the original url along with the custom domain it was resolved on (empty for ours), the reason
+// when the destination was flagged as unsafe and the side of the activation window the link was on
+pub async fn get_original_url_service(shorten_url: &str, domain: &str, db: &Pool<Postgres>) -> Result<Url, ErrorMessage> {
+    tracing::info!("get_original_url was called with the shorten_url {} on {}", shorten_url, domain) ;
     let result = sqlx::query_as::<_, OriginalUrl>
-        ("select original_url from website_urls where shorten_url=$1")
-        .bind(shorten_url).fetch_one(db).await ;
+        (&format!("select w.original_url, d.domain, w.disabled_reason, w.active_from, w.active_until, w.fallback_url, w.coming_soon_message \
+            from website_urls w LEFT JOIN custom_domains d ON d.id = w.domain_id where w.shorten_url=$1 AND {}", domain_condition(2)))
+        .bind(shorten_url).bind(normalise_domain(domain)).fetch_one(db).await ;
     match result {
         Ok(res) => {
             tracing::info!("the res was {:?}", res) ;
-            Ok(res.original_url)
+            Ok(Url {
+                url: res.original_url,
+                domain: res.domain.unwrap_or_default(),
+                disabled_reason: res.disabled_reason.unwrap_or_default(),
+                ..visitor_window(res.active_from, res.active_until, res.fallback_url, res.coming_soon_message)
+            })
         },
         Err(err) => {
             tracing::error!("error was {}", err) ;
             Err(ErrorMessage::new(err.to_string(), 500))
         }
     }
-}
\ No newline at end of file
+}
+
+// the links of other workspaces and the ones which don't exist look the same, so the codes can't be probed
+fn check_workspace(owner: Option<i32>, workspace_id: i32, shorten_url: &str) -> Result<(), ErrorMessage> {
+    match owner {
+        Some(owner) if owner == workspace_id => Ok(()),
+        _ => {
+            tracing::warn!("{} is not a link of the workspace {}", shorten_url, workspace_id) ;
+            Err(ErrorMessage::new("You don't have access to this url".to_string(), 403))
+        }
+    }
+}
+
+// only the members of the link's workspace get its insights, they carry the visitors' ip addresses
+pub async fn verify_link_workspace(shorten_url: &str, domain: &str, workspace_id: i32, db: &Pool<Postgres>) -> Result<(), ErrorMessage> {
+    let owner = sqlx::query_scalar::<_, i32>(&format!("select w.workspace_id from website_urls w where w.shorten_url=$1 AND {}", domain_condition(2)))
+        .bind(shorten_url).bind(normalise_domain(domain)).fetch_optional(db).await
+        .map_err(|err| {
+            tracing::error!("unable to get the owner of {} : {}", shorten_url, err) ;
+            ErrorMessage::new(err.to_string(), 500)
+        })?;
+    check_workspace(owner, workspace_id, shorten_url)
+}
+
+pub async fn get_url_preview_service(shorten_url: &str, domain: &str, db: &Pool<Postgres>) -> Result<Urls, ErrorMessage> {
+    tracing::info!("get_url_preview was called with the shorten_url {}", shorten_url) ;
+    // only reading the row, the view_count stays as it is for the preview
+    let result = sqlx::query_as::<_, UrlModel>
+        (&format!("select {} from website_urls w LEFT JOIN custom_domains d ON d.id = w.domain_id where w.shorten_url=$1 AND {}", URL_COLUMNS, domain_condition(2)))
+        .bind(shorten_url).bind(normalise_domain(domain)).fetch_one(db).await ;
+    match result {
+        Ok(res) => {
+            tracing::info!("got the preview details for {}", shorten_url) ;
+            let window = visitor_window(res.active_from, res.active_until, res.fallback_url.clone(), res.coming_soon_message.clone()) ;
+            let mut preview = Urls::from(res) ;
+            // the destination isn't shown before the launch or after the end, the same as for the redirects
+            if !window.activation_state.is_empty() {
+                preview.original_url = String::new() ;
+            }
+            preview.window = Some(window) ;
+            Ok(preview)
+        },
+        Err(Error::RowNotFound) => {
+            tracing::warn!("NO Row Found for the preview") ;
+            Err(ErrorMessage::new("Shorten url doesn't exists".to_string(), 404))
+        },
+        Err(err) => {
+            tracing::error!("error was {}", err) ;
+            Err(ErrorMessage::new(err.to_string(), 500))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_links_of_the_workspace_pass() {
+        assert!(check_workspace(Some(7), 7, "launch").is_ok());
+        assert_eq!(check_workspace(Some(7), 8, "launch").unwrap_err().status_code, 403);
+        // a missing link is denied the same way as another workspace's
+        assert_eq!(check_workspace(None, 7, "launch").unwrap_err().status_code, 403);
+    }
+}
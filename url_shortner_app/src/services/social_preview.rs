@@ -0,0 +1,241 @@
+use std::sync::Arc;
+use std::time::Duration;
+use proto_definations_snip_sight::generated::url_shortner::SocialPreview;
+use regex::Regex;
+use sqlx::{Error, Pool, Postgres};
+use crate::models::{ErrorMessage, OpenGraphTags, SocialPreviewModel};
+use crate::services::custom_domains::{domain_condition, normalise_domain};
+use crate::services::activation_windows::visitor_window;
+use crate::services::url_safety::{screened_redirects, PublicResolver};
+
+// pages bigger than this are not read completely, og tags live in the head anyway
+const MAX_PAGE_BYTES: usize = 512 * 1024;
+const MAX_REDIRECTS: usize = 5;
+
+// the fetcher is injected in to the service, so tests can point it to a local stub server
+#[tonic::async_trait]
+pub trait PageFetcher: Send + Sync + std::fmt::Debug {
+    async fn fetch(&self, url: &str) -> Result<String, String>;
+}
+
+#[derive(Debug)]
+pub struct HttpPageFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpPageFetcher {
+    pub fn new() -> Self {
+        // every redirect is screened and the names are resolved to public addresses only
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .redirect(screened_redirects(MAX_REDIRECTS))
+            .dns_resolver(Arc::new(PublicResolver))
+            .user_agent("SnipSightBot/1.0 (+https://web.snipsight.phani.services)")
+            .build()
+            .expect("unable to build the http client");
+        Self { client }
+    }
+}
+
+#[tonic::async_trait]
+impl PageFetcher for HttpPageFetcher {
+    async fn fetch(&self, url: &str) -> Result<String, String> {
+        let mut response = self.client.get(url).send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("destination responded with {}", response.status()))
+        }
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            body.extend_from_slice(&chunk);
+            if body.len() >= MAX_PAGE_BYTES {
+                break
+            }
+        }
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+}
+
+// reads og:* (falling back to twitter:*) meta tags from the html
+pub fn extract_open_graph_tags(html: &str) -> OpenGraphTags {
+    let meta_tag = Regex::new(r"(?is)<meta\s[^>]*>").unwrap();
+    let attribute = Regex::new(r#"(?is)([a-z:_-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+
+    let mut tags = OpenGraphTags::default();
+    let mut fallback = OpenGraphTags::default();
+    for tag in meta_tag.find_iter(html) {
+        let mut key = None;
+        let mut content = None;
+        for attr in attribute.captures_iter(tag.as_str()) {
+            let value = attr.get(2).or_else(|| attr.get(3)).map(|v| v.as_str().trim().to_string());
+            match attr[1].to_lowercase().as_str() {
+                "property" | "name" => key = value.map(|v| v.to_lowercase()),
+                "content" => content = value,
+                _ => {}
+            }
+        }
+        let (Some(key), Some(content)) = (key, content) else { continue };
+        if content.is_empty() {
+            continue
+        }
+        let slot = match key.as_str() {
+            "og:title" => &mut tags.title,
+            "og:description" => &mut tags.description,
+            "og:image" | "og:image:url" => &mut tags.image_url,
+            "twitter:title" => &mut fallback.title,
+            "twitter:description" => &mut fallback.description,
+            "twitter:image" => &mut fallback.image_url,
+            _ => continue,
+        };
+        slot.get_or_insert(content);
+    }
+
+    OpenGraphTags {
+        title: tags.title.or(fallback.title),
+        description: tags.description.or(fallback.description),
+        image_url: tags.image_url.or(fallback.image_url),
+    }
+}
+
+// values chosen by the owner are never overwritten by the fetched ones
+pub async fn store_fetched_social_preview(id: i32, tags: OpenGraphTags, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+    tracing::info!("storing the fetched social preview for the id {}", id) ;
+    let result = sqlx::query("update website_urls SET og_title=COALESCE(og_title, LEFT($1, 200)), og_description=COALESCE(og_description, LEFT($2, 500)), og_image_url=COALESCE(og_image_url, LEFT($3, 1200)) where id=$4")
+        .bind(tags.title).bind(tags.description).bind(tags.image_url).bind(id)
+        .execute(db).await ;
+    match result {
+        Ok(res) => Ok(res.rows_affected() > 0),
+        Err(err) => {
+            tracing::error!("error while storing the fetched social preview was {}", err) ;
+            Err(ErrorMessage::new(err.to_string(), 500))
+        }
+    }
+}
+
+pub async fn fetch_and_store_social_preview(id: i32, original_url: String, fetcher: &dyn PageFetcher, db: &Pool<Postgres>) {
+    match fetcher.fetch(&original_url).await {
+        Ok(html) => {
+            let tags = extract_open_graph_tags(&html) ;
+            tracing::info!("og tags found for the id {} were {:?}", id, tags) ;
+            if let Err(err) = store_fetched_social_preview(id, tags, db).await {
+                tracing::error!("unable to store the fetched social preview {:?}", err) ;
+            }
+        },
+        Err(err) => {
+            tracing::warn!("unable to fetch the destination page for social preview : {}", err) ;
+        }
+    }
+}
+
+pub async fn update_social_preview(preview: SocialPreview, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+    tracing::info!("update social preview was called with the id {}", preview.id) ;
+    let result = sqlx::query("update website_urls SET og_title=NULLIF($1, ''), og_description=NULLIF($2, ''), og_image_url=NULLIF($3, '') where id=$4 AND workspace_id=$5")
+        .bind(preview.title).bind(preview.description).bind(preview.image_url)
+        .bind(preview.id).bind(preview.workspace_id)
+        .execute(db).await ;
+    match result {
+        Ok(res) => {
+            if res.rows_affected() > 0 {
+                Ok(true)
+            }else {
+                tracing::warn!("NO Row Found to update the social preview") ;
+                Err(ErrorMessage::new("Row doesn't exists".to_string(), 404))
+            }
+        },
+        Err(err) => {
+            tracing::error!("error while updating social preview was {}", err) ;
+            Err(ErrorMessage::new(String::from("An unexpected database error occurred"), 500))
+        }
+    }
+}
+
+pub async fn get_social_preview(shorten_url: &str, domain: &str, db: &Pool<Postgres>) -> Result<SocialPreview, ErrorMessage> {
+    tracing::info!("get social preview was called with the shorten_url {}", shorten_url) ;
+    let result = sqlx::query_as::<_, SocialPreviewModel>(&format!("select original_url, og_title, og_description, og_image_url, active_from, active_until, fallback_url, coming_soon_message from website_urls where shorten_url=$1 AND disabled_reason IS NULL AND {}", domain_condition(2)))
+        .bind(shorten_url).bind(normalise_domain(domain)).fetch_one(db).await ;
+    // the flagged links never get a card, the unfurlers see the same 404 as for a missing link
+    match result {
+        // outside the activation window the unfurlers get the same pages as the visitors, not the destination
+        Ok(res) => {
+            let window = visitor_window(res.active_from, res.active_until, res.fallback_url, res.coming_soon_message);
+            Ok(SocialPreview {
+                id: 0,
+                user_id: 0,
+                workspace_id: 0,
+                title: res.og_title.unwrap_or_default(),
+                description: res.og_description.unwrap_or_default(),
+                image_url: res.og_image_url.unwrap_or_default(),
+                original_url: if window.activation_state.is_empty() { res.original_url } else { String::new() },
+                window: Some(window),
+            })
+        },
+        Err(Error::RowNotFound) => {
+            tracing::warn!("NO Row Found for the social preview") ;
+            Err(ErrorMessage::new("Shorten url doesn't exists".to_string(), 404))
+        },
+        Err(err) => {
+            tracing::error!("error was {}", err) ;
+            Err(ErrorMessage::new(err.to_string(), 500))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const PAGE: &str = r#"<html><head>
+        <meta property="og:title" content="Launch day">
+        <meta name="twitter:title" content="Ignored twitter title">
+        <meta content='All about the launch' name='twitter:description'>
+        <META PROPERTY="og:image" CONTENT="https://cdn.example.com/launch.png" />
+        </head><body></body></html>"#;
+
+    #[test]
+    fn og_tags_are_preferred_over_twitter_tags() {
+        let tags = extract_open_graph_tags(PAGE);
+        assert_eq!(tags, OpenGraphTags {
+            title: Some("Launch day".to_string()),
+            description: Some("All about the launch".to_string()),
+            image_url: Some("https://cdn.example.com/launch.png".to_string()),
+        });
+    }
+
+    #[tokio::test]
+    async fn http_fetcher_reads_the_stub_server_page() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = [0u8; 1024];
+            let _ = socket.read(&mut buffer).await.unwrap();
+            let response = format!("HTTP/1.1 200 OK\r\ncontent-type: text/html\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}", PAGE.len(), PAGE);
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let html = HttpPageFetcher::new().fetch(&format!("http://{}/launch", address)).await.unwrap();
+        assert_eq!(extract_open_graph_tags(&html).title.as_deref(), Some("Launch day"));
+    }
+
+    #[tokio::test]
+    async fn http_fetcher_doesnt_follow_redirects_in_to_private_addresses() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            // the public page redirects to the internal one, which would answer if it was followed
+            for location in [Some(format!("http://{}/internal", address)), None] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = [0u8; 1024];
+                let _ = socket.read(&mut buffer).await.unwrap();
+                let response = match location {
+                    Some(location) => format!("HTTP/1.1 302 Found\r\nlocation: {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", location),
+                    None => format!("HTTP/1.1 200 OK\r\ncontent-type: text/html\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}", PAGE.len(), PAGE),
+                };
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        assert!(HttpPageFetcher::new().fetch(&format!("http://{}/launch", address)).await.is_err());
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use ipnet::IpNet;
+use reqwest::Url;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use sqlx::{Pool, Postgres};
+use crate::models::ErrorMessage;
+
+// one entry per line in each file, lines starting with '#' are comments
+const DOMAINS_FILE: &str = "domains.txt";
+const URL_PREFIXES_FILE: &str = "url_prefixes.txt";
+const IP_RANGES_FILE: &str = "ip_ranges.txt";
+
+// links are re-screened in batches of these many rows
+const RESCREEN_BATCH_SIZE: i64 = 500;
+
+#[derive(Debug, Default)]
+pub struct ThreatLists {
+    domains: HashSet<String>,
+    url_prefixes: Vec<String>,
+    ip_ranges: Vec<IpNet>,
+}
+
+// the current lists, swapped as a whole when the files change
+pub type SharedThreatLists = Arc<RwLock<Arc<ThreatLists>>>;
+
+fn read_entries(path: &Path) -> Vec<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => content.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.to_lowercase())
+            .collect(),
+        Err(err) => {
+            tracing::warn!("unable to read the threat list {:?} : {}", path, err);
+            vec![]
+        }
+    }
+}
+
+impl ThreatLists {
+    pub fn load(dir: &Path) -> Self {
+        let ip_ranges = read_entries(&dir.join(IP_RANGES_FILE)).into_iter()
+            .filter_map(|entry| {
+                // single addresses are allowed as well as the ranges
+                entry.parse::<IpNet>().or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+                    .map_err(|err| tracing::error!("skipping the invalid ip range {} : {}", entry, err))
+                    .ok()
+            })
+            .collect();
+        let lists = ThreatLists {
+            domains: read_entries(&dir.join(DOMAINS_FILE)).into_iter().map(|domain| domain.trim_end_matches('.').to_string()).collect(),
+            url_prefixes: read_entries(&dir.join(URL_PREFIXES_FILE)),
+            ip_ranges,
+        };
+        tracing::info!("loaded threat lists: {} domains, {} url prefixes, {} ip ranges", lists.domains.len(), lists.url_prefixes.len(), lists.ip_ranges.len());
+        lists
+    }
+
+    fn domain_listed(&self, host: &str) -> bool {
+        // sub domains of a listed domain are listed as well
+        let mut domain = host;
+        loop {
+            if self.domains.contains(domain) {
+                return true
+            }
+            match domain.split_once('.') {
+                Some((_, parent)) => domain = parent,
+                None => return false
+            }
+        }
+    }
+
+    fn ip_listed(&self, ip: &IpAddr) -> bool {
+        self.ip_ranges.iter().any(|range| range.contains(ip))
+    }
+}
+
+fn is_private_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.is_private() || ip.is_loopback() || ip.is_link_local() || ip.is_unspecified()
+            || ip.is_broadcast() || ip.is_documentation() || ip.octets()[0] == 0
+            || (ip.octets()[0] == 100 && (ip.octets()[1] & 0xc0) == 64), // shared address space 100.64.0.0/10
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ip.to_ipv4_mapped() {
+                return is_private_ip(&IpAddr::V4(mapped))
+            }
+            ip.is_loopback() || ip.is_unspecified()
+                || (ip.segments()[0] & 0xfe00) == 0xfc00 // unique local fc00::/7
+                || (ip.segments()[0] & 0xffc0) == 0xfe80 // link local fe80::/10
+        }
+    }
+}
+
+// checks the destination against the threat lists, returns the reason when it was not safe
+pub fn screen_url(original_url: &str, lists: &ThreatLists) -> Result<(), String> {
+    let url = Url::parse(original_url).map_err(|_| "destination url was not valid".to_string())?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err("only http and https destinations are allowed".to_string())
+    }
+    let lowered = original_url.to_lowercase();
+    if lists.url_prefixes.iter().any(|prefix| lowered.starts_with(prefix)) {
+        return Err("destination url was listed as unsafe".to_string())
+    }
+    let host = match url.host_str() {
+        Some(host) => host.trim_start_matches('[').trim_end_matches(']'),
+        None => return Err("destination url was not valid".to_string())
+    };
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return screen_ip(&ip, lists)
+    }
+    let domain = host.trim_end_matches('.');
+    if domain == "localhost" || domain.ends_with(".localhost") || domain.ends_with(".local") || domain.ends_with(".internal") {
+        return Err("private destinations are not allowed".to_string())
+    }
+    if lists.domain_listed(domain) {
+        return Err("destination domain was listed as unsafe".to_string())
+    }
+    Ok(())
+}
+
+fn screen_ip(ip: &IpAddr, lists: &ThreatLists) -> Result<(), String> {
+    if is_private_ip(ip) {
+        Err("private destinations are not allowed".to_string())
+    } else if lists.ip_listed(ip) {
+        Err("destination address was listed as unsafe".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+// used while creating, on top of the lists the addresses the domain resolves to are checked
+pub async fn screen_new_url(original_url: &str, lists: &ThreatLists) -> Result<(), ErrorMessage> {
+    if let Err(reason) = screen_url(original_url, lists) {
+        tracing::warn!("the destination {} was rejected : {}", original_url, reason) ;
+        return Err(ErrorMessage::new(reason, 400))
+    }
+    if let Some(host) = Url::parse(original_url).ok().and_then(|url| url.host_str().map(|host| host.to_string())) {
+        // lookup failures are not rejected, the destination can be down for a while
+        if let Ok(addresses) = tokio::net::lookup_host((host.as_str(), 80)).await {
+            for address in addresses {
+                if let Err(reason) = screen_ip(&address.ip(), lists) {
+                    tracing::warn!("the destination {} resolves to {} : {}", original_url, address.ip(), reason) ;
+                    return Err(ErrorMessage::new(reason, 400))
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// the destination was screened, the pages it redirects to weren't. the clients calling the user given urls screen every
+// hop the same way, so a redirect can't lead them in to our network
+pub fn screened_redirects(max_redirects: usize) -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= max_redirects {
+            return attempt.error("too many redirects")
+        }
+        match screen_url(attempt.url().as_str(), &ThreatLists::default()) {
+            Ok(()) => attempt.follow(),
+            Err(reason) => attempt.error(reason)
+        }
+    })
+}
+
+// resolves the hosts for the clients which call the user given urls. the names resolving to a private address are
+// refused when connecting, so a name which changed its addresses after it was screened (dns rebinding) or which
+// didn't resolve back then can't reach our network
+#[derive(Debug, Default)]
+pub struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses = tokio::net::lookup_host((name.as_str(), 0)).await?.collect::<Vec<_>>();
+            if let Some(address) = addresses.iter().find(|address| is_private_ip(&address.ip())) {
+                return Err(format!("{} resolves to the private address {}", name.as_str(), address.ip()).into())
+            }
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+// screens every link again with the new lists, the offenders are disabled and the links which are
+// no more listed are enabled back
+pub async fn rescreen_links(lists: &ThreatLists, db: &Pool<Postgres>) -> Result<(u64, u64), ErrorMessage> {
+    let (mut disabled, mut enabled) = (0, 0);
+    let mut last_id = 0;
+    loop {
+        let rows = sqlx::query_as::<_, (i32, String, Option<String>)>("select id, original_url, disabled_reason from website_urls where id > $1 ORDER BY id LIMIT $2")
+            .bind(last_id).bind(RESCREEN_BATCH_SIZE).fetch_all(db).await
+            .map_err(|err| ErrorMessage::new(err.to_string(), 500))?;
+        let Some(last) = rows.last() else { break };
+        last_id = last.0;
+
+        for (id, original_url, disabled_reason) in rows {
+            let verdict = screen_url(&original_url, lists).err();
+            if verdict == disabled_reason {
+                continue
+            }
+            sqlx::query("update website_urls SET disabled_reason=$1, disabled_at=CASE WHEN $1 IS NULL THEN NULL ELSE NOW() END where id=$2")
+                .bind(&verdict).bind(id).execute(db).await
+                .map_err(|err| ErrorMessage::new(err.to_string(), 500))?;
+            if verdict.is_some() {
+                disabled += 1;
+            } else {
+                enabled += 1;
+            }
+        }
+    }
+    Ok((disabled, enabled))
+}
+
+fn last_modified(dir: &Path) -> Vec<Option<SystemTime>> {
+    [DOMAINS_FILE, URL_PREFIXES_FILE, IP_RANGES_FILE].iter()
+        .map(|file| std::fs::metadata(dir.join(file)).and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+// reloads the lists when any of the files changes and re-screens the existing links with them
+pub async fn watch_threat_lists(dir: PathBuf, lists: SharedThreatLists, db: Arc<Pool<Postgres>>, every: Duration) {
+    let mut seen = last_modified(&dir);
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        let current = last_modified(&dir);
+        if current == seen {
+            continue
+        }
+        seen = current;
+        let reloaded = Arc::new(ThreatLists::load(&dir));
+        *lists.write().unwrap() = reloaded.clone();
+        match rescreen_links(&reloaded, &db).await {
+            Ok((disabled, enabled)) => tracing::info!("re-screened the links, {} disabled and {} enabled back", disabled, enabled),
+            Err(err) => tracing::error!("unable to re-screen the links {:?}", err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lists() -> ThreatLists {
+        let dir = std::env::temp_dir().join(format!("snipsight-threat-lists-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(DOMAINS_FILE), "# phishing\nevil.example\n").unwrap();
+        std::fs::write(dir.join(URL_PREFIXES_FILE), "https://docs.example.com/forms/d/\n").unwrap();
+        std::fs::write(dir.join(IP_RANGES_FILE), "203.0.113.0/24\n198.51.100.7\nnot-an-ip\n").unwrap();
+        let lists = ThreatLists::load(&dir);
+        std::fs::remove_dir_all(dir).unwrap();
+        lists
+    }
+
+    #[test]
+    fn listed_destinations_are_rejected() {
+        let lists = lists();
+        assert!(screen_url("https://evil.example/login", &lists).is_err());
+        assert!(screen_url("https://login.EVIL.example./x", &lists).is_err());
+        assert!(screen_url("https://docs.example.com/forms/d/abc", &lists).is_err());
+        assert!(screen_url("http://198.51.100.7/", &lists).is_err());
+        assert!(screen_url("https://notevil.example/", &lists).is_ok());
+        assert!(screen_url("https://docs.example.com/document/d/abc", &lists).is_ok());
+    }
+
+    #[test]
+    fn private_destinations_are_rejected() {
+        let lists = ThreatLists::default();
+        for url in ["http://127.0.0.1:8080/", "http://10.1.2.3/", "http://192.168.0.1/", "http://169.254.169.254/latest/meta-data",
+                    "http://[::1]/", "http://[::ffff:10.0.0.1]/", "http://localhost:9091/", "ftp://example.com/file", "javascript:alert(1)"] {
+            assert!(screen_url(url, &lists).is_err(), "{} should be rejected", url);
+        }
+        assert!(screen_url("https://93.184.215.14/", &lists).is_ok());
+    }
+
+    #[tokio::test]
+    async fn names_of_private_addresses_are_not_resolved() {
+        assert!(PublicResolver.resolve("localhost".parse().unwrap()).await.is_err());
+    }
+}
@@ -0,0 +1,551 @@
+use std::sync::Arc;
+use std::time::Duration;
+use aws_sdk_dynamodb::Client as DynamoClient;
+use chrono::{NaiveDate, Utc};
+use hmac::{Hmac, Mac};
+use proto_definations_snip_sight::generated::url_shortner::{WebhookDelivery, WebhookEndpoint, WebhookRule};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use sqlx::{Error, Pool, Postgres};
+use crate::models::{ErrorMessage, PendingWebhookDelivery, WebhookDeliveryModel, WebhookEndpointModel, WebhookRuleModel, WebhookRuleTarget};
+use crate::services::activation_windows::now;
+use crate::services::analytics::link_days;
+use crate::services::outbox::retry_delay;
+use crate::services::url_safety::{screen_new_url, PublicResolver, ThreatLists};
+
+const MAX_ENDPOINTS_PER_WORKSPACE: i64 = 10;
+const MAX_RULES_PER_WORKSPACE: i64 = 50;
+// the rule and link pairs evaluated in one go, and the deliveries sent in one go
+const RULES_BATCH_SIZE: i64 = 200;
+const DELIVERY_BATCH_SIZE: i64 = 20;
+// the claimed deliveries are picked up again after this, when the instance sending them went down. it is longer
+// than a whole batch of timeouts, so a slow batch isn't sent twice
+const DELIVERY_LEASE: Duration = Duration::from_secs(10 * 60);
+// after these many failed attempts the delivery is marked failed, the retries back off like the outbox
+const MAX_DELIVERY_ATTEMPTS: i32 = 8;
+// a spike needs at least these many clicks today, so 3 clicks against an average of 0.2 isn't one
+const MIN_SPIKE_CLICKS: i64 = 10;
+// the delivery log shown to the user
+const DELIVERY_LOG_SIZE: i64 = 100;
+const MAX_ERROR_LENGTH: usize = 500;
+
+pub const CLICK_THRESHOLD: &str = "click_threshold";
+pub const SPIKE: &str = "spike";
+pub const NO_CLICKS: &str = "no_clicks";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleKind {
+    ClickThreshold(i64),
+    Spike { factor: f64, days: i32 },
+    NoClicks(i32),
+}
+
+impl RuleKind {
+    pub fn parse(rule_type: &str, threshold: i64, spike_factor: f64, days: i32) -> Result<Self, ErrorMessage> {
+        match rule_type {
+            CLICK_THRESHOLD if threshold >= 1 => Ok(RuleKind::ClickThreshold(threshold)),
+            CLICK_THRESHOLD => Err(ErrorMessage::new("threshold must be at least 1 click".to_string(), 400)),
+            SPIKE if !(1.5..=100.0).contains(&spike_factor) => Err(ErrorMessage::new("spike_factor must be between 1.5 and 100".to_string(), 400)),
+            SPIKE if (1..=30).contains(&days) => Ok(RuleKind::Spike { factor: spike_factor, days }),
+            SPIKE => Err(ErrorMessage::new("the trailing average of a spike can be 1 to 30 days".to_string(), 400)),
+            NO_CLICKS if (1..=90).contains(&days) => Ok(RuleKind::NoClicks(days)),
+            NO_CLICKS => Err(ErrorMessage::new("days without clicks must be 1 to 90".to_string(), 400)),
+            _ => Err(ErrorMessage::new(format!("rule_type must be {}, {} or {}", CLICK_THRESHOLD, SPIKE, NO_CLICKS), 400)),
+        }
+    }
+
+    fn of_target(target: &WebhookRuleTarget) -> Result<Self, ErrorMessage> {
+        Self::parse(&target.rule_type, target.threshold.unwrap_or_default(), target.spike_factor.unwrap_or_default(), target.days.unwrap_or_default())
+    }
+
+    // the days of clicks the rule looks at, ending today. a threshold only needs the view count
+    fn days(&self) -> i64 {
+        match self {
+            RuleKind::ClickThreshold(_) => 0,
+            RuleKind::Spike { days, .. } => *days as i64 + 1,
+            RuleKind::NoClicks(days) => *days as i64,
+        }
+    }
+}
+
+// the clicks of the link, `daily` has the clicks of the days the rule looks at, oldest first and today last
+#[derive(Debug, Default)]
+pub struct LinkClicks {
+    pub total: i64,
+    pub daily: Vec<i64>,
+}
+
+// the details of the notification when the rule matches, None when it doesn't or it was already notified
+pub fn evaluate(kind: &RuleKind, clicks: &LinkClicks, created_on: NaiveDate, fired_on: Option<NaiveDate>, today: NaiveDate) -> Option<Value> {
+    match kind {
+        // once per link, the clicks only go up
+        RuleKind::ClickThreshold(threshold) => (fired_on.is_none() && clicks.total >= *threshold)
+            .then(|| json!({ "clicks": clicks.total, "threshold": threshold })),
+        // once a day at most
+        RuleKind::Spike { factor, days } => {
+            let (today_clicks, before) = clicks.daily.split_last()?;
+            let average = before.iter().sum::<i64>() as f64 / *days as f64;
+            (fired_on != Some(today) && *today_clicks >= MIN_SPIKE_CLICKS && *today_clicks as f64 >= factor * average.max(1.0))
+                .then(|| json!({ "clicks_today": today_clicks, "trailing_average": (average * 100.0).round() / 100.0, "spike_factor": factor, "days": days }))
+        },
+        // the link has to be older than the window, and it is repeated every `days` days while it stays idle
+        RuleKind::NoClicks(days) => {
+            let window_start = today - chrono::Duration::days(*days as i64 - 1);
+            let idle = created_on < window_start && clicks.daily.iter().all(|clicks| *clicks == 0);
+            let due = fired_on.is_none_or(|fired_on| fired_on < window_start);
+            (idle && due).then(|| json!({ "days": days }))
+        }
+    }
+}
+
+fn database_error(err: Error) -> ErrorMessage {
+    tracing::error!("database error was {}", err) ;
+    ErrorMessage::new(String::from("An unexpected database error occurred"), 500)
+}
+
+// the secret is 256 random bits as hex, from two v4 uuids
+fn new_secret() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+pub async fn create_webhook_endpoint(request: WebhookEndpoint, lists: &ThreatLists, db: &Pool<Postgres>) -> Result<WebhookEndpoint, ErrorMessage> {
+    tracing::info!("create webhook endpoint was called for the workspace {}", request.workspace_id) ;
+    let url = request.url.trim();
+    if url.len() > 2048 {
+        return Err(ErrorMessage::new("the endpoint url can be at most 2048 characters".to_string(), 400))
+    }
+    // the same screening as the destinations, the notifications shouldn't be pointed at our own network
+    screen_new_url(url, lists).await?;
+    let count = sqlx::query_scalar::<_, i64>("select COUNT(*) from webhook_endpoints where workspace_id=$1")
+        .bind(request.workspace_id).fetch_one(db).await.map_err(database_error)?;
+    if count >= MAX_ENDPOINTS_PER_WORKSPACE {
+        return Err(ErrorMessage::new(format!("a workspace can have at most {} webhook endpoints", MAX_ENDPOINTS_PER_WORKSPACE), 400))
+    }
+    let secret = new_secret();
+    let endpoint = sqlx::query_as::<_, WebhookEndpointModel>("insert into webhook_endpoints (user_id, workspace_id, url, secret) values ($1, $2, $3, $4) \
+        RETURNING id, user_id, workspace_id, url, created_at")
+        .bind(request.user_id).bind(request.workspace_id).bind(url).bind(&secret).fetch_one(db).await.map_err(database_error)?;
+    Ok(WebhookEndpoint { secret, ..WebhookEndpoint::from(endpoint) })
+}
+
+pub async fn get_webhook_endpoints(workspace_id: i32, db: &Pool<Postgres>) -> Result<Vec<WebhookEndpoint>, ErrorMessage> {
+    tracing::info!("get webhook endpoints was called for the workspace {}", workspace_id) ;
+    let endpoints = sqlx::query_as::<_, WebhookEndpointModel>("select id, user_id, workspace_id, url, created_at from webhook_endpoints where workspace_id=$1 ORDER BY id")
+        .bind(workspace_id).fetch_all(db).await.map_err(database_error)?;
+    Ok(endpoints.into_iter().map(WebhookEndpoint::from).collect())
+}
+
+pub async fn delete_webhook_endpoint(id: i32, workspace_id: i32, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+    tracing::info!("delete webhook endpoint was called with the id {}", id) ;
+    // its deliveries go along with it through the cascade
+    let result = sqlx::query("DELETE FROM webhook_endpoints where id=$1 AND workspace_id=$2")
+        .bind(id).bind(workspace_id).execute(db).await.map_err(database_error)?;
+    if result.rows_affected() == 0 {
+        return Err(ErrorMessage::new("Webhook endpoint doesn't exists".to_string(), 404))
+    }
+    Ok(true)
+}
+
+pub async fn create_webhook_rule(request: WebhookRule, db: &Pool<Postgres>) -> Result<WebhookRule, ErrorMessage> {
+    tracing::info!("create webhook rule was called for the workspace {}", request.workspace_id) ;
+    let kind = RuleKind::parse(&request.rule_type, request.threshold, request.spike_factor, request.days)?;
+    let url_id = if request.url_id == 0 { None } else { Some(request.url_id) };
+    if let Some(url_id) = url_id {
+        let url = sqlx::query_scalar::<_, i32>("select id from website_urls where id=$1 AND workspace_id=$2")
+            .bind(url_id).bind(request.workspace_id).fetch_optional(db).await.map_err(database_error)?;
+        if url.is_none() {
+            return Err(ErrorMessage::new("Shorten url doesn't exists".to_string(), 404))
+        }
+    }
+    let count = sqlx::query_scalar::<_, i64>("select COUNT(*) from webhook_rules where workspace_id=$1")
+        .bind(request.workspace_id).fetch_one(db).await.map_err(database_error)?;
+    if count >= MAX_RULES_PER_WORKSPACE {
+        return Err(ErrorMessage::new(format!("a workspace can have at most {} webhook rules", MAX_RULES_PER_WORKSPACE), 400))
+    }
+
+    // only the columns of the rule's type are stored
+    let (threshold, spike_factor, days) = match kind {
+        RuleKind::ClickThreshold(threshold) => (Some(threshold), None, None),
+        RuleKind::Spike { factor, days } => (None, Some(factor), Some(days)),
+        RuleKind::NoClicks(days) => (None, None, Some(days)),
+    };
+    let rule = sqlx::query_as::<_, WebhookRuleModel>("insert into webhook_rules (user_id, workspace_id, url_id, rule_type, threshold, spike_factor, days) \
+        values ($1, $2, $3, $4, $5, $6, $7) RETURNING id, user_id, workspace_id, url_id, rule_type, threshold, spike_factor, days, created_at")
+        .bind(request.user_id).bind(request.workspace_id).bind(url_id).bind(&request.rule_type).bind(threshold).bind(spike_factor).bind(days)
+        .fetch_one(db).await.map_err(database_error)?;
+    Ok(WebhookRule::from(rule))
+}
+
+pub async fn get_webhook_rules(workspace_id: i32, db: &Pool<Postgres>) -> Result<Vec<WebhookRule>, ErrorMessage> {
+    tracing::info!("get webhook rules was called for the workspace {}", workspace_id) ;
+    let rules = sqlx::query_as::<_, WebhookRuleModel>("select id, user_id, workspace_id, url_id, rule_type, threshold, spike_factor, days, created_at \
+        from webhook_rules where workspace_id=$1 ORDER BY id")
+        .bind(workspace_id).fetch_all(db).await.map_err(database_error)?;
+    Ok(rules.into_iter().map(WebhookRule::from).collect())
+}
+
+pub async fn delete_webhook_rule(id: i32, workspace_id: i32, db: &Pool<Postgres>) -> Result<bool, ErrorMessage> {
+    tracing::info!("delete webhook rule was called with the id {}", id) ;
+    let result = sqlx::query("DELETE FROM webhook_rules where id=$1 AND workspace_id=$2")
+        .bind(id).bind(workspace_id).execute(db).await.map_err(database_error)?;
+    if result.rows_affected() == 0 {
+        return Err(ErrorMessage::new("Webhook rule doesn't exists".to_string(), 404))
+    }
+    Ok(true)
+}
+
+pub async fn get_webhook_deliveries(workspace_id: i32, db: &Pool<Postgres>) -> Result<Vec<WebhookDelivery>, ErrorMessage> {
+    tracing::info!("get webhook deliveries was called for the workspace {}", workspace_id) ;
+    let deliveries = sqlx::query_as::<_, WebhookDeliveryModel>("select d.id, d.endpoint_id, d.rule_id, d.event_type, d.payload, d.status, \
+        d.attempts, d.response_status, d.last_error, d.created_at, d.delivered_at from webhook_deliveries d \
+        JOIN webhook_endpoints e ON e.id = d.endpoint_id where e.workspace_id=$1 ORDER BY d.id DESC LIMIT $2")
+        .bind(workspace_id).bind(DELIVERY_LOG_SIZE).fetch_all(db).await.map_err(database_error)?;
+    Ok(deliveries.into_iter().map(WebhookDelivery::from).collect())
+}
+
+// the clicks the rule needs, the days come from the rollups and the raw insights like the analytics
+async fn link_clicks(kind: &RuleKind, target: &WebhookRuleTarget, today: NaiveDate, client: &DynamoClient, db: &Pool<Postgres>) -> Result<LinkClicks, ErrorMessage> {
+    let total = target.view_count as i64;
+    if kind.days() == 0 {
+        return Ok(LinkClicks { total, daily: Vec::new() })
+    }
+    let from = today - chrono::Duration::days(kind.days() - 1);
+    let link = (target.url_id, target.shorten_url.clone(), target.domain.clone(), target.rolled_up_until);
+    let days = link_days(&link, from, today, client, db).await?;
+    let daily = from.iter_days().take_while(|day| *day <= today)
+        .map(|day| days.get(&day).map(|aggregate| aggregate.clicks).unwrap_or_default())
+        .collect();
+    Ok(LinkClicks { total, daily })
+}
+
+// records that the rule fired for the link and queues a delivery to every endpoint of the workspace, together
+async fn fire(target: &WebhookRuleTarget, details: Value, today: NaiveDate, db: &Pool<Postgres>) -> Result<(), ErrorMessage> {
+    let payload = json!({
+        "event": target.rule_type,
+        "rule_id": target.rule_id,
+        "url_id": target.url_id,
+        "shorten_url": target.shorten_url,
+        "domain": target.domain,
+        "occurred_at": Utc::now().to_rfc3339(),
+        "details": details,
+    }).to_string();
+    let mut transaction = db.begin().await.map_err(database_error)?;
+    sqlx::query("insert into webhook_rule_firings (rule_id, url_id, fired_on) values ($1, $2, $3) \
+        ON CONFLICT (rule_id, url_id) DO UPDATE SET fired_on=$3")
+        .bind(target.rule_id).bind(target.url_id).bind(today).execute(&mut *transaction).await.map_err(database_error)?;
+    sqlx::query("insert into webhook_deliveries (endpoint_id, rule_id, event_type, payload) \
+        select id, $1, $2, $3 from webhook_endpoints where workspace_id=$4")
+        .bind(target.rule_id).bind(&target.rule_type).bind(payload).bind(target.workspace_id)
+        .execute(&mut *transaction).await.map_err(database_error)?;
+    transaction.commit().await.map_err(database_error)
+}
+
+// goes through every rule and the links it watches, returns how many notifications were queued
+pub async fn evaluate_rules(client: &DynamoClient, db: &Pool<Postgres>) -> Result<u64, ErrorMessage> {
+    let today = now().date();
+    let mut fired = 0;
+    let (mut last_rule, mut last_url) = (0, 0);
+    loop {
+        // the rules of the workspaces without an endpoint are skipped, nobody would get their notifications
+        let targets = sqlx::query_as::<_, WebhookRuleTarget>("select r.id AS rule_id, r.workspace_id, r.rule_type, r.threshold, r.spike_factor, r.days, \
+            w.id AS url_id, w.shorten_url, d.domain, w.rolled_up_until, w.view_count, w.created_at, f.fired_on \
+            from webhook_rules r JOIN website_urls w ON w.workspace_id = r.workspace_id AND (r.url_id IS NULL OR w.id = r.url_id) \
+            LEFT JOIN custom_domains d ON d.id = w.domain_id \
+            LEFT JOIN webhook_rule_firings f ON f.rule_id = r.id AND f.url_id = w.id \
+            where EXISTS (select 1 from webhook_endpoints e where e.workspace_id = r.workspace_id) AND (r.id, w.id) > ($1, $2) \
+            ORDER BY r.id, w.id LIMIT $3")
+            .bind(last_rule).bind(last_url).bind(RULES_BATCH_SIZE).fetch_all(db).await.map_err(database_error)?;
+        let Some(last) = targets.last() else { break };
+        (last_rule, last_url) = (last.rule_id, last.url_id);
+
+        for target in targets {
+            let result = async {
+                let kind = RuleKind::of_target(&target)?;
+                let clicks = link_clicks(&kind, &target, today, client, db).await?;
+                match evaluate(&kind, &clicks, target.created_at.date(), target.fired_on, today) {
+                    Some(details) => fire(&target, details, today, db).await.map(|_| 1),
+                    None => Ok(0)
+                }
+            }.await;
+            match result {
+                Ok(count) => fired += count,
+                Err(err) => tracing::error!("unable to evaluate the webhook rule {} for {} : {}", target.rule_id, target.shorten_url, err.message)
+            }
+        }
+    }
+    Ok(fired)
+}
+
+// the hex HMAC-SHA256 of "<timestamp>.<payload>" with the endpoint's secret, the receivers recompute it to check
+// the payload came from us, the timestamp is signed along so an old payload can't be replayed
+pub fn signature(secret: &str, timestamp: i64, payload: &str) -> String {
+    // HMAC takes a key of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(format!("{}.{}", timestamp, payload).as_bytes());
+    let hex = mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect::<String>();
+    format!("sha256={}", hex)
+}
+
+// reqwest keeps the reason (a refused address, a tls failure) in the sources of its error
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message = format!("{} : {}", message, cause);
+        source = cause.source();
+    }
+    message
+}
+
+#[derive(Debug)]
+pub struct WebhookSender {
+    client: reqwest::Client,
+}
+
+impl WebhookSender {
+    pub fn new(timeout: Duration) -> Self {
+        // the endpoint was screened when it was added, but its name can resolve elsewhere by now and a redirect
+        // could point anywhere, so the addresses are checked on every connection and the redirects aren't followed
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(PublicResolver))
+            .user_agent("SnipSightWebhooks/1.0 (+https://web.snipsight.phani.services)")
+            .build()
+            .expect("unable to build the http client");
+        Self { client }
+    }
+
+    // posts the payload, returns the response status. anything but a 2xx is a failure, with the status when there was one
+    pub async fn send(&self, delivery: &PendingWebhookDelivery) -> Result<u16, (Option<u16>, String)> {
+        let timestamp = Utc::now().timestamp();
+        let response = self.client.post(&delivery.url)
+            .header("content-type", "application/json")
+            .header("x-snipsight-event", &delivery.event_type)
+            .header("x-snipsight-delivery", delivery.id.to_string())
+            .header("x-snipsight-timestamp", timestamp.to_string())
+            .header("x-snipsight-signature", signature(&delivery.secret, timestamp, &delivery.payload))
+            .body(delivery.payload.clone())
+            .send().await;
+        match response {
+            Ok(response) if response.status().is_success() => Ok(response.status().as_u16()),
+            Ok(response) => Err((Some(response.status().as_u16()), format!("the endpoint responded with {}", response.status()))),
+            Err(err) if err.is_timeout() => Err((None, "the endpoint timed out".to_string())),
+            Err(err) => Err((None, error_chain(&err))),
+        }
+    }
+}
+
+// sends the pending deliveries which are due, returns how many were delivered and how many failed.
+// the rows are claimed first by moving their next attempt past the lease, so the posts are sent without
+// holding a transaction and more than one instance of the service can send them
+pub async fn deliver_pending(sender: &WebhookSender, db: &Pool<Postgres>) -> Result<(u64, u64), Error> {
+    let mut deliveries = sqlx::query_as::<_, PendingWebhookDelivery>("WITH due AS (select id from webhook_deliveries \
+            where status = 'pending' AND next_attempt_at <= NOW() ORDER BY id LIMIT $1 FOR UPDATE SKIP LOCKED) \
+        update webhook_deliveries d SET next_attempt_at = NOW() + ($2 * INTERVAL '1 second') FROM due, webhook_endpoints e \
+        where d.id = due.id AND e.id = d.endpoint_id RETURNING d.id, e.url, e.secret, d.event_type, d.payload, d.attempts")
+        .bind(DELIVERY_BATCH_SIZE).bind(DELIVERY_LEASE.as_secs() as i32).fetch_all(db).await?;
+    deliveries.sort_by_key(|delivery| delivery.id);
+
+    let (mut delivered, mut failed) = (0, 0);
+    for delivery in &deliveries {
+        match sender.send(delivery).await {
+            Ok(status) => {
+                sqlx::query("update webhook_deliveries SET status='delivered', attempts=attempts+1, response_status=$1, last_error=NULL, \
+                    delivered_at=NOW() where id=$2")
+                    .bind(status as i32).bind(delivery.id).execute(db).await?;
+                delivered += 1;
+            },
+            Err((status, err)) => {
+                let attempts = delivery.attempts + 1;
+                let delay = retry_delay(delivery.attempts);
+                let state = if attempts >= MAX_DELIVERY_ATTEMPTS { "failed" } else { "pending" };
+                tracing::warn!("unable to deliver the webhook {} (attempt {}), it is {} : {}", delivery.id, attempts, state, err) ;
+                let err = err.chars().take(MAX_ERROR_LENGTH).collect::<String>();
+                sqlx::query("update webhook_deliveries SET status=$1, attempts=$2, response_status=$3, last_error=$4, \
+                    next_attempt_at=NOW() + ($5 * INTERVAL '1 second') where id=$6")
+                    .bind(state).bind(attempts).bind(status.map(|status| status as i32)).bind(err).bind(delay.as_secs() as i32).bind(delivery.id)
+                    .execute(db).await?;
+                failed += 1;
+            }
+        }
+    }
+    Ok((delivered, failed))
+}
+
+pub async fn run_webhook_rules(client: Arc<DynamoClient>, db: Arc<Pool<Postgres>>, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        match evaluate_rules(&client, &db).await {
+            Ok(0) => {},
+            Ok(fired) => tracing::info!("{} webhook notifications were queued", fired),
+            Err(err) => tracing::error!("unable to evaluate the webhook rules {}", err.message)
+        }
+    }
+}
+
+pub async fn run_webhook_deliveries(sender: Arc<WebhookSender>, db: Arc<Pool<Postgres>>, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        match deliver_pending(&sender, &db).await {
+            Ok((0, 0)) => {},
+            Ok((delivered, failed)) => tracing::info!("{} webhooks were delivered, {} failed", delivered, failed),
+            Err(err) => tracing::error!("unable to deliver the webhooks {}", err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[test]
+    fn rules_are_validated() {
+        assert_eq!(RuleKind::parse(CLICK_THRESHOLD, 1000, 0.0, 0).unwrap(), RuleKind::ClickThreshold(1000));
+        assert_eq!(RuleKind::parse(SPIKE, 0, 3.0, 7).unwrap(), RuleKind::Spike { factor: 3.0, days: 7 });
+        assert_eq!(RuleKind::parse(NO_CLICKS, 0, 0.0, 14).unwrap(), RuleKind::NoClicks(14));
+        assert!(RuleKind::parse(CLICK_THRESHOLD, 0, 0.0, 0).is_err());
+        assert!(RuleKind::parse(SPIKE, 0, 1.0, 7).is_err());
+        assert!(RuleKind::parse(SPIKE, 0, 3.0, 0).is_err());
+        assert!(RuleKind::parse(NO_CLICKS, 0, 0.0, 365).is_err());
+        assert!(RuleKind::parse("sometimes", 0, 0.0, 0).is_err());
+    }
+
+    #[test]
+    fn thresholds_fire_once() {
+        let kind = RuleKind::ClickThreshold(1000);
+        let clicks = |total| LinkClicks { total, daily: Vec::new() };
+        assert!(evaluate(&kind, &clicks(999), day(1), None, day(10)).is_none());
+        assert_eq!(evaluate(&kind, &clicks(1000), day(1), None, day(10)).unwrap()["clicks"], 1000);
+        assert!(evaluate(&kind, &clicks(5000), day(1), Some(day(9)), day(10)).is_none());
+    }
+
+    #[test]
+    fn spikes_are_measured_against_the_trailing_average() {
+        let kind = RuleKind::Spike { factor: 3.0, days: 3 };
+        let clicks = |daily: &[i64]| LinkClicks { total: 0, daily: daily.to_vec() };
+        assert!(evaluate(&kind, &clicks(&[10, 10, 10, 29]), day(1), None, day(10)).is_none());
+        let details = evaluate(&kind, &clicks(&[10, 10, 10, 30]), day(1), None, day(10)).unwrap();
+        assert_eq!((details["clicks_today"].as_i64(), details["trailing_average"].as_f64()), (Some(30), Some(10.0)));
+        // already notified today
+        assert!(evaluate(&kind, &clicks(&[10, 10, 10, 30]), day(1), Some(day(10)), day(10)).is_none());
+        // a handful of clicks on a quiet link is not a spike
+        assert!(evaluate(&kind, &clicks(&[0, 0, 0, 9]), day(1), None, day(10)).is_none());
+        assert!(evaluate(&kind, &clicks(&[0, 0, 0, 10]), day(1), None, day(10)).is_some());
+    }
+
+    #[test]
+    fn idle_links_are_repeated_every_window() {
+        let kind = RuleKind::NoClicks(3);
+        let idle = LinkClicks { total: 10, daily: vec![0, 0, 0] };
+        assert!(evaluate(&kind, &idle, day(1), None, day(10)).is_some());
+        assert!(evaluate(&kind, &LinkClicks { total: 10, daily: vec![0, 1, 0] }, day(1), None, day(10)).is_none());
+        // a link created inside the window hasn't had the chance yet
+        assert!(evaluate(&kind, &idle, day(8), None, day(10)).is_none());
+        assert!(evaluate(&kind, &idle, day(1), Some(day(8)), day(10)).is_none());
+        assert!(evaluate(&kind, &idle, day(1), Some(day(7)), day(10)).is_some());
+    }
+
+    #[test]
+    fn signatures_cover_the_timestamp_and_payload() {
+        // what a receiver computes with the secret, e.g. python's hmac.new(secret, f"{timestamp}.{body}", sha256)
+        assert_eq!(signature("s3cret", 1_700_000_000, r#"{"event":"spike"}"#), "sha256=6627854ba2ffe0af6988edc6776c1a996edff371448f9a92f96dec2d7144b5a9");
+        assert_ne!(signature("secret", 1, "{}"), signature("secret", 2, "{}"));
+        assert_ne!(signature("secret", 1, "{}"), signature("other", 1, "{}"));
+    }
+
+    // a local receiver, it answers with the status of the path and keeps the requests it got
+    async fn receiver(requests: Arc<Mutex<Vec<String>>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let requests = requests.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buffer = [0u8; 4096];
+                    // the headers and the body, upto the content length
+                    loop {
+                        let read = socket.read(&mut buffer).await.unwrap();
+                        request.extend_from_slice(&buffer[..read]);
+                        let text = String::from_utf8_lossy(&request).to_string();
+                        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                            let length = head.lines().find_map(|line| line.to_lowercase().strip_prefix("content-length: ").map(|length| length.parse::<usize>().unwrap()));
+                            if read == 0 || body.len() >= length.unwrap_or_default() {
+                                break
+                            }
+                        }
+                    }
+                    let request = String::from_utf8_lossy(&request).to_string();
+                    let status = if request.starts_with("POST /broken") { "500 Internal Server Error" } else { "204 No Content" };
+                    requests.lock().unwrap().push(request);
+                    let response = format!("HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+        format!("http://{}", address)
+    }
+
+    fn delivery(url: String) -> PendingWebhookDelivery {
+        PendingWebhookDelivery {
+            id: 42,
+            url,
+            secret: "s3cret".to_string(),
+            event_type: CLICK_THRESHOLD.to_string(),
+            payload: r#"{"event":"click_threshold","url_id":7}"#.to_string(),
+            attempts: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn deliveries_are_signed() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let server = receiver(requests.clone()).await;
+        let sender = WebhookSender::new(Duration::from_secs(2));
+        assert_eq!(sender.send(&delivery(format!("{}/hooks", server))).await.unwrap(), 204);
+
+        let request = requests.lock().unwrap()[0].clone();
+        let header = |name: &str| request.lines().find_map(|line| line.strip_prefix(&format!("{}: ", name)).map(str::to_string)).unwrap();
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        assert!(head.starts_with("POST /hooks"));
+        assert_eq!((header("x-snipsight-event").as_str(), header("x-snipsight-delivery").as_str()), (CLICK_THRESHOLD, "42"));
+        let timestamp = header("x-snipsight-timestamp").parse::<i64>().unwrap();
+        assert_eq!(header("x-snipsight-signature"), signature("s3cret", timestamp, body));
+        assert_eq!(body, delivery(server).payload);
+    }
+
+    #[tokio::test]
+    async fn failed_deliveries_report_the_status() {
+        let server = receiver(Arc::new(Mutex::new(Vec::new()))).await;
+        let sender = WebhookSender::new(Duration::from_secs(2));
+        let (status, err) = sender.send(&delivery(format!("{}/broken", server))).await.unwrap_err();
+        assert_eq!(status, Some(500));
+        assert!(err.contains("500"));
+
+        // binding and dropping, so nothing listens on the port anymore
+        let address = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+        assert_eq!(sender.send(&delivery(format!("http://{}/", address))).await.unwrap_err().0, None);
+    }
+
+    #[tokio::test]
+    async fn endpoints_resolving_to_private_addresses_are_refused() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let server = receiver(requests.clone()).await;
+        let sender = WebhookSender::new(Duration::from_secs(2));
+        let (status, err) = sender.send(&delivery(server.replace("127.0.0.1", "localhost"))).await.unwrap_err();
+        assert_eq!(status, None);
+        assert!(err.contains("resolves to the private address"), "{}", err);
+        assert!(requests.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::Deserialize;
+use crate::models::ErrorMessage;
+
+// the cached roles are dropped once there are this many, the expired ones first
+const MAX_CACHED_ROLES: usize = 10_000;
+
+// the roles of a workspace member, each one can do everything the ones before it can
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Viewer,
+    Editor,
+    Owner,
+}
+
+impl Role {
+    pub fn parse(role: &str) -> Option<Role> {
+        match role {
+            "viewer" => Some(Role::Viewer),
+            "editor" => Some(Role::Editor),
+            "owner" => Some(Role::Owner),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Editor => "editor",
+            Role::Owner => "owner",
+        }
+    }
+}
+
+// the members live in the authentication service, the lookup is injected so tests can stub them
+#[tonic::async_trait]
+pub trait MembershipLookup: Send + Sync + std::fmt::Debug {
+    // None when the user is not a member of the workspace
+    async fn role(&self, workspace_id: i32, user_id: i32) -> Result<Option<Role>, String>;
+    // the user who made the workspace, the premium features come with their plan
+    async fn owner(&self, workspace_id: i32) -> Result<i32, String>;
+}
+
+// the role along with when it was looked up
+type CachedRole = (Option<Role>, Instant);
+
+// the roles (and the missing memberships) are kept for a while, so a removed member loses the access within the ttl
+#[derive(Debug)]
+pub struct RoleCache {
+    ttl: Duration,
+    roles: Mutex<HashMap<(i32, i32), CachedRole>>,
+}
+
+impl RoleCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, roles: Mutex::new(HashMap::new()) }
+    }
+
+    // the outer None is a miss, the inner one a cached missing membership
+    pub fn get(&self, workspace_id: i32, user_id: i32) -> Option<Option<Role>> {
+        let roles = self.roles.lock().unwrap();
+        roles.get(&(workspace_id, user_id)).filter(|(_, at)| at.elapsed() < self.ttl).map(|(role, _)| *role)
+    }
+
+    pub fn insert(&self, workspace_id: i32, user_id: i32, role: Option<Role>) {
+        let mut roles = self.roles.lock().unwrap();
+        if roles.len() >= MAX_CACHED_ROLES {
+            roles.retain(|_, (_, at)| at.elapsed() < self.ttl);
+            if roles.len() >= MAX_CACHED_ROLES {
+                roles.clear();
+            }
+        }
+        roles.insert((workspace_id, user_id), (role, Instant::now()));
+    }
+}
+
+#[derive(Deserialize)]
+struct WorkspaceRole {
+    role: String,
+}
+
+#[derive(Deserialize)]
+struct WorkspaceOwner {
+    created_by: i32,
+}
+
+#[derive(Debug)]
+pub struct HttpMembershipLookup {
+    client: reqwest::Client,
+    authentication_url: String,
+    cache: RoleCache,
+}
+
+impl HttpMembershipLookup {
+    pub fn new(authentication_url: String, ttl: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .expect("unable to build the http client");
+        Self { client, authentication_url, cache: RoleCache::new(ttl) }
+    }
+
+    pub fn from_env(ttl: Duration) -> Self {
+        let authentication_url = std::env::var("AUTHENTICATION_URL").ok().filter(|url| !url.is_empty())
+            .unwrap_or("http://authentication-container:9090".to_string());
+        Self::new(authentication_url, ttl)
+    }
+}
+
+#[tonic::async_trait]
+impl MembershipLookup for HttpMembershipLookup {
+    async fn role(&self, workspace_id: i32, user_id: i32) -> Result<Option<Role>, String> {
+        if let Some(role) = self.cache.get(workspace_id, user_id) {
+            return Ok(role)
+        }
+        let response = self.client.get(format!("{}/workspace-role/{}/{}", self.authentication_url, workspace_id, user_id))
+            .send().await.map_err(|err| err.to_string())?;
+        let role = if response.status() == reqwest::StatusCode::NOT_FOUND {
+            None
+        } else {
+            let body = response.error_for_status().map_err(|err| err.to_string())?.text().await.map_err(|err| err.to_string())?;
+            let role = serde_json::from_str::<WorkspaceRole>(&body).map_err(|err| err.to_string())?.role;
+            Some(Role::parse(&role).ok_or(format!("unknown role {}", role))?)
+        };
+        self.cache.insert(workspace_id, user_id, role);
+        Ok(role)
+    }
+
+    async fn owner(&self, workspace_id: i32) -> Result<i32, String> {
+        let response = self.client.get(format!("{}/workspace-owner/{}", self.authentication_url, workspace_id))
+            .send().await.map_err(|err| err.to_string())?;
+        let body = response.error_for_status().map_err(|err| err.to_string())?.text().await.map_err(|err| err.to_string())?;
+        Ok(serde_json::from_str::<WorkspaceOwner>(&body).map_err(|err| err.to_string())?.created_by)
+    }
+}
+
+// the workspaces the user isn't a member of look the same as the ones which don't exist
+pub async fn authorize(workspace_id: i32, user_id: i32, needed: Role, lookup: &dyn MembershipLookup) -> Result<Role, ErrorMessage> {
+    let role = lookup.role(workspace_id, user_id).await.map_err(|err| {
+        tracing::error!("unable to get the role of the user {} in the workspace {} : {}", user_id, workspace_id, err) ;
+        ErrorMessage::new("Unable to check the workspace membership".to_string(), 500)
+    })?;
+    match role {
+        Some(role) if role >= needed => Ok(role),
+        Some(role) => {
+            tracing::warn!("user {} is a {} of the workspace {}, {} was needed", user_id, role.as_str(), workspace_id, needed.as_str()) ;
+            Err(ErrorMessage::new(format!("A {} of the workspace can't do this", role.as_str()), 403))
+        },
+        None => {
+            tracing::warn!("user {} is not a member of the workspace {}", user_id, workspace_id) ;
+            Err(ErrorMessage::new("Workspace doesn't exists".to_string(), 404))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct StubLookup(Option<Role>);
+
+    #[tonic::async_trait]
+    impl MembershipLookup for StubLookup {
+        async fn role(&self, _workspace_id: i32, _user_id: i32) -> Result<Option<Role>, String> {
+            Ok(self.0)
+        }
+
+        async fn owner(&self, workspace_id: i32) -> Result<i32, String> {
+            Ok(workspace_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn roles_are_checked_against_the_needed_one() {
+        assert_eq!(authorize(1, 7, Role::Viewer, &StubLookup(Some(Role::Viewer))).await.unwrap(), Role::Viewer);
+        assert!(authorize(1, 7, Role::Editor, &StubLookup(Some(Role::Owner))).await.is_ok());
+        assert_eq!(authorize(1, 7, Role::Editor, &StubLookup(Some(Role::Viewer))).await.unwrap_err().status_code, 403);
+        assert_eq!(authorize(1, 7, Role::Viewer, &StubLookup(None)).await.unwrap_err().status_code, 404);
+    }
+
+    #[test]
+    fn cached_roles_expire() {
+        let cache = RoleCache::new(Duration::from_secs(60));
+        assert_eq!(cache.get(1, 7), None);
+        cache.insert(1, 7, Some(Role::Editor));
+        cache.insert(2, 7, None);
+        assert_eq!(cache.get(1, 7), Some(Some(Role::Editor)));
+        assert_eq!(cache.get(2, 7), Some(None));
+
+        let cache = RoleCache::new(Duration::ZERO);
+        cache.insert(1, 7, Some(Role::Owner));
+        assert_eq!(cache.get(1, 7), None);
+    }
+}